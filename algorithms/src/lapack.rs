@@ -1,10 +1,21 @@
 //! Interface to Lapack routines
+pub mod cholesky_decomp;
+pub mod eigenvalue_decomp;
+pub mod ldl_decomp;
 pub mod lu_decomp;
+pub mod matrix_equations;
+pub mod matrix_functions;
+pub mod pivoted_qr;
+pub mod qr_decomp;
+pub mod schur_decomp;
+pub mod svd;
 pub use lapacke::Layout;
 pub use rlst_common::types::{IndexType, RlstError, RlstResult};
 use rlst_dense::types::Scalar;
 use rlst_dense::{
-    DataContainerMut, GenericBaseMatrixMut, LayoutType, MatrixTraitMut, SizeIdentifier,
+    DataContainer, DataContainerMut, DefaultLayout, Dynamic, GenericBaseMatrix,
+    GenericBaseMatrixMut, LayoutType, MatrixD, MatrixTraitMut, RandomAccessByValue, SizeIdentifier,
+    SliceContainerMut, SliceMatrixMut,
 };
 use std::marker::PhantomData;
 
@@ -16,6 +27,22 @@ pub enum TransposeMode {
     ConjugateTrans = b'C',
 }
 
+/// The matrix norm used for condition number estimates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum NormType {
+    One = b'1',
+    Infinity = b'I',
+}
+
+/// The triangle of a symmetric or Hermitian matrix that is referenced and overwritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TriangularType {
+    Upper = b'U',
+    Lower = b'L',
+}
+
 pub struct LapackData<
     Item: Scalar,
     RS: SizeIdentifier,
@@ -33,6 +60,65 @@ pub fn check_lapack_stride(dim: (IndexType, IndexType), stride: (IndexType, Inde
     stride.0 == 1 && stride.1 >= std::cmp::max(1, dim.0)
 }
 
+/// Return the dimension of a square matrix of dimension `dim`, or an error otherwise.
+pub(crate) fn check_square(dim: (IndexType, IndexType)) -> RlstResult<IndexType> {
+    if dim.0 != dim.1 {
+        return Err(RlstError::SingleDimensionError {
+            expected: dim.0,
+            actual: dim.1,
+        });
+    }
+    Ok(dim.0)
+}
+
+/// Copy the referenced triangle of a Hermitian matrix into the other triangle.
+pub(crate) fn fill_hermitian<Item: Scalar>(mat: &mut MatrixD<Item>, uplo: TriangularType) {
+    let n = rlst_dense::Layout::layout(mat).dim().0;
+    for col in 0..n {
+        for row in col + 1..n {
+            match uplo {
+                TriangularType::Upper => mat[[row, col]] = mat[[col, row]].conj(),
+                TriangularType::Lower => mat[[col, row]] = mat[[row, col]].conj(),
+            }
+        }
+    }
+}
+
+/// Copy `source` into `target`, which must have the same dimension.
+pub(crate) fn copy_matrix<
+    Item: Scalar,
+    SourceData: DataContainer<Item = Item>,
+    SourceR: SizeIdentifier,
+    SourceC: SizeIdentifier,
+    TargetData: DataContainerMut<Item = Item>,
+    TargetR: SizeIdentifier,
+    TargetC: SizeIdentifier,
+>(
+    source: &GenericBaseMatrix<Item, SourceData, SourceR, SourceC>,
+    target: &mut GenericBaseMatrixMut<Item, TargetData, TargetR, TargetC>,
+) -> RlstResult<()> {
+    let dim = rlst_dense::Layout::layout(target).dim();
+    let source_dim = rlst_dense::Layout::layout(source).dim();
+    if source_dim.0 != dim.0 {
+        return Err(RlstError::SingleDimensionError {
+            expected: dim.0,
+            actual: source_dim.0,
+        });
+    }
+    if source_dim.1 != dim.1 {
+        return Err(RlstError::SingleDimensionError {
+            expected: dim.1,
+            actual: source_dim.1,
+        });
+    }
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            target[[row, col]] = source.get_value(row, col);
+        }
+    }
+    Ok(())
+}
+
 pub trait AsLapack<
     Item: Scalar,
     Data: DataContainerMut<Item = Item>,
@@ -60,3 +146,60 @@ impl<Item: Scalar, Data: DataContainerMut<Item = Item>, RS: SizeIdentifier, CS:
     AsLapack<Item, Data, RS, CS> for GenericBaseMatrixMut<Item, Data, RS, CS>
 {
 }
+
+/// Lapack data that borrows the entries of a matrix.
+pub type LapackSliceData<'a, Item, RS, CS> =
+    LapackData<Item, RS, CS, SliceMatrixMut<'a, Item, RS, CS>>;
+
+/// Borrow a matrix for Lapack routines instead of consuming it.
+///
+/// The decompositions computed from the borrowed matrix overwrite its entries, so the
+/// same storage can be reused for repeated factorizations of matrices of equal size.
+pub trait AsLapackMut<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier> {
+    fn lapack_mut(&mut self) -> RlstResult<LapackSliceData<'_, Item, RS, CS>>;
+
+    /// Run `op` on Lapack data for the matrix, independent of its stride.
+    ///
+    /// Matrices with a stride that is not compatible with Lapack, such as row-major
+    /// matrices, are copied into a column-major buffer. The entries of the buffer, e.g. the
+    /// factors of a decomposition computed by `op`, are copied back into the matrix after
+    /// `op` returns. Matrices with a compatible stride are borrowed without copying.
+    fn with_lapack<R, F>(&mut self, op: F) -> RlstResult<R>
+    where
+        F: for<'b> FnOnce(LapackSliceData<'b, Item, Dynamic, Dynamic>) -> RlstResult<R>;
+}
+
+impl<Item: Scalar, Data: DataContainerMut<Item = Item>, RS: SizeIdentifier, CS: SizeIdentifier>
+    AsLapackMut<Item, RS, CS> for GenericBaseMatrixMut<Item, Data, RS, CS>
+{
+    fn lapack_mut(&mut self) -> RlstResult<LapackSliceData<'_, Item, RS, CS>> {
+        let dim = rlst_dense::Layout::layout(self).dim();
+        let stride = rlst_dense::Layout::layout(self).stride();
+        SliceMatrixMut::<'_, Item, RS, CS>::from_data(
+            SliceContainerMut::new(self.data_mut()),
+            DefaultLayout::new(dim, stride),
+        )
+        .lapack()
+    }
+
+    fn with_lapack<R, F>(&mut self, op: F) -> RlstResult<R>
+    where
+        F: for<'b> FnOnce(LapackSliceData<'b, Item, Dynamic, Dynamic>) -> RlstResult<R>,
+    {
+        let dim = rlst_dense::Layout::layout(self).dim();
+        let stride = rlst_dense::Layout::layout(self).stride();
+        if check_lapack_stride(dim, stride) {
+            return op(SliceMatrixMut::<'_, Item, Dynamic, Dynamic>::from_data(
+                SliceContainerMut::new(self.data_mut()),
+                DefaultLayout::new(dim, stride),
+            )
+            .lapack()?);
+        }
+
+        let mut work = MatrixD::<Item>::zeros_from_dim(dim.0, dim.1);
+        copy_matrix(self, &mut work)?;
+        let result = op(work.lapack_mut()?);
+        copy_matrix(&work, self)?;
+        result
+    }
+}
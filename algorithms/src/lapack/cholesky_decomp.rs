@@ -0,0 +1,337 @@
+//! Cholesky decomposition of Hermitian positive definite matrices.
+//!
+//! The decomposition `A = U^H U` or `A = L L^H` is computed with `potrf`, which only
+//! references the chosen triangle of `A` and overwrites it with the factor. Systems are
+//! solved with `potrs` and the inverse is formed with `potri`.
+use crate::lapack::LapackData;
+use crate::traits::cholesky_decomp::CholeskyDecomp;
+use lapacke;
+use num::Zero;
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainer, DataContainerMut, GenericBaseMatrix, GenericBaseMatrixMut, Layout, LayoutType,
+    MatrixD, MatrixTraitMut, SizeIdentifier,
+};
+
+use super::{check_lapack_stride, copy_matrix, fill_hermitian, TriangularType};
+
+pub struct CholeskyDecompLapack<
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    Mat: MatrixTraitMut<Item, RS, CS> + Sized,
+> {
+    data: LapackData<Item, RS, CS, Mat>,
+    uplo: TriangularType,
+}
+
+macro_rules! cholesky_decomp_impl {
+    ($scalar:ty, $lapack_potrf:ident, $lapack_potrs:ident, $lapack_potri:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Compute the Cholesky decomposition of a Hermitian positive definite matrix,
+            /// referencing only the triangle given by `uplo`.
+            ///
+            /// Returns [RlstError::NotPositiveDefinite] with the order of the first leading
+            /// minor that is not positive if the matrix is not positive definite.
+            pub fn cholesky(
+                mut self,
+                uplo: TriangularType,
+            ) -> RlstResult<
+                CholeskyDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>,
+            > {
+                let dim = self.mat.layout().dim();
+                let stride = self.mat.layout().stride();
+
+                if dim.0 != dim.1 {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: dim.0,
+                        actual: dim.1,
+                    });
+                }
+
+                let info = unsafe {
+                    lapacke::$lapack_potrf(
+                        lapacke::Layout::ColumnMajor,
+                        uplo as u8,
+                        dim.0 as i32,
+                        self.mat.data_mut(),
+                        stride.1 as i32,
+                    )
+                };
+                if info == 0 {
+                    Ok(CholeskyDecompLapack { data: self, uplo })
+                } else if info > 0 {
+                    Err(RlstError::NotPositiveDefinite(info as IndexType))
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            CholeskyDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Recompute the decomposition for a new matrix of the same dimension.
+            ///
+            /// The matrix is copied into the storage of the decomposition, so that no
+            /// memory is allocated. If the factorization fails the decomposition must not be
+            /// used before a successful refactorization.
+            pub fn refactor<
+                AData: DataContainer<Item = $scalar>,
+                AR: SizeIdentifier,
+                AC: SizeIdentifier,
+            >(
+                &mut self,
+                mat: &GenericBaseMatrix<$scalar, AData, AR, AC>,
+            ) -> RlstResult<()> {
+                copy_matrix(mat, &mut self.data.mat)?;
+
+                let n = self.data.mat.layout().dim().0;
+                let lda = self.data.mat.layout().stride().1;
+                let info = unsafe {
+                    lapacke::$lapack_potrf(
+                        lapacke::Layout::ColumnMajor,
+                        self.uplo as u8,
+                        n as i32,
+                        self.data.mat.data_mut(),
+                        lda as i32,
+                    )
+                };
+                if info == 0 {
+                    Ok(())
+                } else if info > 0 {
+                    Err(RlstError::NotPositiveDefinite(info as IndexType))
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            CholeskyDecomp
+            for CholeskyDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            type T = $scalar;
+
+            fn data(&self) -> &[Self::T] {
+                self.data.mat.data()
+            }
+
+            fn dim(&self) -> (IndexType, IndexType) {
+                self.data.mat.dim()
+            }
+
+            fn triangular_type(&self) -> TriangularType {
+                self.uplo
+            }
+
+            fn factor(&self) -> MatrixD<Self::T> {
+                let n = self.dim().0;
+                let mat = &self.data.mat;
+                let mut factor = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                for col in 0..n {
+                    for row in 0..n {
+                        let in_triangle = match self.uplo {
+                            TriangularType::Upper => row <= col,
+                            TriangularType::Lower => row >= col,
+                        };
+                        if in_triangle {
+                            factor[[row, col]] = mat[[row, col]];
+                        }
+                    }
+                }
+                factor
+            }
+
+            fn solve<
+                RhsData: DataContainerMut<Item = Self::T>,
+                RhsR: SizeIdentifier,
+                RhsC: SizeIdentifier,
+            >(
+                &self,
+                rhs: &mut GenericBaseMatrixMut<Self::T, RhsData, RhsR, RhsC>,
+            ) -> RlstResult<()> {
+                if !check_lapack_stride(rhs.layout().dim(), rhs.layout().stride()) {
+                    return Err(RlstError::IncompatibleStride);
+                }
+                let n = self.dim().0;
+                if rhs.layout().dim().0 != n {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: n,
+                        actual: rhs.layout().dim().0,
+                    });
+                }
+
+                let mat = &self.data.mat;
+                let ldb = rhs.layout().stride().1;
+                let nrhs = rhs.layout().dim().1;
+                let info = unsafe {
+                    lapacke::$lapack_potrs(
+                        lapacke::Layout::ColumnMajor,
+                        self.uplo as u8,
+                        n as i32,
+                        nrhs as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        rhs.data_mut(),
+                        ldb as i32,
+                    )
+                };
+
+                if info != 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(())
+                }
+            }
+
+            fn inverse(&self) -> RlstResult<MatrixD<Self::T>> {
+                let n = self.dim().0;
+                let mut inverse = self.factor();
+                let info = unsafe {
+                    lapacke::$lapack_potri(
+                        lapacke::Layout::ColumnMajor,
+                        self.uplo as u8,
+                        n as i32,
+                        inverse.data_mut(),
+                        std::cmp::max(1, n) as i32,
+                    )
+                };
+
+                if info != 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+                fill_hermitian(&mut inverse, self.uplo);
+                Ok(inverse)
+            }
+
+            fn log_det(&self) -> <Self::T as Scalar>::Real {
+                // The diagonal of the factor is real and positive.
+                let mat = &self.data.mat;
+                let sum = (0..self.dim().0)
+                    .fold(<<Self::T as Scalar>::Real as Zero>::zero(), |acc, index| {
+                        acc + mat[[index, index]].re().ln()
+                    });
+                sum + sum
+            }
+        }
+    };
+}
+
+cholesky_decomp_impl!(f64, dpotrf, dpotrs, dpotri);
+cholesky_decomp_impl!(f32, spotrf, spotrs, spotri);
+cholesky_decomp_impl!(c32, cpotrf, cpotrs, cpotri);
+cholesky_decomp_impl!(c64, zpotrf, zpotrs, zpotri);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix};
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    // A symmetric positive definite matrix with determinant 28.
+    fn spd_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+        let entries = [[4.0, 2.0, 0.0], [2.0, 5.0, 1.0], [0.0, 1.0, 2.0]];
+        for row in 0..3 {
+            for col in 0..3 {
+                mat[[row, col]] = entries[row][col];
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_cholesky_f64() {
+        let mat = spd_matrix();
+        for uplo in [TriangularType::Upper, TriangularType::Lower] {
+            let chol = Matrix::from_ref(&mat)
+                .eval()
+                .lapack()
+                .unwrap()
+                .cholesky(uplo)
+                .unwrap();
+
+            let mut x = MatrixD::<f64>::zeros_from_dim(3, 1);
+            x[[0, 0]] = 1.0;
+            x[[1, 0]] = -2.0;
+            x[[2, 0]] = 0.5;
+            let mut rhs = mat.dot(&x);
+            chol.solve(&mut rhs).unwrap();
+            for row in 0..3 {
+                assert_float_eq!(rhs[[row, 0]], x[[row, 0]], abs <= 1E-12);
+            }
+
+            let inverse = chol.inverse().unwrap();
+            let product = mat.dot(&inverse);
+            for col in 0..3 {
+                for row in 0..3 {
+                    let expected = if row == col { 1.0 } else { 0.0 };
+                    assert_float_eq!(product[[row, col]], expected, abs <= 1E-12);
+                }
+            }
+
+            assert_float_eq!(chol.log_det(), 28.0_f64.ln(), abs <= 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_cholesky_c64() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+        mat[[0, 0]] = c64::new(4.0, 0.0);
+        mat[[0, 1]] = c64::new(1.0, -1.0);
+        mat[[1, 0]] = c64::new(1.0, 1.0);
+        mat[[1, 1]] = c64::new(3.0, 0.0);
+
+        let chol = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .cholesky(TriangularType::Lower)
+            .unwrap();
+
+        // Check A = L L^H.
+        let factor = chol.factor();
+        for col in 0..2 {
+            for row in 0..2 {
+                let value = (0..2).fold(c64::new(0.0, 0.0), |acc, k| {
+                    acc + factor[[row, k]] * factor[[col, k]].conj()
+                });
+                assert_close(value, mat[[row, col]], 1E-12);
+            }
+        }
+
+        let inverse = chol.inverse().unwrap();
+        let product = mat.dot(&inverse);
+        for col in 0..2 {
+            for row in 0..2 {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert_close(product[[row, col]], c64::new(expected, 0.0), 1E-12);
+            }
+        }
+
+        assert_float_eq!(chol.log_det(), 10.0_f64.ln(), abs <= 1E-12);
+    }
+
+    #[test]
+    fn test_cholesky_not_positive_definite() {
+        let mut mat = spd_matrix();
+        mat[[2, 2]] = -1.0;
+
+        let result = mat.lapack().unwrap().cholesky(TriangularType::Upper);
+        assert!(matches!(result, Err(RlstError::NotPositiveDefinite(3))));
+    }
+}
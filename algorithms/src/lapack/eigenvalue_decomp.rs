@@ -0,0 +1,901 @@
+//! Eigenvalue decompositions of dense matrices.
+//!
+//! Hermitian matrices are diagonalised with the divide and conquer driver `syevd`/`heevd`,
+//! or with the MRRR driver `syevr`/`heevr` when only a selected range of eigenvalues is
+//! needed. General matrices are handled by `geev`, whose results are always returned as
+//! complex values.
+//!
+//! For the generalized problem `A v = lambda B v` with Hermitian `A` and Hermitian positive
+//! definite `B` the driver `sygvd`/`hegvd` is used. General pencils are handled by the QZ
+//! driver `ggev`, which returns the eigenvalues as pairs `(alpha, beta)` so that infinite
+//! eigenvalues with `beta = 0` can be represented.
+use crate::lapack::{check_square, LapackData};
+use crate::traits::eigenvalue_decomp::{
+    EigenvalueDecomposition, EigenvalueRange, EigenvectorMode, GeneralizedEigenvalueDecomposition,
+    HermitianEigenvalueDecomposition,
+};
+use lapacke;
+use num::Zero;
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    ColumnVectorD, DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, MatrixD,
+    SizeIdentifier,
+};
+
+use super::TriangularType;
+
+fn check_same_dimension(
+    dim_a: (IndexType, IndexType),
+    dim_b: (IndexType, IndexType),
+) -> RlstResult<IndexType> {
+    let n = check_square(dim_a)?;
+    if check_square(dim_b)? != n {
+        return Err(RlstError::SingleDimensionError {
+            expected: n,
+            actual: dim_b.0,
+        });
+    }
+    Ok(n)
+}
+
+fn to_column_vector<T: Scalar>(values: &[T]) -> ColumnVectorD<T> {
+    let mut vec = ColumnVectorD::<T>::zeros_from_length(values.len());
+    vec.data_mut().copy_from_slice(values);
+    vec
+}
+
+// Copy the leading `rows x cols` block of a column-major array with leading dimension `ld`.
+fn copy_columns<T: Scalar>(
+    data: &[T],
+    ld: IndexType,
+    rows: IndexType,
+    cols: IndexType,
+) -> MatrixD<T> {
+    let mut mat = MatrixD::<T>::zeros_from_dim(rows, cols);
+    for col in 0..cols {
+        for row in 0..rows {
+            mat[[row, col]] = data[col * ld + row];
+        }
+    }
+    mat
+}
+
+macro_rules! hermitian_eig_impl {
+    ($scalar:ty, $lapack_syevd:ident, $lapack_syevr:ident, $lapack_sygvd:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Compute all eigenvalues and optionally the eigenvectors of a Hermitian matrix,
+            /// referencing only the triangle given by `uplo`.
+            pub fn eigh(
+                mut self,
+                uplo: TriangularType,
+                mode: EigenvectorMode,
+            ) -> RlstResult<HermitianEigenvalueDecomposition<$scalar>> {
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let job = match mode {
+                    EigenvectorMode::None => b'N',
+                    _ => b'V',
+                };
+
+                let mut eigenvalues = vec![<<$scalar as Scalar>::Real as Zero>::zero(); n];
+                let info = unsafe {
+                    lapacke::$lapack_syevd(
+                        lapacke::Layout::ColumnMajor,
+                        job,
+                        uplo as u8,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        &mut eigenvalues,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Hermitian eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let eigenvectors = match mode {
+                    EigenvectorMode::None => None,
+                    _ => Some(copy_columns(self.mat.data(), lda, n, n)),
+                };
+                Ok(HermitianEigenvalueDecomposition {
+                    eigenvalues: to_column_vector(&eigenvalues),
+                    eigenvectors,
+                })
+            }
+
+            /// Compute the eigenvalues in `range` and optionally the corresponding
+            /// eigenvectors of a Hermitian matrix, referencing only the triangle given
+            /// by `uplo`.
+            pub fn eigh_range(
+                mut self,
+                uplo: TriangularType,
+                mode: EigenvectorMode,
+                range: EigenvalueRange<<$scalar as Scalar>::Real>,
+            ) -> RlstResult<HermitianEigenvalueDecomposition<$scalar>> {
+                type Real = <$scalar as Scalar>::Real;
+
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let job = match mode {
+                    EigenvectorMode::None => b'N',
+                    _ => b'V',
+                };
+
+                let zero = <Real as Zero>::zero();
+                let (range_type, vl, vu, il, iu, max_count) = match range {
+                    EigenvalueRange::All => (b'A', zero, zero, 0, 0, n),
+                    EigenvalueRange::Index(start, end) => {
+                        if start >= end || end > n {
+                            return Err(RlstError::GeneralError(format!(
+                                "Invalid eigenvalue index range {}..{} for matrix of dimension {}.",
+                                start, end, n
+                            )));
+                        }
+                        (b'I', zero, zero, start as i32 + 1, end as i32, end - start)
+                    }
+                    EigenvalueRange::Value(lower, upper) => {
+                        if lower >= upper {
+                            return Err(RlstError::GeneralError(format!(
+                                "Invalid eigenvalue interval ({}, {}].",
+                                lower, upper
+                            )));
+                        }
+                        (b'V', lower, upper, 0, 0, n)
+                    }
+                };
+
+                let ldz = std::cmp::max(1, n);
+                let mut count: i32 = 0;
+                let mut eigenvalues = vec![zero; n];
+                let mut z = vec![<$scalar as Zero>::zero(); ldz * std::cmp::max(1, max_count)];
+                let mut isuppz = vec![0; 2 * std::cmp::max(1, max_count)];
+                let info = unsafe {
+                    lapacke::$lapack_syevr(
+                        lapacke::Layout::ColumnMajor,
+                        job,
+                        range_type,
+                        uplo as u8,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        vl,
+                        vu,
+                        il,
+                        iu,
+                        zero,
+                        &mut count,
+                        &mut eigenvalues,
+                        &mut z,
+                        ldz as i32,
+                        &mut isuppz,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Hermitian eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let count = count as IndexType;
+                let eigenvectors = match mode {
+                    EigenvectorMode::None => None,
+                    _ => Some(copy_columns(&z, ldz, n, count)),
+                };
+                Ok(HermitianEigenvalueDecomposition {
+                    eigenvalues: to_column_vector(&eigenvalues[..count]),
+                    eigenvectors,
+                })
+            }
+
+            /// Compute the eigenvalues and optionally the eigenvectors of the
+            /// Hermitian-definite problem `A v = lambda B v`, referencing only the triangles
+            /// of `A` and `B` given by `uplo`.
+            ///
+            /// Returns [RlstError::NotPositiveDefinite] with the order of the first leading
+            /// minor of `B` that is not positive if `B` is not positive definite.
+            pub fn eigh_generalized<
+                BData: DataContainerMut<Item = $scalar>,
+                BRS: SizeIdentifier,
+                BCS: SizeIdentifier,
+            >(
+                mut self,
+                mut b: LapackData<$scalar, BRS, BCS, GenericBaseMatrixMut<$scalar, BData, BRS, BCS>>,
+                uplo: TriangularType,
+                mode: EigenvectorMode,
+            ) -> RlstResult<HermitianEigenvalueDecomposition<$scalar>> {
+                let n = check_same_dimension(self.mat.layout().dim(), b.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let ldb = b.mat.layout().stride().1;
+                let job = match mode {
+                    EigenvectorMode::None => b'N',
+                    _ => b'V',
+                };
+
+                let mut eigenvalues = vec![<<$scalar as Scalar>::Real as Zero>::zero(); n];
+                let info = unsafe {
+                    lapacke::$lapack_sygvd(
+                        lapacke::Layout::ColumnMajor,
+                        1,
+                        job,
+                        uplo as u8,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        b.mat.data_mut(),
+                        ldb as i32,
+                        &mut eigenvalues,
+                    )
+                };
+
+                if info > n as i32 {
+                    return Err(RlstError::NotPositiveDefinite(info as IndexType - n));
+                } else if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Generalized Hermitian eigenvalue decomposition"
+                            .to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let eigenvectors = match mode {
+                    EigenvectorMode::None => None,
+                    _ => Some(copy_columns(self.mat.data(), lda, n, n)),
+                };
+                Ok(HermitianEigenvalueDecomposition {
+                    eigenvalues: to_column_vector(&eigenvalues),
+                    eigenvectors,
+                })
+            }
+        }
+    };
+}
+
+hermitian_eig_impl!(f64, dsyevd, dsyevr, dsygvd);
+hermitian_eig_impl!(f32, ssyevd, ssyevr, ssygvd);
+hermitian_eig_impl!(c32, cheevd, cheevr, chegvd);
+hermitian_eig_impl!(c64, zheevd, zheevr, zhegvd);
+
+// Return the `jobvl` and `jobvr` arguments of `geev`.
+fn geev_jobs(mode: EigenvectorMode) -> (u8, u8) {
+    match mode {
+        EigenvectorMode::None => (b'N', b'N'),
+        EigenvectorMode::Right => (b'N', b'V'),
+        EigenvectorMode::LeftAndRight => (b'V', b'V'),
+    }
+}
+
+// Expand the eigenvectors computed by the real drivers `geev` and `ggev`. A complex
+// conjugate pair of eigenvalues with positive imaginary part first is represented by the
+// real and imaginary parts of the first eigenvector in two consecutive columns.
+fn real_eigenvectors_to_complex<T: Scalar>(
+    vectors: &[T],
+    imag: &[T],
+    ld: IndexType,
+    n: IndexType,
+) -> MatrixD<T::Complex> {
+    let i = T::complex(0.0, 1.0);
+    let mut result = MatrixD::<T::Complex>::zeros_from_dim(n, n);
+    let mut col = 0;
+    while col < n {
+        if imag[col] == T::zero() {
+            for row in 0..n {
+                result[[row, col]] = vectors[col * ld + row].as_c();
+            }
+            col += 1;
+        } else {
+            for row in 0..n {
+                let re = vectors[col * ld + row].as_c();
+                let im = vectors[(col + 1) * ld + row].as_c();
+                result[[row, col]] = re + i * im;
+                result[[row, col + 1]] = re - i * im;
+            }
+            col += 2;
+        }
+    }
+    result
+}
+
+macro_rules! real_eig_impl {
+    ($scalar:ty, $complex:ty, $lapack_geev:ident, $lapack_ggev:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Compute the complex eigenvalues and optionally the eigenvectors of a
+            /// general square matrix.
+            pub fn eig(
+                mut self,
+                mode: EigenvectorMode,
+            ) -> RlstResult<EigenvalueDecomposition<$scalar>> {
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let (jobvl, jobvr) = geev_jobs(mode);
+                let ld = std::cmp::max(1, n);
+
+                let mut wr = vec![<$scalar as Zero>::zero(); n];
+                let mut wi = vec![<$scalar as Zero>::zero(); n];
+                let mut vl =
+                    vec![<$scalar as Zero>::zero(); if jobvl == b'V' { ld * n } else { 1 }];
+                let mut vr =
+                    vec![<$scalar as Zero>::zero(); if jobvr == b'V' { ld * n } else { 1 }];
+                let info = unsafe {
+                    lapacke::$lapack_geev(
+                        lapacke::Layout::ColumnMajor,
+                        jobvl,
+                        jobvr,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        &mut wr,
+                        &mut wi,
+                        &mut vl,
+                        ld as i32,
+                        &mut vr,
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let eigenvalues: Vec<$complex> = wr
+                    .iter()
+                    .zip(wi.iter())
+                    .map(|(&re, &im)| <$complex>::new(re, im))
+                    .collect();
+
+                Ok(EigenvalueDecomposition {
+                    eigenvalues: to_column_vector(&eigenvalues),
+                    left_eigenvectors: if jobvl == b'V' {
+                        Some(real_eigenvectors_to_complex(&vl, &wi, ld, n))
+                    } else {
+                        None
+                    },
+                    right_eigenvectors: if jobvr == b'V' {
+                        Some(real_eigenvectors_to_complex(&vr, &wi, ld, n))
+                    } else {
+                        None
+                    },
+                })
+            }
+
+            /// Compute the generalized eigenvalues and optionally the eigenvectors of the
+            /// problem `A v = lambda B v` with the QZ algorithm.
+            pub fn eig_generalized<
+                BData: DataContainerMut<Item = $scalar>,
+                BRS: SizeIdentifier,
+                BCS: SizeIdentifier,
+            >(
+                mut self,
+                mut b: LapackData<
+                    $scalar,
+                    BRS,
+                    BCS,
+                    GenericBaseMatrixMut<$scalar, BData, BRS, BCS>,
+                >,
+                mode: EigenvectorMode,
+            ) -> RlstResult<GeneralizedEigenvalueDecomposition<$scalar>> {
+                let n = check_same_dimension(self.mat.layout().dim(), b.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let ldb = b.mat.layout().stride().1;
+                let (jobvl, jobvr) = geev_jobs(mode);
+                let ld = std::cmp::max(1, n);
+
+                let mut alphar = vec![<$scalar as Zero>::zero(); n];
+                let mut alphai = vec![<$scalar as Zero>::zero(); n];
+                let mut beta = vec![<$scalar as Zero>::zero(); n];
+                let mut vl =
+                    vec![<$scalar as Zero>::zero(); if jobvl == b'V' { ld * n } else { 1 }];
+                let mut vr =
+                    vec![<$scalar as Zero>::zero(); if jobvr == b'V' { ld * n } else { 1 }];
+                let info = unsafe {
+                    lapacke::$lapack_ggev(
+                        lapacke::Layout::ColumnMajor,
+                        jobvl,
+                        jobvr,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        b.mat.data_mut(),
+                        ldb as i32,
+                        &mut alphar,
+                        &mut alphai,
+                        &mut beta,
+                        &mut vl,
+                        ld as i32,
+                        &mut vr,
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Generalized eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let alpha: Vec<$complex> = alphar
+                    .iter()
+                    .zip(alphai.iter())
+                    .map(|(&re, &im)| <$complex>::new(re, im))
+                    .collect();
+
+                Ok(GeneralizedEigenvalueDecomposition {
+                    alpha: to_column_vector(&alpha),
+                    beta: to_column_vector(&beta),
+                    left_eigenvectors: if jobvl == b'V' {
+                        Some(real_eigenvectors_to_complex(&vl, &alphai, ld, n))
+                    } else {
+                        None
+                    },
+                    right_eigenvectors: if jobvr == b'V' {
+                        Some(real_eigenvectors_to_complex(&vr, &alphai, ld, n))
+                    } else {
+                        None
+                    },
+                })
+            }
+        }
+    };
+}
+
+macro_rules! complex_eig_impl {
+    ($scalar:ty, $lapack_geev:ident, $lapack_ggev:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Compute the eigenvalues and optionally the eigenvectors of a general
+            /// square matrix.
+            pub fn eig(
+                mut self,
+                mode: EigenvectorMode,
+            ) -> RlstResult<EigenvalueDecomposition<$scalar>> {
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let (jobvl, jobvr) = geev_jobs(mode);
+                let ld = std::cmp::max(1, n);
+
+                let mut eigenvalues = vec![<$scalar as Zero>::zero(); n];
+                let mut vl =
+                    vec![<$scalar as Zero>::zero(); if jobvl == b'V' { ld * n } else { 1 }];
+                let mut vr =
+                    vec![<$scalar as Zero>::zero(); if jobvr == b'V' { ld * n } else { 1 }];
+                let info = unsafe {
+                    lapacke::$lapack_geev(
+                        lapacke::Layout::ColumnMajor,
+                        jobvl,
+                        jobvr,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        &mut eigenvalues,
+                        &mut vl,
+                        ld as i32,
+                        &mut vr,
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                Ok(EigenvalueDecomposition {
+                    eigenvalues: to_column_vector(&eigenvalues),
+                    left_eigenvectors: if jobvl == b'V' {
+                        Some(copy_columns(&vl, ld, n, n))
+                    } else {
+                        None
+                    },
+                    right_eigenvectors: if jobvr == b'V' {
+                        Some(copy_columns(&vr, ld, n, n))
+                    } else {
+                        None
+                    },
+                })
+            }
+
+            /// Compute the generalized eigenvalues and optionally the eigenvectors of the
+            /// problem `A v = lambda B v` with the QZ algorithm.
+            pub fn eig_generalized<
+                BData: DataContainerMut<Item = $scalar>,
+                BRS: SizeIdentifier,
+                BCS: SizeIdentifier,
+            >(
+                mut self,
+                mut b: LapackData<
+                    $scalar,
+                    BRS,
+                    BCS,
+                    GenericBaseMatrixMut<$scalar, BData, BRS, BCS>,
+                >,
+                mode: EigenvectorMode,
+            ) -> RlstResult<GeneralizedEigenvalueDecomposition<$scalar>> {
+                let n = check_same_dimension(self.mat.layout().dim(), b.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let ldb = b.mat.layout().stride().1;
+                let (jobvl, jobvr) = geev_jobs(mode);
+                let ld = std::cmp::max(1, n);
+
+                let mut alpha = vec![<$scalar as Zero>::zero(); n];
+                let mut beta = vec![<$scalar as Zero>::zero(); n];
+                let mut vl =
+                    vec![<$scalar as Zero>::zero(); if jobvl == b'V' { ld * n } else { 1 }];
+                let mut vr =
+                    vec![<$scalar as Zero>::zero(); if jobvr == b'V' { ld * n } else { 1 }];
+                let info = unsafe {
+                    lapacke::$lapack_ggev(
+                        lapacke::Layout::ColumnMajor,
+                        jobvl,
+                        jobvr,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        b.mat.data_mut(),
+                        ldb as i32,
+                        &mut alpha,
+                        &mut beta,
+                        &mut vl,
+                        ld as i32,
+                        &mut vr,
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Generalized eigenvalue decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                Ok(GeneralizedEigenvalueDecomposition {
+                    alpha: to_column_vector(&alpha),
+                    beta: to_column_vector(&beta),
+                    left_eigenvectors: if jobvl == b'V' {
+                        Some(copy_columns(&vl, ld, n, n))
+                    } else {
+                        None
+                    },
+                    right_eigenvectors: if jobvr == b'V' {
+                        Some(copy_columns(&vr, ld, n, n))
+                    } else {
+                        None
+                    },
+                })
+            }
+        }
+    };
+}
+
+real_eig_impl!(f64, c64, dgeev, dggev);
+real_eig_impl!(f32, c32, sgeev, sggev);
+complex_eig_impl!(c32, cgeev, cggev);
+complex_eig_impl!(c64, zgeev, zggev);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Matrix, RandomAccessByValue};
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    // A symmetric tridiagonal matrix with eigenvalues 2 - sqrt(2), 2 and 2 + sqrt(2).
+    fn symmetric_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+        for index in 0..3 {
+            mat[[index, index]] = 2.0;
+        }
+        for index in 0..2 {
+            mat[[index, index + 1]] = -1.0;
+            mat[[index + 1, index]] = -1.0;
+        }
+        mat
+    }
+
+    #[test]
+    fn test_eigh_f64() {
+        let mat = symmetric_matrix();
+        let expected = [2.0 - 2.0_f64.sqrt(), 2.0, 2.0 + 2.0_f64.sqrt()];
+
+        let eigh = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eigh(TriangularType::Lower, EigenvectorMode::Right)
+            .unwrap();
+        let vectors = eigh.eigenvectors.unwrap();
+        for (index, &value) in expected.iter().enumerate() {
+            assert_float_eq!(eigh.eigenvalues.get_value(index, 0), value, abs <= 1E-12);
+            // Check A v = lambda v.
+            for row in 0..3 {
+                let av = (0..3).fold(0.0, |acc, col| {
+                    acc + mat[[row, col]] * vectors[[col, index]]
+                });
+                assert_float_eq!(av, value * vectors[[row, index]], abs <= 1E-12);
+            }
+        }
+
+        let by_index = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eigh_range(
+                TriangularType::Upper,
+                EigenvectorMode::Right,
+                EigenvalueRange::Index(1, 3),
+            )
+            .unwrap();
+        assert_eq!(by_index.eigenvalues.layout().dim(), (2, 1));
+        assert_eq!(by_index.eigenvectors.unwrap().layout().dim(), (3, 2));
+        assert_float_eq!(
+            by_index.eigenvalues.get_value(0, 0),
+            expected[1],
+            abs <= 1E-12
+        );
+        assert_float_eq!(
+            by_index.eigenvalues.get_value(1, 0),
+            expected[2],
+            abs <= 1E-12
+        );
+
+        let by_value = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eigh_range(
+                TriangularType::Upper,
+                EigenvectorMode::None,
+                EigenvalueRange::Value(0.0, 2.5),
+            )
+            .unwrap();
+        assert!(by_value.eigenvectors.is_none());
+        assert_eq!(by_value.eigenvalues.layout().dim(), (2, 1));
+        assert_float_eq!(
+            by_value.eigenvalues.get_value(0, 0),
+            expected[0],
+            abs <= 1E-12
+        );
+        assert_float_eq!(
+            by_value.eigenvalues.get_value(1, 0),
+            expected[1],
+            abs <= 1E-12
+        );
+    }
+
+    #[test]
+    fn test_eigh_c64() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+        mat[[0, 0]] = c64::new(2.0, 0.0);
+        mat[[0, 1]] = c64::new(0.0, -1.0);
+        mat[[1, 0]] = c64::new(0.0, 1.0);
+        mat[[1, 1]] = c64::new(2.0, 0.0);
+
+        let eigh = mat
+            .lapack()
+            .unwrap()
+            .eigh(TriangularType::Upper, EigenvectorMode::None)
+            .unwrap();
+        assert!(eigh.eigenvectors.is_none());
+        assert_float_eq!(eigh.eigenvalues.get_value(0, 0), 1.0, abs <= 1E-12);
+        assert_float_eq!(eigh.eigenvalues.get_value(1, 0), 3.0, abs <= 1E-12);
+    }
+
+    #[test]
+    fn test_eig_f64() {
+        // A rotation combined with a real eigenvalue has eigenvalues 1 + 2i, 1 - 2i and 3.
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+        mat[[0, 0]] = 1.0;
+        mat[[0, 1]] = -2.0;
+        mat[[1, 0]] = 2.0;
+        mat[[1, 1]] = 1.0;
+        mat[[2, 2]] = 3.0;
+
+        let eig = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eig(EigenvectorMode::LeftAndRight)
+            .unwrap();
+
+        let right = eig.right_eigenvectors.unwrap();
+        let left = eig.left_eigenvectors.unwrap();
+        let mut found = [false; 3];
+        for index in 0..3 {
+            let value = eig.eigenvalues.get_value(index, 0);
+            for (slot, expected) in [c64::new(1.0, 2.0), c64::new(1.0, -2.0), c64::new(3.0, 0.0)]
+                .iter()
+                .enumerate()
+            {
+                if (value - expected).norm() < 1E-12 {
+                    found[slot] = true;
+                }
+            }
+
+            // Check A v = lambda v and u^H A = lambda u^H.
+            for row in 0..3 {
+                let av = (0..3).fold(c64::new(0.0, 0.0), |acc, col| {
+                    acc + c64::new(mat[[row, col]], 0.0) * right[[col, index]]
+                });
+                assert_close(av, value * right[[row, index]], 1E-12);
+
+                let ua = (0..3).fold(c64::new(0.0, 0.0), |acc, col| {
+                    acc + left[[col, index]].conj() * c64::new(mat[[col, row]], 0.0)
+                });
+                assert_close(ua, value * left[[row, index]].conj(), 1E-12);
+            }
+        }
+        assert_eq!(found, [true; 3]);
+    }
+
+    #[test]
+    fn test_eig_c64() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+        mat[[0, 0]] = c64::new(1.0, 1.0);
+        mat[[0, 1]] = c64::new(2.0, 0.0);
+        mat[[1, 1]] = c64::new(-1.0, 0.0);
+
+        let eig = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eig(EigenvectorMode::Right)
+            .unwrap();
+        assert!(eig.left_eigenvectors.is_none());
+
+        let right = eig.right_eigenvectors.unwrap();
+        for index in 0..2 {
+            let value = eig.eigenvalues.get_value(index, 0);
+            for row in 0..2 {
+                let av = (0..2).fold(c64::new(0.0, 0.0), |acc, col| {
+                    acc + mat[[row, col]] * right[[col, index]]
+                });
+                assert_close(av, value * right[[row, index]], 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigh_generalized_f64() {
+        let mut a = MatrixD::<f64>::zeros_from_dim(2, 2);
+        a[[0, 0]] = 2.0;
+        a[[0, 1]] = 1.0;
+        a[[1, 0]] = 1.0;
+        a[[1, 1]] = 3.0;
+        let mut b = MatrixD::<f64>::zeros_from_dim(2, 2);
+        b[[0, 0]] = 1.0;
+        b[[1, 1]] = 2.0;
+
+        let eigh = Matrix::from_ref(&a)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eigh_generalized(
+                Matrix::from_ref(&b).eval().lapack().unwrap(),
+                TriangularType::Upper,
+                EigenvectorMode::Right,
+            )
+            .unwrap();
+
+        let vectors = eigh.eigenvectors.unwrap();
+        for index in 0..2 {
+            let value = eigh.eigenvalues.get_value(index, 0);
+            // Check A v = lambda B v.
+            for row in 0..2 {
+                let av = (0..2).fold(0.0, |acc, col| acc + a[[row, col]] * vectors[[col, index]]);
+                let bv = (0..2).fold(0.0, |acc, col| acc + b[[row, col]] * vectors[[col, index]]);
+                assert_float_eq!(av, value * bv, abs <= 1E-12);
+            }
+            // Check V^H B V = I.
+            for other in 0..2 {
+                let value = (0..2).fold(0.0, |acc, row| {
+                    acc + vectors[[row, other]] * b[[row, row]] * vectors[[row, index]]
+                });
+                let expected = if other == index { 1.0 } else { 0.0 };
+                assert_float_eq!(value, expected, abs <= 1E-12);
+            }
+        }
+
+        b[[1, 1]] = -2.0;
+        let result = a.lapack().unwrap().eigh_generalized(
+            b.lapack().unwrap(),
+            TriangularType::Upper,
+            EigenvectorMode::None,
+        );
+        assert!(matches!(result, Err(RlstError::NotPositiveDefinite(2))));
+    }
+
+    #[test]
+    fn test_eig_generalized_f64() {
+        // The pencil has the finite eigenvalue 1/2 and an infinite eigenvalue.
+        let mut a = MatrixD::<f64>::zeros_from_dim(2, 2);
+        a[[0, 0]] = 1.0;
+        a[[0, 1]] = 1.0;
+        a[[1, 1]] = 2.0;
+        let mut b = MatrixD::<f64>::zeros_from_dim(2, 2);
+        b[[0, 0]] = 2.0;
+
+        let eig = Matrix::from_ref(&a)
+            .eval()
+            .lapack()
+            .unwrap()
+            .eig_generalized(
+                Matrix::from_ref(&b).eval().lapack().unwrap(),
+                EigenvectorMode::Right,
+            )
+            .unwrap();
+
+        let eigenvalues = eig.eigenvalues();
+        assert_eq!(
+            eigenvalues.iter().filter(|value| value.is_none()).count(),
+            1
+        );
+        let finite = eigenvalues.iter().flatten().next().unwrap();
+        assert_close(*finite, c64::new(0.5, 0.0), 1E-12);
+
+        // Check beta A v = alpha B v.
+        let right = eig.right_eigenvectors.unwrap();
+        for index in 0..2 {
+            let alpha = eig.alpha.get_value(index, 0);
+            let beta = c64::new(eig.beta.get_value(index, 0), 0.0);
+            for row in 0..2 {
+                let av = (0..2).fold(c64::new(0.0, 0.0), |acc, col| {
+                    acc + c64::new(a[[row, col]], 0.0) * right[[col, index]]
+                });
+                let bv = (0..2).fold(c64::new(0.0, 0.0), |acc, col| {
+                    acc + c64::new(b[[row, col]], 0.0) * right[[col, index]]
+                });
+                assert_close(beta * av, alpha * bv, 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generalized_eigenvalues_tolerance() {
+        let eig = GeneralizedEigenvalueDecomposition::<f64> {
+            alpha: to_column_vector(&[c64::new(1.0, 0.0), c64::new(2.0, 1.0)]),
+            beta: to_column_vector(&[1E-17, 2.0]),
+            left_eigenvectors: None,
+            right_eigenvectors: None,
+        };
+
+        // Only a zero beta is treated as infinite without a tolerance.
+        assert!(eig.eigenvalues().iter().all(|value| value.is_some()));
+
+        let eigenvalues = eig.eigenvalues_with_tolerance(f64::EPSILON);
+        assert!(eigenvalues[0].is_none());
+        assert_close(eigenvalues[1].unwrap(), c64::new(1.0, 0.5), 1E-14);
+    }
+}
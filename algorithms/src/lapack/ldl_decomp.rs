@@ -0,0 +1,313 @@
+//! Bunch-Kaufman LDL^H decomposition of symmetric and Hermitian indefinite matrices.
+//!
+//! The decomposition `A = U D U^H` or `A = L D L^H` is computed with `sytrf` for real
+//! and `hetrf` for complex matrices. `D` is block diagonal with `1 x 1` and `2 x 2`
+//! blocks, whose layout is encoded in the pivot indices. Systems are solved with
+//! `sytrs`/`hetrs` and the inverse is formed with `sytri`/`hetri`.
+use crate::lapack::LapackData;
+use crate::traits::ldl_decomp::LDLDecomp;
+use lapacke;
+use num::{One, Zero};
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, MatrixD, MatrixTraitMut,
+    SizeIdentifier,
+};
+
+use super::{check_lapack_stride, fill_hermitian, TriangularType};
+
+pub struct LDLDecompLapack<
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    Mat: MatrixTraitMut<Item, RS, CS> + Sized,
+> {
+    data: LapackData<Item, RS, CS, Mat>,
+    uplo: TriangularType,
+    ipiv: Vec<i32>,
+}
+
+macro_rules! ldl_decomp_impl {
+    ($scalar:ty, $lapack_trf:ident, $lapack_trs:ident, $lapack_tri:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Compute the Bunch-Kaufman LDL^H decomposition of a symmetric or Hermitian
+            /// matrix, referencing only the triangle given by `uplo`.
+            ///
+            /// Returns [RlstError::SingularMatrix] if the matrix is singular.
+            pub fn ldl(
+                mut self,
+                uplo: TriangularType,
+            ) -> RlstResult<
+                LDLDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>,
+            > {
+                let dim = self.mat.layout().dim();
+                let stride = self.mat.layout().stride();
+
+                if dim.0 != dim.1 {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: dim.0,
+                        actual: dim.1,
+                    });
+                }
+
+                let mut ipiv: Vec<i32> = vec![0; dim.0];
+                let info = unsafe {
+                    lapacke::$lapack_trf(
+                        lapacke::Layout::ColumnMajor,
+                        uplo as u8,
+                        dim.0 as i32,
+                        self.mat.data_mut(),
+                        stride.1 as i32,
+                        &mut ipiv,
+                    )
+                };
+                if info == 0 {
+                    Ok(LDLDecompLapack {
+                        data: self,
+                        uplo,
+                        ipiv,
+                    })
+                } else if info > 0 {
+                    Err(RlstError::SingularMatrix)
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            LDLDecomp
+            for LDLDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            type T = $scalar;
+
+            fn data(&self) -> &[Self::T] {
+                self.data.mat.data()
+            }
+
+            fn dim(&self) -> (IndexType, IndexType) {
+                self.data.mat.dim()
+            }
+
+            fn triangular_type(&self) -> TriangularType {
+                self.uplo
+            }
+
+            fn solve<
+                RhsData: DataContainerMut<Item = Self::T>,
+                RhsR: SizeIdentifier,
+                RhsC: SizeIdentifier,
+            >(
+                &self,
+                rhs: &mut GenericBaseMatrixMut<Self::T, RhsData, RhsR, RhsC>,
+            ) -> RlstResult<()> {
+                if !check_lapack_stride(rhs.layout().dim(), rhs.layout().stride()) {
+                    return Err(RlstError::IncompatibleStride);
+                }
+                let n = self.dim().0;
+                if rhs.layout().dim().0 != n {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: n,
+                        actual: rhs.layout().dim().0,
+                    });
+                }
+
+                let mat = &self.data.mat;
+                let ldb = rhs.layout().stride().1;
+                let nrhs = rhs.layout().dim().1;
+                let info = unsafe {
+                    lapacke::$lapack_trs(
+                        lapacke::Layout::ColumnMajor,
+                        self.uplo as u8,
+                        n as i32,
+                        nrhs as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        &self.ipiv,
+                        rhs.data_mut(),
+                        ldb as i32,
+                    )
+                };
+
+                if info != 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(())
+                }
+            }
+
+            fn inverse(&self) -> RlstResult<MatrixD<Self::T>> {
+                let n = self.dim().0;
+                let mat = &self.data.mat;
+
+                // Only the referenced triangle holds the factorization.
+                let mut inverse = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                for col in 0..n {
+                    for row in 0..n {
+                        let in_triangle = match self.uplo {
+                            TriangularType::Upper => row <= col,
+                            TriangularType::Lower => row >= col,
+                        };
+                        if in_triangle {
+                            inverse[[row, col]] = mat[[row, col]];
+                        }
+                    }
+                }
+
+                let info = unsafe {
+                    lapacke::$lapack_tri(
+                        lapacke::Layout::ColumnMajor,
+                        self.uplo as u8,
+                        n as i32,
+                        inverse.data_mut(),
+                        std::cmp::max(1, n) as i32,
+                        &self.ipiv,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::SingularMatrix);
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+                fill_hermitian(&mut inverse, self.uplo);
+                Ok(inverse)
+            }
+
+            fn log_det(&self) -> (<Self::T as Scalar>::Real, <Self::T as Scalar>::Real) {
+                type Real = <$scalar as Scalar>::Real;
+
+                let n = self.dim().0;
+                let mat = &self.data.mat;
+                let mut sign = <Real as One>::one();
+                let mut log_abs = <Real as Zero>::zero();
+
+                // A negative pivot marks the first row of a `2 x 2` block of `D`, whose
+                // off-diagonal entry is stored in the referenced triangle.
+                let mut index = 0;
+                while index < n {
+                    let det = if self.ipiv[index] > 0 {
+                        index += 1;
+                        mat[[index - 1, index - 1]].re()
+                    } else {
+                        let off_diagonal = match self.uplo {
+                            TriangularType::Upper => mat[[index, index + 1]],
+                            TriangularType::Lower => mat[[index + 1, index]],
+                        };
+                        index += 2;
+                        mat[[index - 2, index - 2]].re() * mat[[index - 1, index - 1]].re()
+                            - (off_diagonal * off_diagonal.conj()).re()
+                    };
+                    if det < <Real as Zero>::zero() {
+                        sign = -sign;
+                    }
+                    log_abs += det.abs().ln();
+                }
+                (sign, log_abs)
+            }
+        }
+    };
+}
+
+ldl_decomp_impl!(f64, dsytrf, dsytrs, dsytri);
+ldl_decomp_impl!(f32, ssytrf, ssytrs, ssytri);
+ldl_decomp_impl!(c32, chetrf, chetrs, chetri);
+ldl_decomp_impl!(c64, zhetrf, zhetrs, zhetri);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix};
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    // A symmetric indefinite matrix with a zero leading entry and determinant 7.
+    fn indefinite_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+        let entries = [[0.0, 1.0, 2.0], [1.0, 0.0, 1.0], [2.0, 1.0, -3.0]];
+        for row in 0..3 {
+            for col in 0..3 {
+                mat[[row, col]] = entries[row][col];
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_ldl_f64() {
+        let mat = indefinite_matrix();
+        for uplo in [TriangularType::Upper, TriangularType::Lower] {
+            let ldl = Matrix::from_ref(&mat)
+                .eval()
+                .lapack()
+                .unwrap()
+                .ldl(uplo)
+                .unwrap();
+
+            let mut x = MatrixD::<f64>::zeros_from_dim(3, 1);
+            x[[0, 0]] = 1.0;
+            x[[1, 0]] = -2.0;
+            x[[2, 0]] = 0.5;
+            let mut rhs = mat.dot(&x);
+            ldl.solve(&mut rhs).unwrap();
+            for row in 0..3 {
+                assert_float_eq!(rhs[[row, 0]], x[[row, 0]], abs <= 1E-12);
+            }
+
+            let inverse = ldl.inverse().unwrap();
+            let product = mat.dot(&inverse);
+            for col in 0..3 {
+                for row in 0..3 {
+                    let expected = if row == col { 1.0 } else { 0.0 };
+                    assert_float_eq!(product[[row, col]], expected, abs <= 1E-12);
+                }
+            }
+
+            let (sign, log_abs) = ldl.log_det();
+            assert_eq!(sign, 1.0);
+            assert_float_eq!(log_abs, 7.0_f64.ln(), abs <= 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_ldl_c64() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+        mat[[0, 0]] = c64::new(1.0, 0.0);
+        mat[[0, 1]] = c64::new(2.0, -1.0);
+        mat[[1, 0]] = c64::new(2.0, 1.0);
+        mat[[1, 1]] = c64::new(-1.0, 0.0);
+
+        let ldl = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .ldl(TriangularType::Lower)
+            .unwrap();
+
+        let inverse = ldl.inverse().unwrap();
+        let product = mat.dot(&inverse);
+        for col in 0..2 {
+            for row in 0..2 {
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert_close(product[[row, col]], c64::new(expected, 0.0), 1E-12);
+            }
+        }
+
+        // The determinant is -1 - |2 + i|^2 = -6.
+        let (sign, log_abs) = ldl.log_det();
+        assert_eq!(sign, -1.0);
+        assert_float_eq!(log_abs, 6.0_f64.ln(), abs <= 1E-12);
+    }
+}
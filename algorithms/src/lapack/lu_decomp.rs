@@ -1,12 +1,15 @@
 use crate::lapack::LapackData;
-use crate::traits::lu_decomp::LUDecomp;
+use crate::traits::lu_decomp::{ErrorBounds, LUDecomp};
 use lapacke;
+use num::{Float, One, Zero};
+use rlst_common::permutation::Permutation;
 use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
 use rlst_dense::{
-    DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, MatrixTraitMut, SizeIdentifier,
+    DataContainer, DataContainerMut, GenericBaseMatrix, GenericBaseMatrixMut, Layout, LayoutType,
+    MatrixD, MatrixTraitMut, SizeIdentifier,
 };
 
-use super::{check_lapack_stride, TransposeMode};
+use super::{check_lapack_stride, check_square, copy_matrix, NormType, TransposeMode};
 
 pub struct LUDecompLapack<
     Item: Scalar,
@@ -16,10 +19,62 @@ pub struct LUDecompLapack<
 > {
     data: LapackData<Item, RS, CS, Mat>,
     ipiv: Vec<i32>,
+    // The 1-norm and infinity-norm of the original matrix for condition estimates.
+    norm_one: <Item as Scalar>::Real,
+    norm_inf: <Item as Scalar>::Real,
+    // Workspace for the row sums of the infinity-norm, reused by refactorizations.
+    row_sums: Vec<<Item as Scalar>::Real>,
+    // True if the factor `U` has an exactly zero diagonal entry.
+    singular: bool,
+}
+
+impl<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier, Mat: MatrixTraitMut<Item, RS, CS>>
+    LUDecompLapack<Item, RS, CS, Mat>
+{
+    /// Return the row permutation `P` of the decomposition `P * A = L * U`.
+    pub fn permutation(&self) -> RlstResult<Permutation> {
+        Permutation::from_ipiv(&self.ipiv, self.data.mat.layout().dim().0)
+    }
+
+    /// Return true if the factor `U` has an exactly zero diagonal entry.
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+}
+
+/// Return the 1-norm and infinity-norm of a matrix, using `row_sums` as workspace
+/// for the row sums. Its length must be the number of rows.
+fn matrix_norms<
+    Item: Scalar,
+    Data: DataContainerMut<Item = Item>,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+>(
+    mat: &GenericBaseMatrixMut<Item, Data, RS, CS>,
+    row_sums: &mut [<Item as Scalar>::Real],
+) -> (<Item as Scalar>::Real, <Item as Scalar>::Real) {
+    let dim = mat.layout().dim();
+    row_sums.fill(<<Item as Scalar>::Real as Zero>::zero());
+    let mut norm_one = <<Item as Scalar>::Real as Zero>::zero();
+    for col in 0..dim.1 {
+        let mut col_sum = <<Item as Scalar>::Real as Zero>::zero();
+        for (row, row_sum) in row_sums.iter_mut().enumerate() {
+            let value = mat[[row, col]].abs();
+            col_sum += value;
+            *row_sum += value;
+        }
+        norm_one = norm_one.max(col_sum);
+    }
+    let norm_inf = row_sums
+        .iter()
+        .fold(<<Item as Scalar>::Real as Zero>::zero(), |acc, &value| {
+            acc.max(value)
+        });
+    (norm_one, norm_inf)
 }
 
 macro_rules! lu_decomp_impl {
-    ($scalar:ty, $lapack_getrf:ident, $lapack_getrs:ident) => {
+    ($scalar:ty, $lapack_getrf:ident, $lapack_getrs:ident, $lapack_getri:ident, $lapack_gecon:ident, $lapack_gerfs:ident) => {
         impl<
                 RS: SizeIdentifier,
                 CS: SizeIdentifier,
@@ -39,6 +94,9 @@ macro_rules! lu_decomp_impl {
                 let n = dim.1 as i32;
                 let lda = stride.1 as i32;
 
+                let mut row_sums = vec![<<$scalar as Scalar>::Real as Zero>::zero(); dim.0];
+                let (norm_one, norm_inf) = matrix_norms(&self.mat, &mut row_sums);
+
                 let mut ipiv: Vec<i32> = vec![0; std::cmp::min(dim.0, dim.1)];
                 let info = unsafe {
                     lapacke::$lapack_getrf(
@@ -50,10 +108,61 @@ macro_rules! lu_decomp_impl {
                         &mut ipiv,
                     )
                 };
-                if info == 0 {
-                    return Ok(LUDecompLapack { data: self, ipiv });
+                // A positive `info` denotes an exactly singular factor `U`, for which the
+                // decomposition is still complete.
+                if info < 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(LUDecompLapack {
+                        data: self,
+                        ipiv,
+                        norm_one,
+                        norm_inf,
+                        row_sums,
+                        singular: info > 0,
+                    })
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            LUDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Recompute the decomposition for a new matrix of the same dimension.
+            ///
+            /// The matrix is copied into the storage of the decomposition and the pivot
+            /// indices and the workspace of the norm estimates are reused, so that no memory
+            /// is allocated. A singular matrix is factorized as in [lu](LapackData::lu). If
+            /// an error is returned, the decomposition must not be used before a successful
+            /// refactorization.
+            pub fn refactor<
+                AData: DataContainer<Item = $scalar>,
+                AR: SizeIdentifier,
+                AC: SizeIdentifier,
+            >(
+                &mut self,
+                mat: &GenericBaseMatrix<$scalar, AData, AR, AC>,
+            ) -> RlstResult<()> {
+                copy_matrix(mat, &mut self.data.mat)?;
+                (self.norm_one, self.norm_inf) = matrix_norms(&self.data.mat, &mut self.row_sums);
+
+                let dim = self.data.mat.layout().dim();
+                let lda = self.data.mat.layout().stride().1;
+                let info = unsafe {
+                    lapacke::$lapack_getrf(
+                        lapacke::Layout::ColumnMajor,
+                        dim.0 as i32,
+                        dim.1 as i32,
+                        self.data.mat.data_mut(),
+                        lda as i32,
+                        &mut self.ipiv,
+                    )
+                };
+                if info < 0 {
+                    Err(RlstError::LapackError(info))
                 } else {
-                    return Err(RlstError::LapackError(info));
+                    self.singular = info > 0;
+                    Ok(())
                 }
             }
         }
@@ -83,39 +192,246 @@ macro_rules! lu_decomp_impl {
             ) -> RlstResult<()> {
                 if !check_lapack_stride(rhs.layout().dim(), rhs.layout().stride()) {
                     return Err(RlstError::IncompatibleStride);
+                }
+                let n = check_square(self.data.mat.layout().dim())?;
+                if rhs.layout().dim().0 != n {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: n,
+                        actual: rhs.layout().dim().0,
+                    });
+                }
+                if self.singular {
+                    return Err(RlstError::SingularMatrix);
+                }
+
+                let mat = &self.data.mat;
+                let ldb = rhs.layout().stride().1;
+                let nrhs = rhs.layout().dim().1;
+                let info = unsafe {
+                    lapacke::$lapack_getrs(
+                        lapacke::Layout::ColumnMajor,
+                        trans as u8,
+                        n as i32,
+                        nrhs as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        &self.ipiv,
+                        rhs.data_mut(),
+                        ldb as i32,
+                    )
+                };
+
+                if info != 0 {
+                    Err(RlstError::LapackError(info))
                 } else {
-                    let mat = &self.data.mat;
-                    let ldb = rhs.layout().stride().1;
-
-                    let info = unsafe {
-                        lapacke::$lapack_getrs(
-                            lapacke::Layout::ColumnMajor,
-                            trans as u8,
-                            mat.layout().dim().1 as i32,
-                            mat.layout().dim().1 as i32,
-                            mat.data(),
-                            mat.layout().stride().1 as i32,
-                            &self.ipiv,
-                            rhs.data_mut(),
-                            ldb as i32,
-                        )
-                    };
-
-                    if info != 0 {
-                        return Err(RlstError::LapackError(info));
-                    } else {
-                        Ok(())
+                    Ok(())
+                }
+            }
+
+            fn l(&self) -> MatrixD<Self::T> {
+                let (m, n) = self.dim();
+                let k = std::cmp::min(m, n);
+                let mat = &self.data.mat;
+                let mut l = MatrixD::<$scalar>::zeros_from_dim(m, k);
+                for col in 0..k {
+                    l[[col, col]] = <$scalar as One>::one();
+                    for row in col + 1..m {
+                        l[[row, col]] = mat[[row, col]];
                     }
                 }
+                l
+            }
+
+            fn u(&self) -> MatrixD<Self::T> {
+                let (m, n) = self.dim();
+                let k = std::cmp::min(m, n);
+                let mat = &self.data.mat;
+                let mut u = MatrixD::<$scalar>::zeros_from_dim(k, n);
+                for col in 0..n {
+                    for row in 0..std::cmp::min(col + 1, k) {
+                        u[[row, col]] = mat[[row, col]];
+                    }
+                }
+                u
+            }
+
+            fn p(&self) -> RlstResult<MatrixD<Self::T>> {
+                let m = self.dim().0;
+                let perm = self.permutation()?;
+                let mut p = MatrixD::<$scalar>::zeros_from_dim(m, m);
+                for row in 0..m {
+                    p[[row, perm.get(row)]] = <$scalar as One>::one();
+                }
+                Ok(p)
+            }
+
+            fn det(&self) -> RlstResult<Self::T> {
+                let n = check_square(self.data.mat.layout().dim())?;
+                let mat = &self.data.mat;
+                let mut det = <$scalar as One>::one();
+                for index in 0..n {
+                    det *= mat[[index, index]];
+                    if self.ipiv[index] != index as i32 + 1 {
+                        det = -det;
+                    }
+                }
+                Ok(det)
+            }
+
+            fn log_det(&self) -> RlstResult<(Self::T, <Self::T as Scalar>::Real)> {
+                let n = check_square(self.data.mat.layout().dim())?;
+                let mat = &self.data.mat;
+                let mut sign = <$scalar as One>::one();
+                let mut log_abs = <<$scalar as Scalar>::Real as Zero>::zero();
+                for index in 0..n {
+                    let value = mat[[index, index]];
+                    let abs = value.abs();
+                    if abs == <<$scalar as Scalar>::Real as Zero>::zero() {
+                        return Ok((
+                            <$scalar as Zero>::zero(),
+                            <<$scalar as Scalar>::Real as Float>::neg_infinity(),
+                        ));
+                    }
+                    sign *= value / <$scalar as Scalar>::from_real(abs);
+                    if self.ipiv[index] != index as i32 + 1 {
+                        sign = -sign;
+                    }
+                    log_abs += abs.ln();
+                }
+                Ok((sign, log_abs))
+            }
+
+            fn inverse(&self) -> RlstResult<MatrixD<Self::T>> {
+                let n = check_square(self.data.mat.layout().dim())?;
+                let mat = &self.data.mat;
+                let mut inverse = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                for col in 0..n {
+                    for row in 0..n {
+                        inverse[[row, col]] = mat[[row, col]];
+                    }
+                }
+
+                let info = unsafe {
+                    lapacke::$lapack_getri(
+                        lapacke::Layout::ColumnMajor,
+                        n as i32,
+                        inverse.data_mut(),
+                        std::cmp::max(1, n) as i32,
+                        &self.ipiv,
+                    )
+                };
+
+                if info > 0 {
+                    Err(RlstError::SingularMatrix)
+                } else if info < 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(inverse)
+                }
+            }
+
+            fn rcond(&self, norm: NormType) -> RlstResult<<Self::T as Scalar>::Real> {
+                let n = check_square(self.data.mat.layout().dim())?;
+                let mat = &self.data.mat;
+                let anorm = match norm {
+                    NormType::One => self.norm_one,
+                    NormType::Infinity => self.norm_inf,
+                };
+
+                let mut rcond = <<$scalar as Scalar>::Real as Zero>::zero();
+                let info = unsafe {
+                    lapacke::$lapack_gecon(
+                        lapacke::Layout::ColumnMajor,
+                        norm as u8,
+                        n as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        anorm,
+                        &mut rcond,
+                    )
+                };
+
+                if info != 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(rcond)
+                }
+            }
+
+            fn refine<
+                AData: DataContainer<Item = Self::T>,
+                AR: SizeIdentifier,
+                AC: SizeIdentifier,
+                RhsData: DataContainer<Item = Self::T>,
+                XData: DataContainerMut<Item = Self::T>,
+                RhsR: SizeIdentifier,
+                RhsC: SizeIdentifier,
+            >(
+                &self,
+                a: &GenericBaseMatrix<Self::T, AData, AR, AC>,
+                rhs: &GenericBaseMatrix<Self::T, RhsData, RhsR, RhsC>,
+                x: &mut GenericBaseMatrixMut<Self::T, XData, RhsR, RhsC>,
+                trans: TransposeMode,
+            ) -> RlstResult<ErrorBounds<<Self::T as Scalar>::Real>> {
+                let n = check_square(self.data.mat.layout().dim())?;
+                if !check_lapack_stride(a.layout().dim(), a.layout().stride())
+                    || !check_lapack_stride(rhs.layout().dim(), rhs.layout().stride())
+                    || !check_lapack_stride(x.layout().dim(), x.layout().stride())
+                {
+                    return Err(RlstError::IncompatibleStride);
+                }
+                if a.layout().dim() != (n, n) {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: n,
+                        actual: a.layout().dim().0,
+                    });
+                }
+                if rhs.layout().dim().0 != n || x.layout().dim() != rhs.layout().dim() {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: n,
+                        actual: x.layout().dim().0,
+                    });
+                }
+
+                let mat = &self.data.mat;
+                let nrhs = rhs.layout().dim().1;
+                let ldx = x.layout().stride().1;
+                let mut forward = vec![<<$scalar as Scalar>::Real as Zero>::zero(); nrhs];
+                let mut backward = vec![<<$scalar as Scalar>::Real as Zero>::zero(); nrhs];
+                let info = unsafe {
+                    lapacke::$lapack_gerfs(
+                        lapacke::Layout::ColumnMajor,
+                        trans as u8,
+                        n as i32,
+                        nrhs as i32,
+                        a.data(),
+                        a.layout().stride().1 as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        &self.ipiv,
+                        rhs.data(),
+                        rhs.layout().stride().1 as i32,
+                        x.data_mut(),
+                        ldx as i32,
+                        &mut forward,
+                        &mut backward,
+                    )
+                };
+
+                if info != 0 {
+                    Err(RlstError::LapackError(info))
+                } else {
+                    Ok(ErrorBounds { forward, backward })
+                }
             }
         }
     };
 }
 
-lu_decomp_impl!(f64, dgetrf, dgetrs);
-lu_decomp_impl!(f32, sgetrf, sgetrs);
-lu_decomp_impl!(c32, cgetrf, cgetrs);
-lu_decomp_impl!(c64, zgetrf, zgetrs);
+lu_decomp_impl!(f64, dgetrf, dgetrs, dgetri, dgecon, dgerfs);
+lu_decomp_impl!(f32, sgetrf, sgetrs, sgetri, sgecon, sgerfs);
+lu_decomp_impl!(c32, cgetrf, cgetrs, cgetri, cgecon, cgerfs);
+lu_decomp_impl!(c64, zgetrf, zgetrs, zgetri, zgecon, zgerfs);
 
 #[cfg(test)]
 use super::*;
@@ -156,3 +472,200 @@ fn test_lu_decomp_f64() {
 
     //let lu_decomp = rlst_mat.algorithms().lapack().lu();
 }
+
+#[test]
+fn test_lu_permutation() {
+    let mut rlst_mat = rlst_dense::rlst_mat![f64, (2, 2)];
+
+    rlst_mat[[0, 0]] = 1.0;
+    rlst_mat[[0, 1]] = 1.0;
+    rlst_mat[[1, 0]] = 3.0;
+    rlst_mat[[1, 1]] = 1.0;
+
+    let lu = rlst_mat.lapack().unwrap().lu().unwrap();
+    let perm = lu.permutation().unwrap();
+
+    // Partial pivoting moves the row with the larger first entry to the top.
+    assert_eq!(perm.indices(), &[1, 0]);
+}
+
+#[cfg(test)]
+fn lu_test_matrix() -> MatrixD<f64> {
+    // A non-symmetric matrix with determinant -14 that requires pivoting.
+    let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+    let entries = [[1.0, 2.0, 0.0], [3.0, 1.0, 1.0], [0.0, 4.0, 2.0]];
+    for row in 0..3 {
+        for col in 0..3 {
+            mat[[row, col]] = entries[row][col];
+        }
+    }
+    mat
+}
+
+#[test]
+fn test_lu_factors() {
+    use rlst_dense::{Dot, Matrix};
+
+    let mat = lu_test_matrix();
+    let lu = Matrix::from_ref(&mat)
+        .eval()
+        .lapack()
+        .unwrap()
+        .lu()
+        .unwrap();
+
+    // Check P * A = L * U.
+    let pa = lu.p().unwrap().dot(&mat);
+    let product = lu.l().dot(&lu.u());
+    for col in 0..3 {
+        for row in 0..3 {
+            float_eq::assert_float_eq!(product[[row, col]], pa[[row, col]], abs <= 1E-12);
+        }
+        float_eq::assert_float_eq!(lu.l()[[col, col]], 1.0, abs <= 1E-14);
+    }
+}
+
+#[test]
+fn test_lu_det_inverse() {
+    use rlst_dense::{Dot, Matrix};
+
+    let mat = lu_test_matrix();
+    let lu = Matrix::from_ref(&mat)
+        .eval()
+        .lapack()
+        .unwrap()
+        .lu()
+        .unwrap();
+
+    float_eq::assert_float_eq!(lu.det().unwrap(), -14.0, abs <= 1E-12);
+    let (sign, log_abs) = lu.log_det().unwrap();
+    assert_eq!(sign, -1.0);
+    float_eq::assert_float_eq!(log_abs, 14.0_f64.ln(), abs <= 1E-12);
+
+    let product = mat.dot(&lu.inverse().unwrap());
+    for col in 0..3 {
+        for row in 0..3 {
+            let expected = if row == col { 1.0 } else { 0.0 };
+            float_eq::assert_float_eq!(product[[row, col]], expected, abs <= 1E-12);
+        }
+    }
+
+    // A singular matrix has determinant zero and no inverse.
+    let mut singular = MatrixD::<f64>::zeros_from_dim(2, 2);
+    singular[[0, 0]] = 1.0;
+    singular[[0, 1]] = 2.0;
+    singular[[1, 0]] = 2.0;
+    singular[[1, 1]] = 4.0;
+    let lu = singular.lapack().unwrap().lu().unwrap();
+    assert!(lu.is_singular());
+    assert_eq!(lu.det().unwrap(), 0.0);
+    assert_eq!(lu.log_det().unwrap().1, f64::NEG_INFINITY);
+    assert!(matches!(lu.inverse(), Err(RlstError::SingularMatrix)));
+    let mut rhs = MatrixD::<f64>::zeros_from_dim(2, 1);
+    assert!(matches!(
+        lu.solve(&mut rhs, TransposeMode::NoTrans),
+        Err(RlstError::SingularMatrix)
+    ));
+}
+
+#[test]
+fn test_lu_rcond_refine() {
+    use rlst_dense::{Dot, Matrix};
+
+    let mut diag = MatrixD::<f64>::zeros_from_dim(3, 3);
+    diag[[0, 0]] = 1.0;
+    diag[[1, 1]] = 10.0;
+    diag[[2, 2]] = 100.0;
+    let lu = diag.lapack().unwrap().lu().unwrap();
+    float_eq::assert_float_eq!(lu.rcond(NormType::One).unwrap(), 0.01, abs <= 1E-12);
+    float_eq::assert_float_eq!(lu.rcond(NormType::Infinity).unwrap(), 0.01, abs <= 1E-12);
+
+    let mat = lu_test_matrix();
+    let lu = Matrix::from_ref(&mat)
+        .eval()
+        .lapack()
+        .unwrap()
+        .lu()
+        .unwrap();
+    let mut expected = MatrixD::<f64>::zeros_from_dim(3, 1);
+    expected[[0, 0]] = 1.0;
+    expected[[1, 0]] = -1.0;
+    expected[[2, 0]] = 2.0;
+    let rhs = mat.dot(&expected);
+    let mut x = Matrix::from_ref(&rhs).eval();
+    lu.solve(&mut x, TransposeMode::NoTrans).unwrap();
+
+    let bounds = lu
+        .refine(&mat, &rhs, &mut x, TransposeMode::NoTrans)
+        .unwrap();
+    assert_eq!(bounds.forward.len(), 1);
+    assert!(bounds.backward[0] <= 1E-14);
+    for row in 0..3 {
+        float_eq::assert_float_eq!(x[[row, 0]], expected[[row, 0]], abs <= 1E-12);
+    }
+}
+
+#[test]
+fn test_lu_lapack_mut_refactor() {
+    use crate::lapack::AsLapackMut;
+    use rlst_dense::Dot;
+
+    let mat = lu_test_matrix();
+    let mut storage = MatrixD::<f64>::zeros_from_dim(3, 3);
+
+    // The decomposition borrows `storage`, which is overwritten by each factorization.
+    let mut lu = storage.lapack_mut().unwrap().lu().unwrap();
+    for step in 0..3 {
+        let mut shifted = lu_test_matrix();
+        for index in 0..3 {
+            shifted[[index, index]] += step as f64;
+        }
+        lu.refactor(&shifted).unwrap();
+
+        let mut x = MatrixD::<f64>::zeros_from_dim(3, 1);
+        x[[0, 0]] = 1.0;
+        x[[1, 0]] = 2.0;
+        x[[2, 0]] = -1.0;
+        let mut rhs = shifted.dot(&x);
+        lu.solve(&mut rhs, TransposeMode::NoTrans).unwrap();
+        for row in 0..3 {
+            float_eq::assert_float_eq!(rhs[[row, 0]], x[[row, 0]], abs <= 1E-12);
+        }
+    }
+    assert!(lu.refactor(&MatrixD::<f64>::zeros_from_dim(2, 3)).is_err());
+
+    // The borrowed matrix stays available after the decomposition is dropped.
+    lu.refactor(&mat).unwrap();
+    drop(lu);
+    assert_eq!(storage[[0, 0]], 3.0);
+}
+
+#[test]
+fn test_lu_row_major() {
+    use crate::lapack::AsLapackMut;
+    use rlst_dense::{DefaultLayout, Dynamic, SliceContainerMut, SliceMatrixMut};
+
+    // The entries of the test matrix in row-major order.
+    let mut data = [1.0, 2.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0, 2.0];
+    let mut mat = SliceMatrixMut::<'_, f64, Dynamic, Dynamic>::from_data(
+        SliceContainerMut::new(&mut data),
+        DefaultLayout::new((3, 3), (3, 1)),
+    );
+    assert!(matches!(
+        mat.lapack_mut(),
+        Err(RlstError::IncompatibleStride)
+    ));
+
+    let det = mat.with_lapack(|data| data.lu()?.det()).unwrap();
+    float_eq::assert_float_eq!(det, -14.0, abs <= 1E-12);
+
+    // The factors are copied back in row-major order.
+    let lu = lu_test_matrix().lapack().unwrap().lu().unwrap();
+    let u = lu.u();
+    for col in 0..3 {
+        for row in 0..=col {
+            float_eq::assert_float_eq!(mat[[row, col]], u[[row, col]], abs <= 1E-12);
+        }
+    }
+    assert_eq!(data[1], u[[0, 1]]);
+}
@@ -0,0 +1,204 @@
+//! Sylvester and Lyapunov equations for dense matrices.
+//!
+//! The Sylvester equation `A X + X B = C` is solved with the Bartels-Stewart algorithm.
+//! With the Schur decompositions `A = U S U^H` and `B = V T V^H` the equation becomes the
+//! triangular equation `S Y + Y T = U^H C V`, which is solved by `trsyl`, and the solution
+//! is recovered as `X = U Y V^H`. The continuous Lyapunov equation `A X + X A^H = Q` is the
+//! special case `B = A^H`, which only requires the Schur decomposition of `A`.
+use crate::lapack::matrix_functions::adjoint;
+use crate::lapack::{check_square, AsLapack};
+use crate::traits::matrix_equations::MatrixEquations;
+use crate::traits::schur_decomp::SchurDecomp;
+use lapacke;
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{Dot, Layout, LayoutType, Matrix, MatrixD};
+
+fn check_rhs<T: Scalar>(mat: &MatrixD<T>, dim: (IndexType, IndexType)) -> RlstResult<()> {
+    let rhs_dim = mat.layout().dim();
+    if rhs_dim.0 != dim.0 {
+        return Err(RlstError::SingleDimensionError {
+            expected: dim.0,
+            actual: rhs_dim.0,
+        });
+    }
+    if rhs_dim.1 != dim.1 {
+        return Err(RlstError::SingleDimensionError {
+            expected: dim.1,
+            actual: rhs_dim.1,
+        });
+    }
+    Ok(())
+}
+
+macro_rules! matrix_equations_impl {
+    ($scalar:ty, $adjoint:expr, $lapack_trsyl:ident) => {
+        impl MatrixEquations for MatrixD<$scalar> {
+            type T = $scalar;
+
+            fn solve_sylvester(
+                &self,
+                b: &MatrixD<Self::T>,
+                c: &MatrixD<Self::T>,
+            ) -> RlstResult<MatrixD<Self::T>> {
+                let m = check_square(self.layout().dim())?;
+                let n = check_square(b.layout().dim())?;
+                check_rhs(c, (m, n))?;
+
+                let schur_a = Matrix::from_ref(self).eval().lapack()?.schur()?;
+                let schur_b = Matrix::from_ref(b).eval().lapack()?.schur()?;
+
+                let mut rhs = adjoint(schur_a.z()).dot(c).dot(schur_b.z());
+                let mut scale = [<<$scalar as Scalar>::Real as num::One>::one()];
+                let info = unsafe {
+                    lapacke::$lapack_trsyl(
+                        lapacke::Layout::ColumnMajor,
+                        b'N',
+                        b'N',
+                        1,
+                        m as i32,
+                        n as i32,
+                        schur_a.t().data(),
+                        std::cmp::max(1, m) as i32,
+                        schur_b.t().data(),
+                        std::cmp::max(1, n) as i32,
+                        rhs.data_mut(),
+                        std::cmp::max(1, m) as i32,
+                        &mut scale,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::SingularMatrix);
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let mut solution = schur_a.z().dot(&rhs).dot(&adjoint(schur_b.z()));
+                let factor = <$scalar as Scalar>::from_real(scale[0].recip());
+                solution.for_each(|elem| *elem *= factor);
+                Ok(solution)
+            }
+
+            fn solve_lyapunov(&self, q: &MatrixD<Self::T>) -> RlstResult<MatrixD<Self::T>> {
+                let n = check_square(self.layout().dim())?;
+                check_rhs(q, (n, n))?;
+
+                let schur = Matrix::from_ref(self).eval().lapack()?.schur()?;
+
+                let mut rhs = adjoint(schur.z()).dot(q).dot(schur.z());
+                let mut scale = [<<$scalar as Scalar>::Real as num::One>::one()];
+                let ld = std::cmp::max(1, n) as i32;
+                let info = unsafe {
+                    lapacke::$lapack_trsyl(
+                        lapacke::Layout::ColumnMajor,
+                        b'N',
+                        $adjoint,
+                        1,
+                        n as i32,
+                        n as i32,
+                        schur.t().data(),
+                        ld,
+                        schur.t().data(),
+                        ld,
+                        rhs.data_mut(),
+                        ld,
+                        &mut scale,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::SingularMatrix);
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let mut solution = schur.z().dot(&rhs).dot(&adjoint(schur.z()));
+                let factor = <$scalar as Scalar>::from_real(scale[0].recip());
+                solution.for_each(|elem| *elem *= factor);
+                Ok(solution)
+            }
+        }
+    };
+}
+
+// The `trsyl` argument for the adjoint is `T` for real and `C` for complex types.
+matrix_equations_impl!(f64, b'T', dtrsyl);
+matrix_equations_impl!(f32, b'T', strsyl);
+matrix_equations_impl!(c32, b'C', ctrsyl);
+matrix_equations_impl!(c64, b'C', ztrsyl);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    fn from_rows<T: Scalar>(rows: &[&[T]]) -> MatrixD<T> {
+        let mut mat = MatrixD::<T>::zeros_from_dim(rows.len(), rows[0].len());
+        for (row, values) in rows.iter().enumerate() {
+            for (col, &value) in values.iter().enumerate() {
+                mat[[row, col]] = value;
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_sylvester_f64() {
+        // A has the complex eigenvalues 1 +- 2i to exercise the real Schur form.
+        let a = from_rows(&[&[1.0, -2.0, 0.0], &[2.0, 1.0, 0.5], &[0.0, 0.0, 3.0]]);
+        let b = from_rows(&[&[4.0, 1.0], &[0.0, 2.0]]);
+        let c = from_rows(&[&[1.0, 2.0], &[3.0, 4.0], &[5.0, 6.0]]);
+
+        let x = a.solve_sylvester(&b, &c).unwrap();
+        let residual_a = a.dot(&x);
+        let residual_b = x.dot(&b);
+        for col in 0..2 {
+            for row in 0..3 {
+                assert_float_eq!(
+                    residual_a[[row, col]] + residual_b[[row, col]],
+                    c[[row, col]],
+                    abs <= 1E-12
+                );
+            }
+        }
+
+        // A and -A have the common eigenvalue 0 if A is singular.
+        let singular = from_rows(&[&[0.0, 1.0], &[0.0, 0.0]]);
+        let minus = from_rows(&[&[0.0, -1.0], &[0.0, 0.0]]);
+        assert!(singular.solve_sylvester(&minus, &b).is_err());
+    }
+
+    #[test]
+    fn test_lyapunov_c64() {
+        let a = from_rows(&[
+            &[c64::new(-2.0, 1.0), c64::new(1.0, 0.0)],
+            &[c64::new(0.5, -1.0), c64::new(-3.0, 0.0)],
+        ]);
+        let q = from_rows(&[
+            &[c64::new(1.0, 0.0), c64::new(0.0, 2.0)],
+            &[c64::new(0.0, -2.0), c64::new(5.0, 0.0)],
+        ]);
+
+        let x = a.solve_lyapunov(&q).unwrap();
+        let ax = a.dot(&x);
+        let xah = x.dot(&adjoint(&a));
+        for col in 0..2 {
+            for row in 0..2 {
+                assert_close(ax[[row, col]] + xah[[row, col]], q[[row, col]], 1E-12);
+                // The solution of a Lyapunov equation with Hermitian right-hand side is
+                // Hermitian.
+                assert_close(x[[row, col]], x[[col, row]].conj(), 1E-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,669 @@
+//! Matrix functions of dense matrices.
+//!
+//! The matrix exponential is computed by scaling and squaring with diagonal Padé
+//! approximants (Higham, 2005). The matrix square root and logarithm are computed from the
+//! complex Schur decomposition `A = Z T Z^H` of [schur_decomp](crate::lapack::schur_decomp).
+//! The square root of the triangular factor uses the recurrence of Björck and Hammarling.
+//! The logarithm uses inverse scaling and squaring, taking triangular square roots until `T`
+//! is close to the identity and then evaluating `log(I + X)` with Gauss-Legendre quadrature.
+//! Functions of Hermitian matrices are computed from the eigendecomposition of
+//! [eigh](crate::lapack::LapackData::eigh).
+use crate::lapack::schur_decomp::SchurDecompLapack;
+use crate::lapack::{check_square, AsLapack, TransposeMode, TriangularType};
+use crate::traits::eigenvalue_decomp::{EigenvectorMode, HermitianEigenvalueDecomposition};
+use crate::traits::lu_decomp::LUDecomp;
+use crate::traits::matrix_functions::{MatrixFunctionResult, MatrixFunctions};
+use num::{Float, One, ToPrimitive, Zero};
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::matrix_multiply::Gemm;
+use rlst_dense::{Dot, Layout, LayoutType, Matrix, MatrixD};
+
+// Padé coefficients `b_0, ..., b_m` of the diagonal approximants to the exponential.
+const PADE_3: [f64; 4] = [120.0, 60.0, 12.0, 1.0];
+const PADE_5: [f64; 6] = [30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+const PADE_7: [f64; 8] = [
+    17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+];
+const PADE_9: [f64; 10] = [
+    17643225600.0,
+    8821612800.0,
+    2075673600.0,
+    302702400.0,
+    34594560.0,
+    2162160.0,
+    110880.0,
+    3960.0,
+    90.0,
+    1.0,
+];
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+
+// The diagonal Padé approximants to the exponential in order of increasing degree.
+const PADE: [&[f64]; 5] = [&PADE_3, &PADE_5, &PADE_7, &PADE_9, &PADE_13];
+
+// Largest 1-norms for which the approximants of degree 3, 5, 7, 9 and 13 are accurate
+// to double precision.
+const THETA_DOUBLE: [f64; 5] = [
+    1.495585217958292e-2,
+    2.53939833006323e-1,
+    9.504178996162932e-1,
+    2.097847961257068,
+    5.371920351148152,
+];
+
+// Largest 1-norms for which the approximants of degree 3, 5 and 7 are accurate to single
+// precision. Higher degrees are not needed in single precision.
+const THETA_SINGLE: [f64; 3] = [4.258730016922831e-1, 1.880152677804762, 3.92572478313866];
+
+// Gauss-Legendre nodes and weights on [-1, 1] for the quadrature of `log(I + X)`.
+const GAUSS_LEGENDRE: [(f64, f64); 8] = [
+    (-0.9602898564975363, 0.1012285362903763),
+    (-0.7966664774136267, 0.2223810344533745),
+    (-0.525532409916329, 0.3137066458778873),
+    (-0.1834346424956498, 0.362683783378362),
+    (0.1834346424956498, 0.362683783378362),
+    (0.525532409916329, 0.3137066458778873),
+    (0.7966664774136267, 0.2223810344533745),
+    (0.9602898564975363, 0.1012285362903763),
+];
+
+// The logarithm takes square roots until the distance to the identity is below this value.
+const LOGM_THRESHOLD: f64 = 0.25;
+const LOGM_MAX_SQRT: usize = 64;
+
+// Lapack routines needed by the matrix functions for a given scalar type.
+trait MatFunScalar: Scalar + Gemm {
+    const IS_COMPLEX: bool;
+
+    // The Padé thresholds for the precision of the type. The approximant of the highest
+    // degree is used together with scaling and squaring.
+    const THETA: &'static [f64];
+
+    // Convert from the associated complex type, dropping the imaginary part for real types.
+    fn from_complex(value: Self::Complex) -> Self;
+
+    // Solve `mat * X = rhs` in place via an LU decomposition.
+    fn lu_solve(mat: MatrixD<Self>, rhs: &mut MatrixD<Self>) -> RlstResult<()>;
+
+    // Return the eigenvalues and eigenvectors of a Hermitian matrix.
+    fn eigh(mat: MatrixD<Self>) -> RlstResult<HermitianEigenvalueDecomposition<Self>>;
+
+    // Return the complex Schur decomposition `A = Z T Z^H`.
+    fn complex_schur(mat: MatrixD<Self::Complex>) -> RlstResult<SchurDecompLapack<Self::Complex>>;
+}
+
+macro_rules! mat_fun_scalar_impl {
+    ($scalar:ty, $complex:ty, $is_complex:expr, $theta:expr, $from_complex:expr) => {
+        impl MatFunScalar for $scalar {
+            const IS_COMPLEX: bool = $is_complex;
+            const THETA: &'static [f64] = &$theta;
+
+            fn from_complex(value: Self::Complex) -> Self {
+                $from_complex(value)
+            }
+
+            fn lu_solve(mat: MatrixD<Self>, rhs: &mut MatrixD<Self>) -> RlstResult<()> {
+                mat.lapack()?.lu()?.solve(rhs, TransposeMode::NoTrans)
+            }
+
+            fn complex_schur(mat: MatrixD<$complex>) -> RlstResult<SchurDecompLapack<$complex>> {
+                mat.lapack()?.schur()
+            }
+
+            fn eigh(mat: MatrixD<Self>) -> RlstResult<HermitianEigenvalueDecomposition<Self>> {
+                mat.lapack()?
+                    .eigh(TriangularType::Upper, EigenvectorMode::Right)
+            }
+        }
+    };
+}
+
+mat_fun_scalar_impl!(f64, c64, false, THETA_DOUBLE, |value: c64| value.re());
+mat_fun_scalar_impl!(f32, c32, false, THETA_SINGLE, |value: c32| value.re());
+mat_fun_scalar_impl!(c64, c64, true, THETA_DOUBLE, |value: c64| value);
+mat_fun_scalar_impl!(c32, c32, true, THETA_SINGLE, |value: c32| value);
+
+fn from_f64<T: Scalar>(value: f64) -> T {
+    T::from_real(T::real(value))
+}
+
+fn identity<T: Scalar>(n: IndexType) -> MatrixD<T> {
+    let mut ident = MatrixD::<T>::zeros_from_dim(n, n);
+    for index in 0..n {
+        ident[[index, index]] = T::one();
+    }
+    ident
+}
+
+fn copy<T: Scalar>(mat: &MatrixD<T>) -> MatrixD<T> {
+    Matrix::from_ref(mat).eval()
+}
+
+// Return the sum of `alpha * mat` over all terms.
+fn combine<T: Scalar>(n: IndexType, terms: &[(T, &MatrixD<T>)]) -> MatrixD<T> {
+    let mut result = MatrixD::<T>::zeros_from_dim(n, n);
+    for (alpha, mat) in terms {
+        for col in 0..n {
+            for row in 0..n {
+                result[[row, col]] += *alpha * mat[[row, col]];
+            }
+        }
+    }
+    result
+}
+
+pub(crate) fn adjoint<T: Scalar>(mat: &MatrixD<T>) -> MatrixD<T> {
+    let dim = mat.layout().dim();
+    let mut result = MatrixD::<T>::zeros_from_dim(dim.1, dim.0);
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            result[[col, row]] = mat[[row, col]].conj();
+        }
+    }
+    result
+}
+
+fn norm1<T: Scalar>(mat: &MatrixD<T>) -> T::Real {
+    let dim = mat.layout().dim();
+    (0..dim.1).fold(<T::Real as Zero>::zero(), |acc, col| {
+        let sum = (0..dim.0).fold(<T::Real as Zero>::zero(), |sum, row| {
+            sum + mat[[row, col]].abs()
+        });
+        Float::max(acc, sum)
+    })
+}
+
+fn norm_fro<T: Scalar>(mat: &MatrixD<T>) -> T::Real {
+    let dim = mat.layout().dim();
+    let mut sum = <T::Real as Zero>::zero();
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            sum += mat[[row, col]].square();
+        }
+    }
+    Float::sqrt(sum)
+}
+
+fn relative<Real: Float>(residual: Real, norm: Real) -> Real {
+    if norm > Real::zero() {
+        residual / norm
+    } else {
+        residual
+    }
+}
+
+fn to_complex<T: Scalar>(mat: &MatrixD<T>) -> MatrixD<T::Complex> {
+    let dim = mat.layout().dim();
+    let mut result = MatrixD::<T::Complex>::zeros_from_dim(dim.0, dim.1);
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            result[[row, col]] = mat[[row, col]].as_c();
+        }
+    }
+    result
+}
+
+// Convert a result computed in complex arithmetic back to the scalar type. For real types
+// an error is returned if the imaginary part is not negligible.
+fn from_complex<T: MatFunScalar>(mat: &MatrixD<T::Complex>, name: &str) -> RlstResult<MatrixD<T>> {
+    let dim = mat.layout().dim();
+    if !T::IS_COMPLEX {
+        let mut max_abs = <T::Real as Zero>::zero();
+        let mut max_imag = <T::Real as Zero>::zero();
+        for col in 0..dim.1 {
+            for row in 0..dim.0 {
+                max_abs = Float::max(max_abs, mat[[row, col]].abs());
+                max_imag = Float::max(max_imag, Float::abs(mat[[row, col]].im()));
+            }
+        }
+        if max_imag > Float::sqrt(T::Real::epsilon()) * max_abs {
+            return Err(RlstError::GeneralError(format!(
+                "Matrix has no real principal {}.",
+                name
+            )));
+        }
+    }
+    let mut result = MatrixD::<T>::zeros_from_dim(dim.0, dim.1);
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            result[[row, col]] = T::from_complex(mat[[row, col]]);
+        }
+    }
+    Ok(result)
+}
+
+// Return `U` and `V` such that the Padé approximant with coefficients `coeffs`
+// is `(V - U)^{-1} (V + U)`.
+fn pade_terms<T: MatFunScalar>(mat: &MatrixD<T>, coeffs: &[f64]) -> (MatrixD<T>, MatrixD<T>) {
+    let n = mat.layout().dim().0;
+    let ident = identity::<T>(n);
+    let b = |index: usize| from_f64::<T>(coeffs[index]);
+    let a2 = mat.dot(mat);
+
+    if coeffs.len() == PADE_13.len() {
+        let a4 = a2.dot(&a2);
+        let a6 = a4.dot(&a2);
+
+        let u_high = combine(n, &[(b(13), &a6), (b(11), &a4), (b(9), &a2)]);
+        let u_low = combine(n, &[(b(7), &a6), (b(5), &a4), (b(3), &a2), (b(1), &ident)]);
+        let u_inner = combine(n, &[(T::one(), &a6.dot(&u_high)), (T::one(), &u_low)]);
+
+        let v_high = combine(n, &[(b(12), &a6), (b(10), &a4), (b(8), &a2)]);
+        let v_low = combine(n, &[(b(6), &a6), (b(4), &a4), (b(2), &a2), (b(0), &ident)]);
+        let v = combine(n, &[(T::one(), &a6.dot(&v_high)), (T::one(), &v_low)]);
+
+        (mat.dot(&u_inner), v)
+    } else {
+        let mut power = identity::<T>(n);
+        let mut u_inner = MatrixD::<T>::zeros_from_dim(n, n);
+        let mut v = MatrixD::<T>::zeros_from_dim(n, n);
+        for index in 0..coeffs.len() / 2 {
+            if index > 0 {
+                power = power.dot(&a2);
+            }
+            u_inner = combine(n, &[(T::one(), &u_inner), (b(2 * index + 1), &power)]);
+            v = combine(n, &[(T::one(), &v), (b(2 * index), &power)]);
+        }
+        (mat.dot(&u_inner), v)
+    }
+}
+
+fn factorial(n: usize) -> f64 {
+    (1..=n).fold(1.0, |acc, k| acc * k as f64)
+}
+
+fn expm<T: MatFunScalar>(mat: &MatrixD<T>) -> RlstResult<MatrixFunctionResult<T>> {
+    let n = check_square(mat.layout().dim())?;
+    if n == 0 {
+        return Ok(MatrixFunctionResult {
+            value: MatrixD::<T>::zeros_from_dim(0, 0),
+            error_estimate: <T::Real as Zero>::zero(),
+        });
+    }
+
+    let norm = norm1(mat).to_f64().unwrap();
+    if !norm.is_finite() {
+        return Err(RlstError::GeneralError(
+            "Matrix has entries that are not finite.".to_string(),
+        ));
+    }
+    let highest = T::THETA.len() - 1;
+    let (coeffs, squarings) = match T::THETA[..highest].iter().position(|&theta| norm <= theta) {
+        Some(index) => (PADE[index], 0),
+        None => (
+            PADE[highest],
+            (norm / T::THETA[highest]).log2().ceil().max(0.0) as i32,
+        ),
+    };
+
+    let scale = 2.0_f64.powi(-squarings);
+    let scaled = combine(n, &[(from_f64::<T>(scale), mat)]);
+    let (u, v) = pade_terms(&scaled, coeffs);
+
+    let mut value = combine(n, &[(T::one(), &v), (T::one(), &u)]);
+    T::lu_solve(combine(n, &[(T::one(), &v), (-T::one(), &u)]), &mut value)?;
+    for _ in 0..squarings {
+        value = value.dot(&value);
+    }
+
+    // The Padé approximant of degree m has the error term
+    // (m!)^2 / ((2m)! (2m + 1)!) x^(2m + 1) for small x.
+    let degree = coeffs.len() - 1;
+    let eta = norm * scale;
+    let truncation = factorial(degree).powi(2)
+        / (factorial(2 * degree) * factorial(2 * degree + 1))
+        * eta.powi(2 * degree as i32 + 1);
+    let rounding = n as f64 * T::Real::epsilon().to_f64().unwrap();
+    let error_estimate = T::real(2.0_f64.powi(squarings) * (truncation + rounding));
+
+    Ok(MatrixFunctionResult {
+        value,
+        error_estimate,
+    })
+}
+
+// Square root of an upper triangular matrix by the Björck-Hammarling recurrence.
+fn triangular_sqrt<C: Scalar>(mat: &MatrixD<C>) -> RlstResult<MatrixD<C>> {
+    let n = mat.layout().dim().0;
+    let mut result = MatrixD::<C>::zeros_from_dim(n, n);
+    for col in 0..n {
+        result[[col, col]] = mat[[col, col]].sqrt();
+        for row in (0..col).rev() {
+            let mut sum = mat[[row, col]];
+            for k in row + 1..col {
+                sum -= result[[row, k]] * result[[k, col]];
+            }
+            let denominator = result[[row, row]] + result[[col, col]];
+            if denominator == C::zero() {
+                if sum == C::zero() {
+                    continue;
+                }
+                return Err(RlstError::SingularMatrix);
+            }
+            result[[row, col]] = sum / denominator;
+        }
+    }
+    Ok(result)
+}
+
+// Solve `mat * X = rhs` for an upper triangular matrix `mat`.
+fn triangular_solve<C: Scalar>(mat: &MatrixD<C>, rhs: &MatrixD<C>) -> MatrixD<C> {
+    let n = mat.layout().dim().0;
+    let ncols = rhs.layout().dim().1;
+    let mut result = MatrixD::<C>::zeros_from_dim(n, ncols);
+    for col in 0..ncols {
+        for row in (0..n).rev() {
+            let mut sum = rhs[[row, col]];
+            for k in row + 1..n {
+                sum -= mat[[row, k]] * result[[k, col]];
+            }
+            result[[row, col]] = sum / mat[[row, row]];
+        }
+    }
+    result
+}
+
+fn sqrtm<T: MatFunScalar>(mat: &MatrixD<T>) -> RlstResult<MatrixFunctionResult<T>>
+where
+    T::Complex: Gemm,
+{
+    let n = check_square(mat.layout().dim())?;
+    if n == 0 {
+        return Ok(MatrixFunctionResult {
+            value: MatrixD::<T>::zeros_from_dim(0, 0),
+            error_estimate: <T::Real as Zero>::zero(),
+        });
+    }
+
+    let (schur, unitary) = T::complex_schur(to_complex(mat))?.into_factors();
+    let root = triangular_sqrt(&schur)?;
+    let value = from_complex::<T>(&unitary.dot(&root).dot(&adjoint(&unitary)), "square root")?;
+
+    let residual = norm_fro(&combine(
+        n,
+        &[(T::one(), &value.dot(&value)), (-T::one(), mat)],
+    ));
+    Ok(MatrixFunctionResult {
+        value,
+        error_estimate: relative(residual, norm_fro(mat)),
+    })
+}
+
+fn logm<T: MatFunScalar>(mat: &MatrixD<T>) -> RlstResult<MatrixFunctionResult<T>>
+where
+    T::Complex: Gemm,
+{
+    let n = check_square(mat.layout().dim())?;
+    if n == 0 {
+        return Ok(MatrixFunctionResult {
+            value: MatrixD::<T>::zeros_from_dim(0, 0),
+            error_estimate: <T::Real as Zero>::zero(),
+        });
+    }
+
+    let (mut schur, unitary) = T::complex_schur(to_complex(mat))?.into_factors();
+    if (0..n).any(|index| schur[[index, index]] == T::Complex::zero()) {
+        return Err(RlstError::SingularMatrix);
+    }
+
+    let one = T::Complex::one();
+    let ident = identity::<T::Complex>(n);
+    let threshold = T::real(LOGM_THRESHOLD);
+    let mut square_roots = 0;
+    while norm1(&combine(n, &[(one, &schur), (-one, &ident)])) > threshold {
+        if square_roots == LOGM_MAX_SQRT {
+            return Err(RlstError::OperationFailed("logm".to_string()));
+        }
+        schur = triangular_sqrt(&schur)?;
+        square_roots += 1;
+    }
+
+    // log(I + X) = int_0^1 X (I + t X)^{-1} dt
+    let x = combine(n, &[(one, &schur), (-one, &ident)]);
+    let mut log = MatrixD::<T::Complex>::zeros_from_dim(n, n);
+    for (node, weight) in GAUSS_LEGENDRE {
+        let t = from_f64::<T::Complex>(0.5 * (node + 1.0));
+        let shifted = combine(n, &[(one, &ident), (t, &x)]);
+        let term = triangular_solve(&shifted, &x);
+        log = combine(n, &[(one, &log), (from_f64(0.5 * weight), &term)]);
+    }
+    let log = combine(n, &[(from_f64(2.0_f64.powi(square_roots as i32)), &log)]);
+
+    let value = from_complex::<T>(&unitary.dot(&log).dot(&adjoint(&unitary)), "logarithm")?;
+
+    let exponential = expm(&value)?.value;
+    let residual = norm_fro(&combine(n, &[(T::one(), &exponential), (-T::one(), mat)]));
+    Ok(MatrixFunctionResult {
+        value,
+        error_estimate: relative(residual, norm_fro(mat)),
+    })
+}
+
+fn funm<T: MatFunScalar, F: Fn(T::Real) -> T::Real>(
+    mat: &MatrixD<T>,
+    f: F,
+) -> RlstResult<MatrixFunctionResult<T>> {
+    let n = check_square(mat.layout().dim())?;
+    let norm = norm_fro(mat);
+
+    let mut asymmetry = <T::Real as Zero>::zero();
+    for col in 0..n {
+        for row in 0..=col {
+            asymmetry = Float::max(asymmetry, (mat[[row, col]] - mat[[col, row]].conj()).abs());
+        }
+    }
+    if asymmetry > T::real(10 * n) * T::Real::epsilon() * norm {
+        return Err(RlstError::GeneralError(
+            "Matrix is not Hermitian.".to_string(),
+        ));
+    }
+    if n == 0 {
+        return Ok(MatrixFunctionResult {
+            value: MatrixD::<T>::zeros_from_dim(0, 0),
+            error_estimate: <T::Real as Zero>::zero(),
+        });
+    }
+
+    let eigh = T::eigh(copy(mat))?;
+    let eigenvectors = eigh.eigenvectors.unwrap();
+
+    let mut scaled = copy(&eigenvectors);
+    let mut residual = mat.dot(&eigenvectors);
+    for (col, &lambda) in eigh.eigenvalues.data().iter().enumerate() {
+        let value = T::from_real(f(lambda));
+        for row in 0..n {
+            scaled[[row, col]] *= value;
+            residual[[row, col]] -= eigenvectors[[row, col]].mul_real(lambda);
+        }
+    }
+
+    Ok(MatrixFunctionResult {
+        value: scaled.dot(&adjoint(&eigenvectors)),
+        error_estimate: relative(norm_fro(&residual), norm),
+    })
+}
+
+macro_rules! matrix_functions_impl {
+    ($scalar:ty) => {
+        impl MatrixFunctions for MatrixD<$scalar> {
+            type T = $scalar;
+
+            fn expm(&self) -> RlstResult<MatrixFunctionResult<Self::T>> {
+                expm(self)
+            }
+
+            fn logm(&self) -> RlstResult<MatrixFunctionResult<Self::T>> {
+                logm(self)
+            }
+
+            fn sqrtm(&self) -> RlstResult<MatrixFunctionResult<Self::T>> {
+                sqrtm(self)
+            }
+
+            fn funm<F: Fn(<Self::T as Scalar>::Real) -> <Self::T as Scalar>::Real>(
+                &self,
+                f: F,
+            ) -> RlstResult<MatrixFunctionResult<Self::T>> {
+                funm(self, f)
+            }
+        }
+    };
+}
+
+matrix_functions_impl!(f64);
+matrix_functions_impl!(f32);
+matrix_functions_impl!(c32);
+matrix_functions_impl!(c64);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use float_eq::assert_float_eq;
+
+    fn assert_matrix_eq<T: Scalar>(actual: &MatrixD<T>, expected: &MatrixD<T>, tol: f64) {
+        let n = actual.layout().dim().0;
+        let diff = combine(n, &[(T::one(), actual), (-T::one(), expected)]);
+        let error = relative(norm_fro(&diff), norm_fro(expected));
+        assert!(
+            error.to_f64().unwrap() < tol,
+            "Relative error {} exceeds tolerance {}.",
+            error,
+            tol
+        );
+    }
+
+    #[test]
+    fn test_expm_diagonal() {
+        let mut mat = rlst_dense::rlst_mat![f64, (2, 2)];
+        mat[[0, 0]] = 0.5;
+        mat[[1, 1]] = 12.0;
+
+        let result = mat.expm().unwrap();
+        assert_float_eq!(result.value[[0, 0]], 0.5_f64.exp(), rmax <= 1e-14);
+        assert_float_eq!(result.value[[1, 1]], 12.0_f64.exp(), rmax <= 1e-13);
+        assert_float_eq!(result.value[[0, 1]], 0.0, abs <= 1e-10);
+        assert!(result.error_estimate < 1e-12);
+    }
+
+    #[test]
+    fn test_expm_rotation() {
+        // exp([[0, t], [-t, 0]]) is a rotation by the angle t.
+        let t = 3.0_f64;
+        let mut mat = rlst_dense::rlst_mat![f64, (2, 2)];
+        mat[[0, 1]] = t;
+        mat[[1, 0]] = -t;
+
+        let result = mat.expm().unwrap();
+        let mut expected = rlst_dense::rlst_mat![f64, (2, 2)];
+        expected[[0, 0]] = t.cos();
+        expected[[0, 1]] = t.sin();
+        expected[[1, 0]] = -t.sin();
+        expected[[1, 1]] = t.cos();
+        assert_matrix_eq(&result.value, &expected, 1e-13);
+    }
+
+    #[test]
+    fn test_expm_single_precision() {
+        // The single precision approximant of degree 7 is used without scaling for the
+        // 1-norm 3, for which double precision requires degree 13.
+        let t = 3.0_f32;
+        let mut mat = rlst_dense::rlst_mat![f32, (2, 2)];
+        mat[[0, 1]] = t;
+        mat[[1, 0]] = -t;
+
+        let result = mat.expm().unwrap();
+        let mut expected = rlst_dense::rlst_mat![f32, (2, 2)];
+        expected[[0, 0]] = t.cos();
+        expected[[0, 1]] = t.sin();
+        expected[[1, 0]] = -t.sin();
+        expected[[1, 1]] = t.cos();
+        assert_matrix_eq(&result.value, &expected, 1e-5);
+        assert!(result.error_estimate < 1e-5);
+    }
+
+    #[test]
+    fn test_expm_infinite_entry() {
+        let mut mat = rlst_dense::rlst_mat![f64, (2, 2)];
+        mat[[0, 1]] = f64::INFINITY;
+
+        assert!(matches!(mat.expm(), Err(RlstError::GeneralError(_))));
+    }
+
+    #[test]
+    fn test_sqrtm_logm_roundtrip() {
+        let mut mat = rlst_dense::rlst_mat![c64, (3, 3)];
+        mat[[0, 0]] = c64::new(4.0, 1.0);
+        mat[[0, 1]] = c64::new(1.0, 0.0);
+        mat[[1, 1]] = c64::new(3.0, 0.0);
+        mat[[1, 2]] = c64::new(0.0, 2.0);
+        mat[[2, 0]] = c64::new(0.5, 0.0);
+        mat[[2, 2]] = c64::new(5.0, -1.0);
+
+        let root = mat.sqrtm().unwrap();
+        assert!(root.error_estimate < 1e-12);
+        assert_matrix_eq(&root.value.dot(&root.value), &mat, 1e-12);
+
+        let log = mat.logm().unwrap();
+        assert!(log.error_estimate < 1e-12);
+        assert_matrix_eq(&log.value.expm().unwrap().value, &mat, 1e-12);
+    }
+
+    #[test]
+    fn test_real_sqrtm() {
+        let mut mat = rlst_dense::rlst_mat![f64, (2, 2)];
+        mat[[0, 0]] = 5.0;
+        mat[[0, 1]] = 4.0;
+        mat[[1, 0]] = 4.0;
+        mat[[1, 1]] = 5.0;
+
+        // The eigenvalues are 1 and 9, so the square root is [[2, 1], [1, 2]].
+        let root = mat.sqrtm().unwrap();
+        let mut expected = rlst_dense::rlst_mat![f64, (2, 2)];
+        expected[[0, 0]] = 2.0;
+        expected[[0, 1]] = 1.0;
+        expected[[1, 0]] = 1.0;
+        expected[[1, 1]] = 2.0;
+        assert_matrix_eq(&root.value, &expected, 1e-13);
+
+        let mut negative = rlst_dense::rlst_mat![f64, (2, 2)];
+        negative[[0, 0]] = -1.0;
+        negative[[1, 1]] = 2.0;
+        assert!(negative.sqrtm().is_err());
+        assert!(negative.logm().is_err());
+    }
+
+    #[test]
+    fn test_funm() {
+        let mut mat = rlst_dense::rlst_mat![f64, (2, 2)];
+        mat[[0, 0]] = 5.0;
+        mat[[0, 1]] = 4.0;
+        mat[[1, 0]] = 4.0;
+        mat[[1, 1]] = 5.0;
+
+        let root = mat.funm(|lambda| lambda.sqrt()).unwrap();
+        assert!(root.error_estimate < 1e-13);
+        assert_matrix_eq(&root.value, &mat.sqrtm().unwrap().value, 1e-13);
+
+        let exponential = mat.funm(|lambda| lambda.exp()).unwrap();
+        assert_matrix_eq(&exponential.value, &mat.expm().unwrap().value, 1e-12);
+
+        mat[[0, 1]] = 3.0;
+        assert!(mat.funm(|lambda| lambda).is_err());
+    }
+}
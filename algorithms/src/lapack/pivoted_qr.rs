@@ -0,0 +1,294 @@
+//! Column-pivoted QR decomposition of dense matrices.
+//!
+//! The decomposition `A P = Q R` is computed with `geqp3`. The pivoting ensures that the
+//! magnitudes of the diagonal entries of `R` are non-increasing, so that the numerical
+//! rank can be read off the diagonal. Partitioning `R = [R11 R12; 0 R22]` with a `k x k`
+//! block `R11` gives the interpolative decomposition `A ~ C [I, R11^{-1} R12] P^T`, where
+//! `C` contains the first `k` pivot columns of `A`.
+use crate::lapack::qr_decomp::QRDecompLapack;
+use crate::lapack::LapackData;
+use crate::traits::pivoted_qr::{InterpolativeDecomposition, PivotedQRDecomp};
+use crate::traits::qr_decomp::{QRDecomp, QRMode};
+use lapacke;
+use num::{One, Zero};
+use rlst_common::permutation::Permutation;
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, MatrixD, MatrixTraitMut,
+    SizeIdentifier,
+};
+
+use super::TransposeMode;
+
+pub struct PivotedQRDecompLapack<
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    Mat: MatrixTraitMut<Item, RS, CS> + Sized,
+> {
+    qr: QRDecompLapack<Item, RS, CS, Mat>,
+    jpvt: Vec<i32>,
+}
+
+macro_rules! pivoted_qr_impl {
+    ($scalar:ty, $lapack_geqp3:ident, $lapack_trtrs:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            pub fn pivoted_qr(
+                mut self,
+            ) -> RlstResult<
+                PivotedQRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>,
+            > {
+                let dim = self.mat.layout().dim();
+                let stride = self.mat.layout().stride();
+
+                let m = dim.0 as i32;
+                let n = dim.1 as i32;
+                let lda = stride.1 as i32;
+
+                // A zero entry marks a column as free to be pivoted.
+                let mut jpvt: Vec<i32> = vec![0; dim.1];
+                let mut tau: Vec<$scalar> =
+                    vec![<$scalar as Zero>::zero(); std::cmp::min(dim.0, dim.1)];
+                let info = unsafe {
+                    lapacke::$lapack_geqp3(
+                        lapacke::Layout::ColumnMajor,
+                        m,
+                        n,
+                        self.mat.data_mut(),
+                        lda,
+                        &mut jpvt,
+                        &mut tau,
+                    )
+                };
+                if info == 0 {
+                    Ok(PivotedQRDecompLapack {
+                        qr: QRDecompLapack {
+                            data: self,
+                            tau,
+                            work: Vec::new(),
+                        },
+                        jpvt,
+                    })
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            PivotedQRDecomp
+            for PivotedQRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            type T = $scalar;
+            type QR = QRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>;
+
+            fn qr(&self) -> &Self::QR {
+                &self.qr
+            }
+
+            fn permutation(&self) -> RlstResult<Permutation> {
+                Permutation::from_indices(
+                    self.jpvt
+                        .iter()
+                        .map(|&pivot| (pivot - 1) as IndexType)
+                        .collect(),
+                )
+            }
+
+            fn rank(&self, tol: <$scalar as Scalar>::Real) -> IndexType {
+                let mat = &self.qr.data.mat;
+                let k = self.qr.tau.len();
+                if k == 0 {
+                    return 0;
+                }
+                let threshold = tol * mat[[0, 0]].abs();
+                (0..k)
+                    .take_while(|&index| mat[[index, index]].abs() > threshold)
+                    .count()
+            }
+
+            fn interpolative_decomposition(
+                &self,
+                k: IndexType,
+            ) -> RlstResult<InterpolativeDecomposition<Self::T>> {
+                let (m, n) = self.qr.dim();
+                if k > std::cmp::min(m, n) {
+                    return Err(RlstError::GeneralError(format!(
+                        "Rank {} of interpolative decomposition exceeds the dimensions {} x {}.",
+                        k, m, n
+                    )));
+                }
+                let mat = &self.qr.data.mat;
+                let perm = self.permutation()?;
+
+                // The skeleton columns are the first `k` columns of `A P = Q R`.
+                let mut skeleton = MatrixD::<$scalar>::zeros_from_dim(m, k);
+                for col in 0..k {
+                    for row in 0..=col {
+                        skeleton[[row, col]] = mat[[row, col]];
+                    }
+                }
+                if k > 0 {
+                    self.qr.apply_q(&mut skeleton, TransposeMode::NoTrans)?;
+                }
+
+                // Solve `R11 T = R12` for the coefficients of the remaining columns.
+                let mut coefficients = MatrixD::<$scalar>::zeros_from_dim(k, n - k);
+                for col in 0..n - k {
+                    for row in 0..k {
+                        coefficients[[row, col]] = mat[[row, k + col]];
+                    }
+                }
+                if k > 0 && n > k {
+                    let r11 = self.qr.r(QRMode::Thin);
+                    let mut block = MatrixD::<$scalar>::zeros_from_dim(k, k);
+                    for col in 0..k {
+                        for row in 0..=col {
+                            block[[row, col]] = r11[[row, col]];
+                        }
+                    }
+                    let info = unsafe {
+                        lapacke::$lapack_trtrs(
+                            lapacke::Layout::ColumnMajor,
+                            b'U',
+                            b'N',
+                            b'N',
+                            k as i32,
+                            (n - k) as i32,
+                            block.data(),
+                            k as i32,
+                            coefficients.data_mut(),
+                            k as i32,
+                        )
+                    };
+                    if info > 0 {
+                        return Err(RlstError::SingularMatrix);
+                    } else if info < 0 {
+                        return Err(RlstError::LapackError(info));
+                    }
+                }
+
+                // Assemble `X = [I, T] P^T`.
+                let mut interpolation = MatrixD::<$scalar>::zeros_from_dim(k, n);
+                for col in 0..n {
+                    let target = perm.get(col);
+                    for row in 0..k {
+                        interpolation[[row, target]] = if col < k {
+                            if row == col {
+                                <$scalar as One>::one()
+                            } else {
+                                <$scalar as Zero>::zero()
+                            }
+                        } else {
+                            coefficients[[row, col - k]]
+                        };
+                    }
+                }
+
+                Ok(InterpolativeDecomposition {
+                    skeleton_indices: perm.indices()[..k].to_vec(),
+                    skeleton,
+                    interpolation,
+                })
+            }
+        }
+    };
+}
+
+pivoted_qr_impl!(f64, dgeqp3, dtrtrs);
+pivoted_qr_impl!(f32, sgeqp3, strtrs);
+pivoted_qr_impl!(c32, cgeqp3, ctrtrs);
+pivoted_qr_impl!(c64, zgeqp3, ztrtrs);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix};
+
+    // A 6 x 5 matrix of rank 3 whose columns 1 and 3 are combinations of the others.
+    fn rank_deficient_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(6, 5);
+        for row in 0..6 {
+            let x = row as f64;
+            mat[[row, 0]] = 1.0;
+            mat[[row, 2]] = x;
+            mat[[row, 4]] = x * x;
+            mat[[row, 1]] = 2.0 - x;
+            mat[[row, 3]] = 3.0 * x * x + 0.5;
+        }
+        mat
+    }
+
+    #[test]
+    fn test_pivoted_qr() {
+        let mat = rank_deficient_matrix();
+        let pivoted = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .pivoted_qr()
+            .unwrap();
+
+        assert_eq!(pivoted.rank(1E-10), 3);
+
+        // Check A P = Q R.
+        let perm = pivoted.permutation().unwrap();
+        let product = pivoted
+            .qr()
+            .q(QRMode::Thin)
+            .unwrap()
+            .dot(&pivoted.qr().r(QRMode::Thin));
+        for col in 0..5 {
+            for row in 0..6 {
+                assert_float_eq!(product[[row, col]], mat[[row, perm.get(col)]], abs <= 1E-12);
+            }
+        }
+
+        // The diagonal of R is non-increasing in magnitude.
+        let r = pivoted.qr().r(QRMode::Thin);
+        for index in 1..5 {
+            assert!(r[[index, index]].abs() <= r[[index - 1, index - 1]].abs());
+        }
+    }
+
+    #[test]
+    fn test_interpolative_decomposition() {
+        let mat = rank_deficient_matrix();
+        let pivoted = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .pivoted_qr()
+            .unwrap();
+
+        let id = pivoted.interpolative_decomposition(3).unwrap();
+        assert_eq!(id.skeleton_indices.len(), 3);
+        assert_eq!(id.skeleton.layout().dim(), (6, 3));
+        assert_eq!(id.interpolation.layout().dim(), (3, 5));
+
+        for (index, &col) in id.skeleton_indices.iter().enumerate() {
+            for row in 0..6 {
+                assert_float_eq!(id.skeleton[[row, index]], mat[[row, col]], abs <= 1E-12);
+            }
+            for other in 0..3 {
+                let expected = if other == index { 1.0 } else { 0.0 };
+                assert_float_eq!(id.interpolation[[other, col]], expected, abs <= 1E-12);
+            }
+        }
+
+        // The matrix has rank 3, so the decomposition is exact up to rounding.
+        let product = id.skeleton.dot(&id.interpolation);
+        for col in 0..5 {
+            for row in 0..6 {
+                assert_float_eq!(product[[row, col]], mat[[row, col]], abs <= 1E-10);
+            }
+        }
+
+        assert!(pivoted.interpolative_decomposition(6).is_err());
+    }
+}
@@ -0,0 +1,486 @@
+//! QR decomposition of dense matrices.
+//!
+//! The decomposition `A = Q R` is computed with `geqrf`, which stores `R` in the upper
+//! triangle and the Householder reflectors defining `Q` below the diagonal. `Q` is applied
+//! with `ormqr`/`unmqr` and formed explicitly with `orgqr`/`ungqr`.
+use crate::lapack::LapackData;
+use crate::traits::qr_decomp::{QRDecomp, QRMode};
+use lapacke;
+use num::{ToPrimitive, Zero};
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainer, DataContainerMut, GenericBaseMatrix, GenericBaseMatrixMut, Layout, LayoutType,
+    MatrixD, MatrixTraitMut, RandomAccessByValue, SizeIdentifier,
+};
+
+use super::{check_lapack_stride, copy_matrix, TransposeMode};
+
+pub struct QRDecompLapack<
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    Mat: MatrixTraitMut<Item, RS, CS> + Sized,
+> {
+    pub(crate) data: LapackData<Item, RS, CS, Mat>,
+    pub(crate) tau: Vec<Item>,
+    // Workspace of `geqrf` kept for refactorizations.
+    pub(crate) work: Vec<Item>,
+}
+
+/// Return the `trans` argument of `ormqr`/`unmqr` for a transpose mode.
+fn qr_trans(trans: TransposeMode, is_complex: bool) -> RlstResult<u8> {
+    match (trans, is_complex) {
+        (TransposeMode::NoTrans, _) => Ok(b'N'),
+        (TransposeMode::Trans, false) | (TransposeMode::ConjugateTrans, false) => Ok(b'T'),
+        (TransposeMode::ConjugateTrans, true) => Ok(b'C'),
+        (TransposeMode::Trans, true) => Err(RlstError::NotImplemented(
+            "Application of Q^T for complex QR decompositions".to_string(),
+        )),
+    }
+}
+
+macro_rules! qr_decomp_impl {
+    ($scalar:ty, $is_complex:expr, $lapack_geqrf_work:ident, $lapack_ormqr:ident, $lapack_orgqr:ident, $lapack_trtrs:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            pub fn qr(
+                mut self,
+            ) -> RlstResult<
+                QRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>,
+            > {
+                let dim = self.mat.layout().dim();
+                let stride = self.mat.layout().stride();
+
+                let m = dim.0 as i32;
+                let n = dim.1 as i32;
+                let lda = stride.1 as i32;
+
+                let mut tau: Vec<$scalar> =
+                    vec![<$scalar as Zero>::zero(); std::cmp::min(dim.0, dim.1)];
+
+                // Query the optimal workspace size.
+                let mut work = vec![<$scalar as Zero>::zero(); 1];
+                let info = unsafe {
+                    lapacke::$lapack_geqrf_work(
+                        lapacke::Layout::ColumnMajor,
+                        m,
+                        n,
+                        self.mat.data_mut(),
+                        lda,
+                        &mut tau,
+                        &mut work,
+                        -1,
+                    )
+                };
+                if info != 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+                let lwork = std::cmp::max(1, work[0].re().to_usize().unwrap());
+                work.resize(lwork, <$scalar as Zero>::zero());
+
+                let info = unsafe {
+                    lapacke::$lapack_geqrf_work(
+                        lapacke::Layout::ColumnMajor,
+                        m,
+                        n,
+                        self.mat.data_mut(),
+                        lda,
+                        &mut tau,
+                        &mut work,
+                        lwork as i32,
+                    )
+                };
+                if info == 0 {
+                    Ok(QRDecompLapack {
+                        data: self,
+                        tau,
+                        work,
+                    })
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            QRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            /// Recompute the decomposition for a new matrix of the same dimension.
+            ///
+            /// The matrix is copied into the storage of the decomposition and the
+            /// Householder coefficients and the workspace are reused, so that no memory
+            /// is allocated.
+            pub fn refactor<
+                AData: DataContainer<Item = $scalar>,
+                AR: SizeIdentifier,
+                AC: SizeIdentifier,
+            >(
+                &mut self,
+                mat: &GenericBaseMatrix<$scalar, AData, AR, AC>,
+            ) -> RlstResult<()> {
+                copy_matrix(mat, &mut self.data.mat)?;
+
+                let dim = self.data.mat.layout().dim();
+                let lda = self.data.mat.layout().stride().1;
+                let lwork = self.work.len();
+                let info = unsafe {
+                    lapacke::$lapack_geqrf_work(
+                        lapacke::Layout::ColumnMajor,
+                        dim.0 as i32,
+                        dim.1 as i32,
+                        self.data.mat.data_mut(),
+                        lda as i32,
+                        &mut self.tau,
+                        &mut self.work,
+                        lwork as i32,
+                    )
+                };
+                if info == 0 {
+                    Ok(())
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $scalar>, RS: SizeIdentifier, CS: SizeIdentifier>
+            QRDecomp
+            for QRDecompLapack<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            type T = $scalar;
+
+            fn data(&self) -> &[Self::T] {
+                self.data.mat.data()
+            }
+
+            fn dim(&self) -> (IndexType, IndexType) {
+                self.data.mat.dim()
+            }
+
+            fn apply_q<
+                RhsData: DataContainerMut<Item = Self::T>,
+                RhsR: SizeIdentifier,
+                RhsC: SizeIdentifier,
+            >(
+                &self,
+                rhs: &mut GenericBaseMatrixMut<Self::T, RhsData, RhsR, RhsC>,
+                trans: TransposeMode,
+            ) -> RlstResult<()> {
+                let trans = qr_trans(trans, $is_complex)?;
+                let dim = self.dim();
+                let rhs_dim = rhs.layout().dim();
+
+                if rhs_dim.0 != dim.0 {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: dim.0,
+                        actual: rhs_dim.0,
+                    });
+                }
+                if !check_lapack_stride(rhs_dim, rhs.layout().stride()) {
+                    return Err(RlstError::IncompatibleStride);
+                }
+
+                let mat = &self.data.mat;
+                let ldc = rhs.layout().stride().1 as i32;
+                let info = unsafe {
+                    lapacke::$lapack_ormqr(
+                        lapacke::Layout::ColumnMajor,
+                        b'L',
+                        trans,
+                        rhs_dim.0 as i32,
+                        rhs_dim.1 as i32,
+                        self.tau.len() as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        &self.tau,
+                        rhs.data_mut(),
+                        ldc,
+                    )
+                };
+
+                if info == 0 {
+                    Ok(())
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+
+            fn q(&self, mode: QRMode) -> RlstResult<MatrixD<Self::T>> {
+                let (m, n) = self.dim();
+                let k = self.tau.len();
+                let cols = match mode {
+                    QRMode::Thin => k,
+                    QRMode::Full => m,
+                };
+
+                // Copy the Householder reflectors into the leading columns of `Q`.
+                let mat = &self.data.mat;
+                let mut q = MatrixD::<$scalar>::zeros_from_dim(m, cols);
+                for col in 0..std::cmp::min(cols, n) {
+                    for row in 0..m {
+                        q[[row, col]] = mat[[row, col]];
+                    }
+                }
+
+                let info = unsafe {
+                    lapacke::$lapack_orgqr(
+                        lapacke::Layout::ColumnMajor,
+                        m as i32,
+                        cols as i32,
+                        k as i32,
+                        q.data_mut(),
+                        std::cmp::max(1, m) as i32,
+                        &self.tau,
+                    )
+                };
+
+                if info == 0 {
+                    Ok(q)
+                } else {
+                    Err(RlstError::LapackError(info))
+                }
+            }
+
+            fn r(&self, mode: QRMode) -> MatrixD<Self::T> {
+                let (m, n) = self.dim();
+                let rows = match mode {
+                    QRMode::Thin => self.tau.len(),
+                    QRMode::Full => m,
+                };
+
+                let mat = &self.data.mat;
+                let mut r = MatrixD::<$scalar>::zeros_from_dim(rows, n);
+                for col in 0..n {
+                    for row in 0..std::cmp::min(col + 1, rows) {
+                        r[[row, col]] = mat[[row, col]];
+                    }
+                }
+                r
+            }
+
+            fn solve_least_squares<
+                RhsData: DataContainer<Item = Self::T>,
+                RhsR: SizeIdentifier,
+                RhsC: SizeIdentifier,
+            >(
+                &self,
+                rhs: &GenericBaseMatrix<Self::T, RhsData, RhsR, RhsC>,
+            ) -> RlstResult<MatrixD<Self::T>> {
+                let (m, n) = self.dim();
+                if m < n {
+                    return Err(RlstError::NotImplemented(
+                        "Least-squares solution of underdetermined systems".to_string(),
+                    ));
+                }
+                let rhs_dim = rhs.layout().dim();
+                if rhs_dim.0 != m {
+                    return Err(RlstError::SingleDimensionError {
+                        expected: m,
+                        actual: rhs_dim.0,
+                    });
+                }
+
+                let mut work = MatrixD::<$scalar>::zeros_from_dim(m, rhs_dim.1);
+                for col in 0..rhs_dim.1 {
+                    for row in 0..m {
+                        work[[row, col]] = rhs.get_value(row, col);
+                    }
+                }
+                self.apply_q(&mut work, TransposeMode::ConjugateTrans)?;
+
+                // Solve `R x = (Q^H rhs)[0..n]` using the leading `n` rows of `work`.
+                let mat = &self.data.mat;
+                let info = unsafe {
+                    lapacke::$lapack_trtrs(
+                        lapacke::Layout::ColumnMajor,
+                        b'U',
+                        b'N',
+                        b'N',
+                        n as i32,
+                        rhs_dim.1 as i32,
+                        mat.data(),
+                        mat.layout().stride().1 as i32,
+                        work.data_mut(),
+                        std::cmp::max(1, m) as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::SingularMatrix);
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let mut sol = MatrixD::<$scalar>::zeros_from_dim(n, rhs_dim.1);
+                for col in 0..rhs_dim.1 {
+                    for row in 0..n {
+                        sol[[row, col]] = work[[row, col]];
+                    }
+                }
+                Ok(sol)
+            }
+        }
+    };
+}
+
+qr_decomp_impl!(f64, false, dgeqrf_work, dormqr, dorgqr, dtrtrs);
+qr_decomp_impl!(f32, false, sgeqrf_work, sormqr, sorgqr, strtrs);
+qr_decomp_impl!(c32, true, cgeqrf_work, cunmqr, cungqr, ctrtrs);
+qr_decomp_impl!(c64, true, zgeqrf_work, zunmqr, zungqr, ztrtrs);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix};
+
+    fn assert_close(actual: c64, expected: c64) {
+        assert!(
+            (actual - expected).norm() < 1E-12,
+            "Expected {}, got {}.",
+            expected,
+            actual
+        );
+    }
+
+    fn example_matrix() -> MatrixD<c64> {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(4, 3);
+        for row in 0..4 {
+            for col in 0..3 {
+                mat[[row, col]] = c64::new((1 + row * col) as f64, row as f64 - col as f64);
+            }
+        }
+        mat[[0, 0]] = c64::new(5.0, 0.0);
+        mat
+    }
+
+    #[test]
+    fn test_qr_reconstruction() {
+        let mat = example_matrix();
+        let qr = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .qr()
+            .unwrap();
+
+        for mode in [QRMode::Thin, QRMode::Full] {
+            let q = qr.q(mode).unwrap();
+            let r = qr.r(mode);
+            let product = q.dot(&r);
+            for row in 0..4 {
+                for col in 0..3 {
+                    assert_close(product[[row, col]], mat[[row, col]]);
+                }
+            }
+
+            let cols = q.layout().dim().1;
+            for first in 0..cols {
+                for second in 0..cols {
+                    let inner: c64 = (0..4)
+                        .map(|row| q[[row, first]].conj() * q[[row, second]])
+                        .sum();
+                    let expected = if first == second { 1.0 } else { 0.0 };
+                    assert_close(inner, c64::new(expected, 0.0));
+                }
+            }
+        }
+
+        assert_eq!(qr.q(QRMode::Thin).unwrap().layout().dim(), (4, 3));
+        assert_eq!(qr.q(QRMode::Full).unwrap().layout().dim(), (4, 4));
+        assert_eq!(qr.r(QRMode::Thin).layout().dim(), (3, 3));
+        assert_eq!(qr.r(QRMode::Full).layout().dim(), (4, 3));
+    }
+
+    #[test]
+    fn test_qr_apply_q() {
+        let mat = example_matrix();
+        let qr = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .qr()
+            .unwrap();
+        let q = qr.q(QRMode::Full).unwrap();
+
+        let mut rhs = MatrixD::<c64>::zeros_from_dim(4, 2);
+        rhs[[0, 0]] = c64::new(1.0, 0.0);
+        rhs[[3, 1]] = c64::new(0.0, 2.0);
+        let expected = q.dot(&rhs);
+
+        qr.apply_q(&mut rhs, TransposeMode::NoTrans).unwrap();
+        for row in 0..4 {
+            for col in 0..2 {
+                assert_close(rhs[[row, col]], expected[[row, col]]);
+            }
+        }
+
+        qr.apply_q(&mut rhs, TransposeMode::ConjugateTrans).unwrap();
+        assert_close(rhs[[3, 1]], c64::new(0.0, 2.0));
+        assert!(qr.apply_q(&mut rhs, TransposeMode::Trans).is_err());
+    }
+
+    #[test]
+    fn test_qr_least_squares() {
+        // Fit a line through points that lie exactly on y = 2 - 3x.
+        let mut mat = MatrixD::<f64>::zeros_from_dim(5, 2);
+        let mut rhs = MatrixD::<f64>::zeros_from_dim(5, 1);
+        for row in 0..5 {
+            let x = row as f64;
+            mat[[row, 0]] = 1.0;
+            mat[[row, 1]] = x;
+            rhs[[row, 0]] = 2.0 - 3.0 * x;
+        }
+
+        let qr = mat.lapack().unwrap().qr().unwrap();
+        let sol = qr.solve_least_squares(&rhs).unwrap();
+        assert_eq!(sol.layout().dim(), (2, 1));
+        assert_float_eq!(sol[[0, 0]], 2.0, abs <= 1E-12);
+        assert_float_eq!(sol[[1, 0]], -3.0, abs <= 1E-12);
+
+        // For inconsistent data the residual is orthogonal to the columns.
+        rhs[[2, 0]] += 1.0;
+        let sol = qr.solve_least_squares(&rhs).unwrap();
+        let mut residual = [0.0; 5];
+        for row in 0..5 {
+            residual[row] = rhs[[row, 0]] - sol[[0, 0]] - sol[[1, 0]] * row as f64;
+        }
+        let ones: f64 = residual.iter().sum();
+        let slope: f64 = residual
+            .iter()
+            .enumerate()
+            .map(|(row, r)| row as f64 * r)
+            .sum();
+        assert_float_eq!(ones, 0.0, abs <= 1E-12);
+        assert_float_eq!(slope, 0.0, abs <= 1E-12);
+    }
+
+    #[test]
+    fn test_qr_refactor() {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 2);
+        mat[[0, 0]] = 1.0;
+        mat[[1, 1]] = 2.0;
+        mat[[2, 0]] = 1.0;
+        let mut qr = MatrixD::<f64>::zeros_from_dim(3, 2)
+            .lapack()
+            .unwrap()
+            .qr()
+            .unwrap();
+
+        for step in 1..3 {
+            mat[[2, 1]] = step as f64;
+            qr.refactor(&mat).unwrap();
+
+            // Check A = Q R.
+            let product = qr.q(QRMode::Thin).unwrap().dot(&qr.r(QRMode::Thin));
+            for col in 0..2 {
+                for row in 0..3 {
+                    assert_float_eq!(product[[row, col]], mat[[row, col]], abs <= 1E-12);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Schur decomposition of dense matrices.
+//!
+//! The decomposition `A = Z T Z^H` is computed with `gees`. For real matrices `T` is in
+//! real Schur form, with `2 x 2` diagonal blocks for complex conjugate eigenvalue pairs.
+//! The eigenvalues on the diagonal of `T` are reordered with `trsen`.
+use crate::lapack::{check_square, LapackData};
+use crate::traits::schur_decomp::SchurDecomp;
+use lapacke;
+use num::Zero;
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, MatrixD, SizeIdentifier,
+};
+
+pub struct SchurDecompLapack<Item: Scalar> {
+    t: MatrixD<Item>,
+    z: MatrixD<Item>,
+    eigenvalues: Vec<<Item as Scalar>::Complex>,
+}
+
+impl<Item: Scalar> SchurDecompLapack<Item> {
+    /// Consume the decomposition and return the Schur form `T` and the Schur vectors `Z`.
+    pub fn into_factors(self) -> (MatrixD<Item>, MatrixD<Item>) {
+        (self.t, self.z)
+    }
+}
+
+fn check_select(select: &[bool], n: IndexType) -> RlstResult<Vec<i32>> {
+    if select.len() != n {
+        return Err(RlstError::SingleDimensionError {
+            expected: n,
+            actual: select.len(),
+        });
+    }
+    Ok(select.iter().map(|&value| value as i32).collect())
+}
+
+macro_rules! schur_common_impl {
+    () => {
+        fn dim(&self) -> (IndexType, IndexType) {
+            self.t.layout().dim()
+        }
+
+        fn t(&self) -> &MatrixD<Self::T> {
+            &self.t
+        }
+
+        fn z(&self) -> &MatrixD<Self::T> {
+            &self.z
+        }
+
+        fn eigenvalues(&self) -> &[<Self::T as Scalar>::Complex] {
+            &self.eigenvalues
+        }
+    };
+}
+
+macro_rules! real_schur_impl {
+    ($scalar:ty, $complex:ty, $lapack_gees:ident, $lapack_trsen:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            pub fn schur(mut self) -> RlstResult<SchurDecompLapack<$scalar>> {
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let ld = std::cmp::max(1, n);
+
+                let mut sdim = 0;
+                let mut wr = vec![<$scalar as Zero>::zero(); n];
+                let mut wi = vec![<$scalar as Zero>::zero(); n];
+                let mut z = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                let info = unsafe {
+                    lapacke::$lapack_gees(
+                        lapacke::Layout::ColumnMajor,
+                        b'V',
+                        b'N',
+                        None,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        &mut sdim,
+                        &mut wr,
+                        &mut wi,
+                        z.data_mut(),
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Schur decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let mut t = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                for col in 0..n {
+                    for row in 0..n {
+                        t[[row, col]] = self.mat[[row, col]];
+                    }
+                }
+
+                Ok(SchurDecompLapack {
+                    t,
+                    z,
+                    eigenvalues: wr
+                        .iter()
+                        .zip(wi.iter())
+                        .map(|(&re, &im)| <$complex>::new(re, im))
+                        .collect(),
+                })
+            }
+        }
+
+        impl SchurDecomp for SchurDecompLapack<$scalar> {
+            type T = $scalar;
+
+            schur_common_impl!();
+
+            fn reorder(&mut self, select: &[bool]) -> RlstResult<IndexType> {
+                let n = self.dim().0;
+                let select = check_select(select, n)?;
+                let ld = std::cmp::max(1, n) as i32;
+
+                let mut m = 0;
+                let mut s = [<$scalar as Zero>::zero()];
+                let mut sep = [<$scalar as Zero>::zero()];
+                let mut wr = vec![<$scalar as Zero>::zero(); n];
+                let mut wi = vec![<$scalar as Zero>::zero(); n];
+                let info = unsafe {
+                    lapacke::$lapack_trsen(
+                        lapacke::Layout::ColumnMajor,
+                        b'N',
+                        b'V',
+                        &select,
+                        n as i32,
+                        self.t.data_mut(),
+                        ld,
+                        self.z.data_mut(),
+                        ld,
+                        &mut wr,
+                        &mut wi,
+                        &mut m,
+                        &mut s,
+                        &mut sep,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Reordering of Schur form".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                self.eigenvalues = wr
+                    .iter()
+                    .zip(wi.iter())
+                    .map(|(&re, &im)| <$complex>::new(re, im))
+                    .collect();
+                Ok(m as IndexType)
+            }
+        }
+    };
+}
+
+macro_rules! complex_schur_impl {
+    ($scalar:ty, $lapack_gees:ident, $lapack_trsen:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            pub fn schur(mut self) -> RlstResult<SchurDecompLapack<$scalar>> {
+                let n = check_square(self.mat.layout().dim())?;
+                let lda = self.mat.layout().stride().1;
+                let ld = std::cmp::max(1, n);
+
+                let mut sdim = 0;
+                let mut eigenvalues = vec![<$scalar as Zero>::zero(); n];
+                let mut z = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                let info = unsafe {
+                    lapacke::$lapack_gees(
+                        lapacke::Layout::ColumnMajor,
+                        b'V',
+                        b'N',
+                        None,
+                        n as i32,
+                        self.mat.data_mut(),
+                        lda as i32,
+                        &mut sdim,
+                        &mut eigenvalues,
+                        z.data_mut(),
+                        ld as i32,
+                    )
+                };
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Schur decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let mut t = MatrixD::<$scalar>::zeros_from_dim(n, n);
+                for col in 0..n {
+                    for row in 0..n {
+                        t[[row, col]] = self.mat[[row, col]];
+                    }
+                }
+
+                Ok(SchurDecompLapack { t, z, eigenvalues })
+            }
+        }
+
+        impl SchurDecomp for SchurDecompLapack<$scalar> {
+            type T = $scalar;
+
+            schur_common_impl!();
+
+            fn reorder(&mut self, select: &[bool]) -> RlstResult<IndexType> {
+                let n = self.dim().0;
+                let select = check_select(select, n)?;
+                let ld = std::cmp::max(1, n) as i32;
+
+                let mut m = 0;
+                let mut s = [<<$scalar as Scalar>::Real as Zero>::zero()];
+                let mut sep = [<<$scalar as Scalar>::Real as Zero>::zero()];
+                let info = unsafe {
+                    lapacke::$lapack_trsen(
+                        lapacke::Layout::ColumnMajor,
+                        b'N',
+                        b'V',
+                        &select,
+                        n as i32,
+                        self.t.data_mut(),
+                        ld,
+                        self.z.data_mut(),
+                        ld,
+                        &mut self.eigenvalues,
+                        &mut m,
+                        &mut s,
+                        &mut sep,
+                    )
+                };
+
+                if info != 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+                Ok(m as IndexType)
+            }
+        }
+    };
+}
+
+real_schur_impl!(f64, c64, dgees, dtrsen);
+real_schur_impl!(f32, c32, sgees, strsen);
+complex_schur_impl!(c32, cgees, ctrsen);
+complex_schur_impl!(c64, zgees, ztrsen);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix};
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    // Check `A = Z T Z^T` for a real decomposition.
+    fn check_real_schur(mat: &MatrixD<f64>, schur: &SchurDecompLapack<f64>) {
+        let n = mat.layout().dim().0;
+        let zt = schur.z().dot(schur.t());
+        for col in 0..n {
+            for row in 0..n {
+                let value = (0..n).fold(0.0, |acc, k| acc + zt[[row, k]] * schur.z()[[col, k]]);
+                assert_float_eq!(value, mat[[row, col]], abs <= 1E-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_schur_reorder() {
+        // The matrix has eigenvalues 3 and 1 +- 2i.
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 3);
+        mat[[0, 0]] = 3.0;
+        mat[[0, 1]] = 1.0;
+        mat[[1, 1]] = 1.0;
+        mat[[1, 2]] = -2.0;
+        mat[[2, 1]] = 2.0;
+        mat[[2, 2]] = 1.0;
+
+        let mut schur = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .schur()
+            .unwrap();
+        check_real_schur(&mat, &schur);
+
+        // Move the complex pair to the front by selecting one of its eigenvalues.
+        let select: Vec<bool> = schur
+            .eigenvalues()
+            .iter()
+            .map(|value| value.im > 0.0)
+            .collect();
+        assert_eq!(schur.reorder(&select).unwrap(), 2);
+        check_real_schur(&mat, &schur);
+        assert!(schur.eigenvalues()[0].im.abs() > 1.0);
+        assert_close(schur.eigenvalues()[2], c64::new(3.0, 0.0), 1E-12);
+        assert_float_eq!(schur.t()[[2, 1]], 0.0, abs <= 1E-12);
+    }
+
+    #[test]
+    fn test_complex_schur_reorder() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+        mat[[0, 0]] = c64::new(1.0, 0.0);
+        mat[[0, 1]] = c64::new(2.0, 1.0);
+        mat[[1, 0]] = c64::new(0.0, 0.0);
+        mat[[1, 1]] = c64::new(0.0, 3.0);
+
+        let mut schur = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .schur()
+            .unwrap();
+
+        let select: Vec<bool> = schur
+            .eigenvalues()
+            .iter()
+            .map(|value| value.im > 1.0)
+            .collect();
+        assert_eq!(schur.reorder(&select).unwrap(), 1);
+        assert_close(schur.eigenvalues()[0], c64::new(0.0, 3.0), 1E-12);
+        assert_close(schur.t()[[0, 0]], c64::new(0.0, 3.0), 1E-12);
+
+        // Check A = Z T Z^H.
+        let zt = schur.z().dot(schur.t());
+        for col in 0..2 {
+            for row in 0..2 {
+                let value = (0..2).fold(c64::new(0.0, 0.0), |acc, k| {
+                    acc + zt[[row, k]] * schur.z()[[col, k]].conj()
+                });
+                assert_close(value, mat[[row, col]], 1E-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Singular value decomposition of dense matrices.
+//!
+//! The decomposition `A = U S V^H` is computed with the divide and conquer driver
+//! `gesdd`. In the rare case that `gesdd` fails to converge the computation is repeated
+//! with the QR iteration driver `gesvd`.
+use crate::lapack::LapackData;
+use crate::traits::svd::{SVDMode, SVD};
+use lapacke;
+use num::{Float, Zero};
+use rlst_common::types::{c32, c64, IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainerMut, GenericBaseMatrixMut, Layout, LayoutType, LowRankMatrix, MatrixD,
+    SizeIdentifier,
+};
+
+pub struct SVDLapack<Item: Scalar> {
+    dim: (IndexType, IndexType),
+    mode: SVDMode,
+    singular_values: Vec<<Item as Scalar>::Real>,
+    u: Option<MatrixD<Item>>,
+    vt: Option<MatrixD<Item>>,
+}
+
+impl<Item: Scalar> SVDLapack<Item> {
+    /// Return the singular vectors or an error if only singular values were computed.
+    fn vectors(&self) -> RlstResult<(&MatrixD<Item>, &MatrixD<Item>)> {
+        match (&self.u, &self.vt) {
+            (Some(u), Some(vt)) => Ok((u, vt)),
+            _ => Err(RlstError::GeneralError(
+                "Singular vectors were not computed.".to_string(),
+            )),
+        }
+    }
+}
+
+macro_rules! svd_impl {
+    ($scalar:ty, $lapack_gesdd:ident, $lapack_gesvd:ident) => {
+        impl<RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainerMut<Item = $scalar>>
+            LapackData<$scalar, RS, CS, GenericBaseMatrixMut<$scalar, Data, RS, CS>>
+        {
+            pub fn svd(mut self, mode: SVDMode) -> RlstResult<SVDLapack<$scalar>> {
+                let dim = self.mat.layout().dim();
+                let stride = self.mat.layout().stride();
+
+                let (m, n) = dim;
+                let k = std::cmp::min(m, n);
+                let (job, u_cols, vt_rows) = match mode {
+                    SVDMode::Values => (b'N', 0, 0),
+                    SVDMode::Thin => (b'S', k, k),
+                    SVDMode::Full => (b'A', m, n),
+                };
+
+                let mut singular_values = vec![<<$scalar as Scalar>::Real as Zero>::zero(); k];
+                let mut u = MatrixD::<$scalar>::zeros_from_dim(m, u_cols);
+                let mut vt = MatrixD::<$scalar>::zeros_from_dim(vt_rows, n);
+                let ldu = std::cmp::max(1, m) as i32;
+                let ldvt = std::cmp::max(1, vt_rows) as i32;
+
+                // `gesdd` destroys its input, so it works on a copy to keep `A` available
+                // for the fallback.
+                let mut work = MatrixD::<$scalar>::zeros_from_dim(m, n);
+                for col in 0..n {
+                    for row in 0..m {
+                        work[[row, col]] = self.mat[[row, col]];
+                    }
+                }
+
+                let mut info = unsafe {
+                    lapacke::$lapack_gesdd(
+                        lapacke::Layout::ColumnMajor,
+                        job,
+                        m as i32,
+                        n as i32,
+                        work.data_mut(),
+                        std::cmp::max(1, m) as i32,
+                        &mut singular_values,
+                        u.data_mut(),
+                        ldu,
+                        vt.data_mut(),
+                        ldvt,
+                    )
+                };
+
+                if info > 0 {
+                    let mut superb =
+                        vec![<<$scalar as Scalar>::Real as Zero>::zero(); std::cmp::max(1, k)];
+                    info = unsafe {
+                        lapacke::$lapack_gesvd(
+                            lapacke::Layout::ColumnMajor,
+                            job,
+                            job,
+                            m as i32,
+                            n as i32,
+                            self.mat.data_mut(),
+                            stride.1 as i32,
+                            &mut singular_values,
+                            u.data_mut(),
+                            ldu,
+                            vt.data_mut(),
+                            ldvt,
+                            &mut superb,
+                        )
+                    };
+                }
+
+                if info > 0 {
+                    return Err(RlstError::OperationFailed(
+                        "Singular value decomposition".to_string(),
+                    ));
+                } else if info < 0 {
+                    return Err(RlstError::LapackError(info));
+                }
+
+                let (u, vt) = match mode {
+                    SVDMode::Values => (None, None),
+                    _ => (Some(u), Some(vt)),
+                };
+
+                Ok(SVDLapack {
+                    dim,
+                    mode,
+                    singular_values,
+                    u,
+                    vt,
+                })
+            }
+        }
+
+        impl SVD for SVDLapack<$scalar> {
+            type T = $scalar;
+
+            fn dim(&self) -> (IndexType, IndexType) {
+                self.dim
+            }
+
+            fn mode(&self) -> SVDMode {
+                self.mode
+            }
+
+            fn singular_values(&self) -> &[<Self::T as Scalar>::Real] {
+                &self.singular_values
+            }
+
+            fn u(&self) -> Option<&MatrixD<Self::T>> {
+                self.u.as_ref()
+            }
+
+            fn vt(&self) -> Option<&MatrixD<Self::T>> {
+                self.vt.as_ref()
+            }
+
+            fn norm_2(&self) -> <Self::T as Scalar>::Real {
+                self.singular_values
+                    .first()
+                    .copied()
+                    .unwrap_or(<<Self::T as Scalar>::Real as Zero>::zero())
+            }
+
+            fn condition_number(&self) -> <Self::T as Scalar>::Real {
+                match self.singular_values.last() {
+                    Some(&smallest) if smallest > <<Self::T as Scalar>::Real as Zero>::zero() => {
+                        self.norm_2() / smallest
+                    }
+                    _ => <<Self::T as Scalar>::Real as Float>::infinity(),
+                }
+            }
+
+            fn rank(&self, tol: <Self::T as Scalar>::Real) -> IndexType {
+                let threshold = tol * self.norm_2();
+                self.singular_values
+                    .iter()
+                    .take_while(|&&value| value > threshold)
+                    .count()
+            }
+
+            fn pseudo_inverse(
+                &self,
+                tol: <Self::T as Scalar>::Real,
+            ) -> RlstResult<MatrixD<Self::T>> {
+                let (u, vt) = self.vectors()?;
+                let (m, n) = self.dim;
+                let rank = self.rank(tol);
+
+                let mut pinv = MatrixD::<$scalar>::zeros_from_dim(n, m);
+                for index in 0..rank {
+                    let scale = <$scalar as Scalar>::from_real(
+                        <<Self::T as Scalar>::Real as num::One>::one()
+                            / self.singular_values[index],
+                    );
+                    for col in 0..m {
+                        let factor = scale * u[[col, index]].conj();
+                        for row in 0..n {
+                            pinv[[row, col]] += vt[[index, row]].conj() * factor;
+                        }
+                    }
+                }
+                Ok(pinv)
+            }
+
+            fn low_rank_approximation(&self, k: IndexType) -> RlstResult<LowRankMatrix<Self::T>> {
+                let (u, vt) = self.vectors()?;
+                let (m, n) = self.dim;
+                if k > self.singular_values.len() {
+                    return Err(RlstError::GeneralError(format!(
+                        "Rank {} of approximation exceeds the dimensions {} x {}.",
+                        k, m, n
+                    )));
+                }
+
+                let mut left = MatrixD::<$scalar>::zeros_from_dim(m, k);
+                let mut right = MatrixD::<$scalar>::zeros_from_dim(n, k);
+                for index in 0..k {
+                    let scale = <$scalar as Scalar>::from_real(self.singular_values[index]);
+                    for row in 0..m {
+                        left[[row, index]] = u[[row, index]] * scale;
+                    }
+                    for row in 0..n {
+                        right[[row, index]] = vt[[index, row]].conj();
+                    }
+                }
+                Ok(LowRankMatrix::from_factors(left, right))
+            }
+        }
+    };
+}
+
+svd_impl!(f64, dgesdd, dgesvd);
+svd_impl!(f32, sgesdd, sgesvd);
+svd_impl!(c32, cgesdd, cgesvd);
+svd_impl!(c64, zgesdd, zgesvd);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lapack::AsLapack;
+    use float_eq::assert_float_eq;
+    use rlst_dense::{Dot, Matrix, RandomAccessByValue};
+
+    fn assert_close(actual: c64, expected: c64, tol: f64) {
+        assert!(
+            (actual - expected).norm() <= tol,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    // A 3 x 2 matrix with singular values 3 and 1.
+    fn test_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 2);
+        mat[[0, 0]] = 2.0;
+        mat[[0, 1]] = 1.0;
+        mat[[1, 0]] = 1.0;
+        mat[[1, 1]] = 2.0;
+        mat
+    }
+
+    #[test]
+    fn test_svd_f64() {
+        let mat = test_matrix();
+        let svd = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .svd(SVDMode::Thin)
+            .unwrap();
+
+        assert_float_eq!(svd.singular_values()[0], 3.0, abs <= 1E-12);
+        assert_float_eq!(svd.singular_values()[1], 1.0, abs <= 1E-12);
+        assert_float_eq!(svd.norm_2(), 3.0, abs <= 1E-12);
+        assert_float_eq!(svd.condition_number(), 3.0, abs <= 1E-12);
+        assert_eq!(svd.rank(1E-10), 2);
+
+        let u = svd.u().unwrap();
+        let vt = svd.vt().unwrap();
+        assert_eq!(u.layout().dim(), (3, 2));
+        assert_eq!(vt.layout().dim(), (2, 2));
+
+        // Check A = U S V^H.
+        for col in 0..2 {
+            for row in 0..3 {
+                let value = (0..2).fold(0.0, |acc, index| {
+                    acc + u[[row, index]] * svd.singular_values()[index] * vt[[index, col]]
+                });
+                assert_float_eq!(value, mat[[row, col]], abs <= 1E-12);
+            }
+        }
+
+        // Check A A^+ A = A.
+        let pinv = svd.pseudo_inverse(1E-10).unwrap();
+        assert_eq!(pinv.layout().dim(), (2, 3));
+        let product = mat.dot(&pinv).dot(&mat);
+        for col in 0..2 {
+            for row in 0..3 {
+                assert_float_eq!(product[[row, col]], mat[[row, col]], abs <= 1E-12);
+            }
+        }
+
+        // The best rank-1 approximation has error equal to the second singular value.
+        let approx = svd.low_rank_approximation(1).unwrap();
+        assert_eq!(approx.rank(), 1);
+        for col in 0..2 {
+            for row in 0..2 {
+                assert_float_eq!(approx.get_value(row, col), 1.5, abs <= 1E-12);
+            }
+        }
+        assert!(svd.low_rank_approximation(3).is_err());
+    }
+
+    #[test]
+    fn test_svd_values_only() {
+        let mut mat = test_matrix();
+        // Make the matrix rank deficient.
+        mat[[1, 1]] = 0.5;
+
+        let svd = mat.lapack().unwrap().svd(SVDMode::Values).unwrap();
+        assert!(svd.u().is_none());
+        assert!(svd.vt().is_none());
+        assert_eq!(svd.rank(1E-10), 1);
+        assert!(svd.condition_number() > 1E10);
+        assert!(svd.pseudo_inverse(1E-10).is_err());
+    }
+
+    #[test]
+    fn test_svd_full_c64() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 3);
+        mat[[0, 0]] = c64::new(1.0, 1.0);
+        mat[[0, 2]] = c64::new(0.0, -2.0);
+        mat[[1, 1]] = c64::new(3.0, 0.0);
+
+        let svd = Matrix::from_ref(&mat)
+            .eval()
+            .lapack()
+            .unwrap()
+            .svd(SVDMode::Full)
+            .unwrap();
+
+        assert_float_eq!(svd.singular_values()[0], 3.0, abs <= 1E-12);
+        assert_float_eq!(svd.singular_values()[1], 6.0_f64.sqrt(), abs <= 1E-12);
+
+        // V^H is a unitary 3 x 3 matrix.
+        let vt = svd.vt().unwrap();
+        assert_eq!(vt.layout().dim(), (3, 3));
+        for col in 0..3 {
+            for row in 0..3 {
+                let value = (0..3).fold(c64::new(0.0, 0.0), |acc, index| {
+                    acc + vt[[row, index]] * vt[[col, index]].conj()
+                });
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert_close(value, c64::new(expected, 0.0), 1E-12);
+            }
+        }
+    }
+}
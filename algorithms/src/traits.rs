@@ -1,2 +1,11 @@
 pub mod arnoldi;
+pub mod cholesky_decomp;
+pub mod eigenvalue_decomp;
+pub mod ldl_decomp;
 pub mod lu_decomp;
+pub mod matrix_equations;
+pub mod matrix_functions;
+pub mod pivoted_qr;
+pub mod qr_decomp;
+pub mod schur_decomp;
+pub mod svd;
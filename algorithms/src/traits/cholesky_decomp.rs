@@ -0,0 +1,31 @@
+//! Trait for Cholesky Decomposition
+use crate::lapack::TriangularType;
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{DataContainerMut, GenericBaseMatrixMut, MatrixD, SizeIdentifier};
+
+pub trait CholeskyDecomp {
+    type T: Scalar;
+
+    fn data(&self) -> &[Self::T];
+
+    fn dim(&self) -> (IndexType, IndexType);
+
+    /// Return the triangle in which the factor is stored.
+    fn triangular_type(&self) -> TriangularType;
+
+    /// Return the triangular factor, which is `U` with `A = U^H U` for
+    /// [TriangularType::Upper] and `L` with `A = L L^H` for [TriangularType::Lower].
+    fn factor(&self) -> MatrixD<Self::T>;
+
+    /// Overwrite `rhs` with the solution `x` of `A x = rhs`.
+    fn solve<Data: DataContainerMut<Item = Self::T>, RhsR: SizeIdentifier, RhsC: SizeIdentifier>(
+        &self,
+        rhs: &mut GenericBaseMatrixMut<Self::T, Data, RhsR, RhsC>,
+    ) -> RlstResult<()>;
+
+    /// Return the inverse of `A`.
+    fn inverse(&self) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return the natural logarithm of the determinant of `A`, which is positive.
+    fn log_det(&self) -> <Self::T as Scalar>::Real;
+}
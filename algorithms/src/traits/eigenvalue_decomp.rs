@@ -0,0 +1,94 @@
+//! Types for eigenvalue decompositions
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{ColumnVectorD, MatrixD};
+
+/// The eigenvectors computed alongside the eigenvalues.
+///
+/// For Hermitian matrices the left and right eigenvectors coincide, so that
+/// [EigenvectorMode::Right] and [EigenvectorMode::LeftAndRight] are equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EigenvectorMode {
+    /// Only the eigenvalues are computed.
+    None,
+    /// The right eigenvectors `A v = lambda v` are computed.
+    Right,
+    /// The left eigenvectors `u^H A = lambda u^H` and the right eigenvectors are computed.
+    LeftAndRight,
+}
+
+/// The eigenvalues of a Hermitian matrix that are computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EigenvalueRange<Real> {
+    /// All eigenvalues.
+    All,
+    /// The eigenvalues with indices `start..end` in ascending order.
+    Index(IndexType, IndexType),
+    /// The eigenvalues in the half-open interval `(lower, upper]`.
+    Value(Real, Real),
+}
+
+/// The eigendecomposition `A = V diag(lambda) V^H` of a Hermitian matrix, or of a
+/// Hermitian-definite pencil `A v = lambda B v`.
+pub struct HermitianEigenvalueDecomposition<T: Scalar> {
+    /// The real eigenvalues in ascending order.
+    pub eigenvalues: ColumnVectorD<<T as Scalar>::Real>,
+    /// The eigenvectors as columns, if they were requested. They are orthonormal for
+    /// a standard problem and satisfy `V^H B V = I` for a generalized problem.
+    pub eigenvectors: Option<MatrixD<T>>,
+}
+
+/// The eigenvalues and eigenvectors of a general square matrix. The results are complex
+/// even for real matrices.
+pub struct EigenvalueDecomposition<T: Scalar> {
+    /// The eigenvalues in the order computed by Lapack.
+    pub eigenvalues: ColumnVectorD<<T as Scalar>::Complex>,
+    /// The left eigenvectors as columns normalised to unit 2-norm, if they were requested.
+    pub left_eigenvectors: Option<MatrixD<<T as Scalar>::Complex>>,
+    /// The right eigenvectors as columns normalised to unit 2-norm, if they were requested.
+    pub right_eigenvectors: Option<MatrixD<<T as Scalar>::Complex>>,
+}
+
+/// The generalized eigenvalues `lambda = alpha / beta` and eigenvectors `A v = lambda B v`
+/// of a general matrix pencil `(A, B)`. The results are complex even for real matrices.
+pub struct GeneralizedEigenvalueDecomposition<T: Scalar> {
+    /// The numerators of the eigenvalues.
+    pub alpha: ColumnVectorD<<T as Scalar>::Complex>,
+    /// The denominators of the eigenvalues. A zero entry denotes an infinite eigenvalue.
+    pub beta: ColumnVectorD<T>,
+    /// The left eigenvectors `u^H A = lambda u^H B` as columns, if they were requested.
+    pub left_eigenvectors: Option<MatrixD<<T as Scalar>::Complex>>,
+    /// The right eigenvectors as columns, if they were requested.
+    pub right_eigenvectors: Option<MatrixD<<T as Scalar>::Complex>>,
+}
+
+impl<T: Scalar> GeneralizedEigenvalueDecomposition<T> {
+    /// Return the eigenvalues `alpha / beta`, with `None` for infinite eigenvalues.
+    ///
+    /// An eigenvalue is only infinite if `beta` is exactly zero. Lapack usually returns a
+    /// tiny nonzero `beta` for an eigenvalue that is infinite in exact arithmetic, which
+    /// results in a huge finite eigenvalue. Use
+    /// [eigenvalues_with_tolerance](Self::eigenvalues_with_tolerance) to detect these.
+    pub fn eigenvalues(&self) -> Vec<Option<<T as Scalar>::Complex>> {
+        self.eigenvalues_with_tolerance(<<T as Scalar>::Real as num::Zero>::zero())
+    }
+
+    /// Return the eigenvalues `alpha / beta`, with `None` for the eigenvalues that satisfy
+    /// `|beta| <= tol * |alpha|`.
+    pub fn eigenvalues_with_tolerance(
+        &self,
+        tol: <T as Scalar>::Real,
+    ) -> Vec<Option<<T as Scalar>::Complex>> {
+        self.alpha
+            .data()
+            .iter()
+            .zip(self.beta.data().iter())
+            .map(|(&alpha, &beta)| {
+                if beta.abs() <= tol * alpha.abs() {
+                    None
+                } else {
+                    Some(alpha / beta.as_c())
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,28 @@
+//! Trait for LDL^H Decomposition
+use crate::lapack::TriangularType;
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{DataContainerMut, GenericBaseMatrixMut, MatrixD, SizeIdentifier};
+
+pub trait LDLDecomp {
+    type T: Scalar;
+
+    fn data(&self) -> &[Self::T];
+
+    fn dim(&self) -> (IndexType, IndexType);
+
+    /// Return the triangle in which the factor is stored.
+    fn triangular_type(&self) -> TriangularType;
+
+    /// Overwrite `rhs` with the solution `x` of `A x = rhs`.
+    fn solve<Data: DataContainerMut<Item = Self::T>, RhsR: SizeIdentifier, RhsC: SizeIdentifier>(
+        &self,
+        rhs: &mut GenericBaseMatrixMut<Self::T, Data, RhsR, RhsC>,
+    ) -> RlstResult<()>;
+
+    /// Return the inverse of `A`.
+    fn inverse(&self) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return the sign and the natural logarithm of the absolute value of the determinant
+    /// of `A`, which is real.
+    fn log_det(&self) -> (<Self::T as Scalar>::Real, <Self::T as Scalar>::Real);
+}
@@ -1,8 +1,25 @@
 //! Trait for LU Decomposition
-use crate::lapack::TransposeMode;
+use crate::lapack::{NormType, TransposeMode};
 pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
-use rlst_dense::{DataContainerMut, GenericBaseMatrixMut, SizeIdentifier};
+use rlst_dense::{
+    DataContainer, DataContainerMut, GenericBaseMatrix, GenericBaseMatrixMut, MatrixD,
+    SizeIdentifier,
+};
 
+/// Error bounds for each column of the solution of a linear system.
+pub struct ErrorBounds<Real> {
+    /// Estimated bounds on the relative forward error in the maximum norm.
+    pub forward: Vec<Real>,
+    /// The componentwise relative backward errors.
+    pub backward: Vec<Real>,
+}
+
+/// The LU decomposition `P A = L U` of a matrix.
+///
+/// The decomposition of a singular matrix is computed without an error, so that its
+/// determinant can be evaluated. The factor `U` then has an exactly zero diagonal entry,
+/// the determinant is zero, and [solve](LUDecomp::solve) and [inverse](LUDecomp::inverse)
+/// return [RlstError::SingularMatrix].
 pub trait LUDecomp {
     type T: Scalar;
 
@@ -10,9 +27,56 @@ pub trait LUDecomp {
 
     fn dim(&self) -> (IndexType, IndexType);
 
+    /// Solve `op(A) X = rhs` in place for a square matrix.
+    ///
+    /// Returns [RlstError::SingularMatrix] if the matrix is singular.
     fn solve<Data: DataContainerMut<Item = Self::T>, RhsR: SizeIdentifier, RhsC: SizeIdentifier>(
         &self,
         rhs: &mut GenericBaseMatrixMut<Self::T, Data, RhsR, RhsC>,
         trans: TransposeMode,
     ) -> RlstResult<()>;
+
+    /// Return the `m x k` unit lower triangular factor `L` of `P A = L U` with `k = min(m, n)`.
+    fn l(&self) -> MatrixD<Self::T>;
+
+    /// Return the `k x n` upper triangular factor `U` of `P A = L U` with `k = min(m, n)`.
+    fn u(&self) -> MatrixD<Self::T>;
+
+    /// Return the `m x m` permutation matrix `P` of `P A = L U`.
+    fn p(&self) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return the determinant of a square matrix.
+    fn det(&self) -> RlstResult<Self::T>;
+
+    /// Return the sign and the natural logarithm of the absolute value of the determinant
+    /// of a square matrix, such that `det = sign * exp(log_abs)`. The sign has modulus
+    /// one, or is zero for a singular matrix.
+    fn log_det(&self) -> RlstResult<(Self::T, <Self::T as Scalar>::Real)>;
+
+    /// Return the inverse of a square matrix.
+    ///
+    /// Returns [RlstError::SingularMatrix] if the matrix is singular.
+    fn inverse(&self) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return an estimate of the reciprocal condition number of a square matrix in the
+    /// given norm.
+    fn rcond(&self, norm: NormType) -> RlstResult<<Self::T as Scalar>::Real>;
+
+    /// Improve the solution `x` of `op(A) x = rhs` by iterative refinement and return
+    /// error bounds for each column. The matrix `a` must be the matrix that was factorised.
+    fn refine<
+        AData: DataContainer<Item = Self::T>,
+        AR: SizeIdentifier,
+        AC: SizeIdentifier,
+        RhsData: DataContainer<Item = Self::T>,
+        XData: DataContainerMut<Item = Self::T>,
+        RhsR: SizeIdentifier,
+        RhsC: SizeIdentifier,
+    >(
+        &self,
+        a: &GenericBaseMatrix<Self::T, AData, AR, AC>,
+        rhs: &GenericBaseMatrix<Self::T, RhsData, RhsR, RhsC>,
+        x: &mut GenericBaseMatrixMut<Self::T, XData, RhsR, RhsC>,
+        trans: TransposeMode,
+    ) -> RlstResult<ErrorBounds<<Self::T as Scalar>::Real>>;
 }
@@ -0,0 +1,24 @@
+//! Trait for Sylvester and Lyapunov equations
+pub use rlst_common::types::{RlstError, RlstResult, Scalar};
+use rlst_dense::MatrixD;
+
+pub trait MatrixEquations {
+    type T: Scalar;
+
+    /// Solve the Sylvester equation `A X + X B = C` for `X`, where `A` is this matrix.
+    ///
+    /// Returns [RlstError::SingularMatrix] if `A` and `-B` have common or nearly common
+    /// eigenvalues, in which case the solution is not unique or badly conditioned.
+    fn solve_sylvester(
+        &self,
+        b: &MatrixD<Self::T>,
+        c: &MatrixD<Self::T>,
+    ) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Solve the continuous Lyapunov equation `A X + X A^H = Q` for `X`, where `A` is
+    /// this matrix.
+    ///
+    /// Returns [RlstError::SingularMatrix] if `A` and `-A^H` have common or nearly common
+    /// eigenvalues.
+    fn solve_lyapunov(&self, q: &MatrixD<Self::T>) -> RlstResult<MatrixD<Self::T>>;
+}
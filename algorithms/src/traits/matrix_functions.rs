@@ -0,0 +1,45 @@
+//! Trait for matrix functions
+pub use rlst_common::types::{RlstResult, Scalar};
+use rlst_dense::MatrixD;
+
+/// The value of a matrix function together with an estimate of its relative error.
+pub struct MatrixFunctionResult<T: Scalar> {
+    /// The value of the matrix function.
+    pub value: MatrixD<T>,
+    /// An estimate of the relative error of `value` in the Frobenius norm.
+    pub error_estimate: <T as Scalar>::Real,
+}
+
+pub trait MatrixFunctions {
+    type T: Scalar;
+
+    /// Compute the matrix exponential by scaling and squaring with Padé approximants.
+    ///
+    /// The error estimate is the leading term of the Padé remainder, amplified by the
+    /// squaring phase, plus a rounding term. An error is returned if the 1-norm of the
+    /// matrix is not finite.
+    fn expm(&self) -> RlstResult<MatrixFunctionResult<Self::T>>;
+
+    /// Compute the principal matrix logarithm via a Schur decomposition and
+    /// inverse scaling and squaring.
+    ///
+    /// The error estimate is the relative residual `|expm(X) - A| / |A|`. For real matrices
+    /// an error is returned if the principal logarithm is not real.
+    fn logm(&self) -> RlstResult<MatrixFunctionResult<Self::T>>;
+
+    /// Compute the principal matrix square root via a Schur decomposition.
+    ///
+    /// The error estimate is the relative residual `|X * X - A| / |A|`. For real matrices
+    /// an error is returned if the principal square root is not real.
+    fn sqrtm(&self) -> RlstResult<MatrixFunctionResult<Self::T>>;
+
+    /// Compute `f(A)` for a Hermitian matrix `A` via its eigendecomposition
+    /// `A = V diag(lambda) V^H` as `V diag(f(lambda)) V^H`.
+    ///
+    /// The error estimate is the relative residual `|A V - V diag(lambda)| / |A|` of the
+    /// eigendecomposition. An error is returned if the matrix is not Hermitian.
+    fn funm<F: Fn(<Self::T as Scalar>::Real) -> <Self::T as Scalar>::Real>(
+        &self,
+        f: F,
+    ) -> RlstResult<MatrixFunctionResult<Self::T>>;
+}
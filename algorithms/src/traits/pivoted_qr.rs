@@ -0,0 +1,38 @@
+//! Trait for column-pivoted QR Decomposition
+use crate::traits::qr_decomp::QRDecomp;
+use rlst_common::permutation::Permutation;
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::MatrixD;
+
+/// A rank-`k` interpolative decomposition `A ~ C X` of an `m x n` matrix `A`.
+pub struct InterpolativeDecomposition<T: Scalar> {
+    /// The indices of the `k` skeleton columns of `A`.
+    pub skeleton_indices: Vec<IndexType>,
+    /// The `m x k` matrix `C` of skeleton columns of `A`.
+    pub skeleton: MatrixD<T>,
+    /// The `k x n` interpolation matrix `X`. The columns of `X` belonging to the
+    /// skeleton columns form the identity.
+    pub interpolation: MatrixD<T>,
+}
+
+pub trait PivotedQRDecomp {
+    type T: Scalar;
+    type QR: QRDecomp<T = Self::T>;
+
+    /// Return the QR decomposition `A P = Q R` of the column-permuted matrix.
+    fn qr(&self) -> &Self::QR;
+
+    /// Return the column permutation `P`. Column `i` of `A P` is column
+    /// `permutation.get(i)` of `A`.
+    fn permutation(&self) -> RlstResult<Permutation>;
+
+    /// Return the numerical rank, which is the number of diagonal entries of `R` with
+    /// `|r_ii| > tol * |r_00|`.
+    fn rank(&self, tol: <Self::T as Scalar>::Real) -> IndexType;
+
+    /// Return a rank-`k` interpolative decomposition built from the first `k` pivot columns.
+    fn interpolative_decomposition(
+        &self,
+        k: IndexType,
+    ) -> RlstResult<InterpolativeDecomposition<Self::T>>;
+}
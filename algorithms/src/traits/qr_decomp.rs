@@ -0,0 +1,52 @@
+//! Trait for QR Decomposition
+use crate::lapack::TransposeMode;
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{
+    DataContainer, DataContainerMut, GenericBaseMatrix, GenericBaseMatrixMut, MatrixD,
+    SizeIdentifier,
+};
+
+/// The shape of the factors of a QR decomposition of an `m x n` matrix with `k = min(m, n)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QRMode {
+    /// `Q` is `m x k` with orthonormal columns and `R` is `k x n`.
+    Thin,
+    /// `Q` is a unitary `m x m` matrix and `R` is `m x n`.
+    Full,
+}
+
+pub trait QRDecomp {
+    type T: Scalar;
+
+    fn data(&self) -> &[Self::T];
+
+    fn dim(&self) -> (IndexType, IndexType);
+
+    /// Overwrite `rhs` with `Q * rhs`, or with `Q^T * rhs` or `Q^H * rhs` depending on `trans`.
+    ///
+    /// For real types `Q^T` and `Q^H` coincide. For complex types only
+    /// [TransposeMode::NoTrans] and [TransposeMode::ConjugateTrans] are supported.
+    fn apply_q<Data: DataContainerMut<Item = Self::T>, RhsR: SizeIdentifier, RhsC: SizeIdentifier>(
+        &self,
+        rhs: &mut GenericBaseMatrixMut<Self::T, Data, RhsR, RhsC>,
+        trans: TransposeMode,
+    ) -> RlstResult<()>;
+
+    /// Return the factor `Q`.
+    fn q(&self, mode: QRMode) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return the upper triangular factor `R`.
+    fn r(&self, mode: QRMode) -> MatrixD<Self::T>;
+
+    /// Return the solution `x` of the least-squares problem `min |A x - rhs|_2`.
+    ///
+    /// The matrix `A` must have at least as many rows as columns and full column rank.
+    fn solve_least_squares<
+        Data: DataContainer<Item = Self::T>,
+        RhsR: SizeIdentifier,
+        RhsC: SizeIdentifier,
+    >(
+        &self,
+        rhs: &GenericBaseMatrix<Self::T, Data, RhsR, RhsC>,
+    ) -> RlstResult<MatrixD<Self::T>>;
+}
@@ -0,0 +1,28 @@
+//! Trait for Schur Decomposition
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::MatrixD;
+
+pub trait SchurDecomp {
+    type T: Scalar;
+
+    fn dim(&self) -> (IndexType, IndexType);
+
+    /// Return the Schur form `T` of `A = Z T Z^H`. It is upper triangular for complex
+    /// types and quasi-upper triangular with `2 x 2` blocks for complex conjugate
+    /// eigenvalue pairs for real types.
+    fn t(&self) -> &MatrixD<Self::T>;
+
+    /// Return the unitary matrix `Z` of Schur vectors.
+    fn z(&self) -> &MatrixD<Self::T>;
+
+    /// Return the eigenvalues in the order in which they appear on the diagonal of `T`.
+    fn eigenvalues(&self) -> &[<Self::T as Scalar>::Complex];
+
+    /// Reorder the decomposition so that the eigenvalues with `select[i] == true` appear
+    /// in the leading block of `T`, and return the dimension of that block. The leading
+    /// columns of `Z` then span the corresponding invariant subspace.
+    ///
+    /// For real types a complex conjugate pair is moved if either of its eigenvalues is
+    /// selected.
+    fn reorder(&mut self, select: &[bool]) -> RlstResult<IndexType>;
+}
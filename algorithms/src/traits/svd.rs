@@ -0,0 +1,52 @@
+//! Trait for Singular Value Decomposition
+pub use rlst_common::types::{IndexType, RlstError, RlstResult, Scalar};
+use rlst_dense::{LowRankMatrix, MatrixD};
+
+/// The factors computed by a singular value decomposition `A = U S V^H` of an `m x n`
+/// matrix with `k = min(m, n)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SVDMode {
+    /// Only the singular values are computed.
+    Values,
+    /// `U` is `m x k` and `V^H` is `k x n`.
+    Thin,
+    /// `U` is a unitary `m x m` matrix and `V^H` is a unitary `n x n` matrix.
+    Full,
+}
+
+pub trait SVD {
+    type T: Scalar;
+
+    fn dim(&self) -> (IndexType, IndexType);
+
+    /// Return the mode with which the decomposition was computed.
+    fn mode(&self) -> SVDMode;
+
+    /// Return the `min(m, n)` singular values in non-increasing order.
+    fn singular_values(&self) -> &[<Self::T as Scalar>::Real];
+
+    /// Return the left singular vectors `U`, or `None` for [SVDMode::Values].
+    fn u(&self) -> Option<&MatrixD<Self::T>>;
+
+    /// Return the conjugate transpose `V^H` of the right singular vectors, or `None`
+    /// for [SVDMode::Values].
+    fn vt(&self) -> Option<&MatrixD<Self::T>>;
+
+    /// Return the spectral norm, which is the largest singular value.
+    fn norm_2(&self) -> <Self::T as Scalar>::Real;
+
+    /// Return the 2-norm condition number `s_max / s_min`, which is infinite for a
+    /// rank-deficient matrix.
+    fn condition_number(&self) -> <Self::T as Scalar>::Real;
+
+    /// Return the numerical rank, which is the number of singular values with
+    /// `s_i > tol * s_max`.
+    fn rank(&self, tol: <Self::T as Scalar>::Real) -> IndexType;
+
+    /// Return the `n x m` Moore-Penrose pseudo-inverse `V S^+ U^H`, treating singular
+    /// values with `s_i <= tol * s_max` as zero.
+    fn pseudo_inverse(&self, tol: <Self::T as Scalar>::Real) -> RlstResult<MatrixD<Self::T>>;
+
+    /// Return the best rank-`k` approximation `U_k S_k V_k^H` in factored form.
+    fn low_rank_approximation(&self, k: IndexType) -> RlstResult<LowRankMatrix<Self::T>>;
+}
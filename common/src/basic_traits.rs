@@ -48,7 +48,7 @@ pub trait MultSomeInto {
     type T: Scalar;
 
     // self -> ax + self;
-    fn mult_sum_into(&self, alpha: Self::T, x: &Self);
+    fn mult_sum_into(&mut self, alpha: Self::T, x: &Self);
 }
 
 pub trait Duplicate {
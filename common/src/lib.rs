@@ -1,4 +1,16 @@
 //! Common RLST data structures
+//!
+//! With the default `std` feature disabled the crate is `no_std` and only requires `alloc`.
+//! [RlstError](types::RlstError) then does not implement `std::error::Error`, and the
+//! [rendering] module is not available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod basic_traits;
+pub mod permutation;
+#[cfg(feature = "std")]
+pub mod rendering;
+pub mod scalar;
 pub mod types;
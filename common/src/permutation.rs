@@ -0,0 +1,186 @@
+//! Permutations of index sets.
+//!
+//! A [Permutation] of length `n` is stored as a vector `indices` containing each of
+//! `0..n` exactly once. Applying the permutation to a sequence `x` produces the sequence
+//! `y` with `y[i] = x[indices[i]]`. For a matrix, applying a permutation to the rows
+//! therefore corresponds to multiplying with the permutation matrix `P` from the left,
+//! where `P` has a one in position `(i, indices[i])`.
+
+use crate::types::{IndexType, RlstError, RlstResult};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A permutation of the indices `0..n`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Permutation {
+    indices: Vec<IndexType>,
+}
+
+impl Permutation {
+    /// Create the identity permutation of length `n`.
+    pub fn identity(n: IndexType) -> Self {
+        Self {
+            indices: (0..n).collect(),
+        }
+    }
+
+    /// Create a permutation from a vector of indices.
+    ///
+    /// Returns an error if `indices` does not contain each of `0..indices.len()` exactly once.
+    pub fn from_indices(indices: Vec<IndexType>) -> RlstResult<Self> {
+        let mut seen = vec![false; indices.len()];
+        for &index in &indices {
+            if index >= indices.len() || seen[index] {
+                return Err(RlstError::InvalidPermutation(format!(
+                    "Index {} is out of bounds or appears more than once.",
+                    index
+                )));
+            }
+            seen[index] = true;
+        }
+        Ok(Self { indices })
+    }
+
+    /// Create a permutation of length `n` from a Lapack pivot sequence.
+    ///
+    /// The pivot sequence uses the Lapack convention as returned by `getrf`. The
+    /// indices are one based and row `i` was interchanged with row `ipiv[i] - 1` for
+    /// `i = 0, 1, ...` in that order. The returned permutation applied to the rows of
+    /// the original matrix `A` gives the matrix `P * A = L * U`.
+    pub fn from_ipiv(ipiv: &[i32], n: IndexType) -> RlstResult<Self> {
+        if ipiv.len() > n {
+            return Err(RlstError::InvalidPermutation(format!(
+                "Pivot sequence of length {} is longer than the permutation length {}.",
+                ipiv.len(),
+                n
+            )));
+        }
+        let mut indices: Vec<IndexType> = (0..n).collect();
+        for (row, &pivot) in ipiv.iter().enumerate() {
+            if pivot < 1 || pivot as IndexType > n {
+                return Err(RlstError::InvalidPermutation(format!(
+                    "Pivot {} out of bounds for length {}.",
+                    pivot, n
+                )));
+            }
+            indices.swap(row, pivot as IndexType - 1);
+        }
+        Ok(Self { indices })
+    }
+
+    /// Return the length of the permutation.
+    pub fn len(&self) -> IndexType {
+        self.indices.len()
+    }
+
+    /// Return true if the permutation has length zero.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Return the index vector of the permutation.
+    pub fn indices(&self) -> &[IndexType] {
+        &self.indices
+    }
+
+    /// Return the index that is moved to position `index`.
+    #[inline]
+    pub fn get(&self, index: IndexType) -> IndexType {
+        self.indices[index]
+    }
+
+    /// Return the inverse permutation.
+    pub fn inverse(&self) -> Self {
+        let mut indices = vec![0; self.len()];
+        for (position, &index) in self.indices.iter().enumerate() {
+            indices[index] = position;
+        }
+        Self { indices }
+    }
+
+    /// Return the permutation that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Permutation) -> RlstResult<Self> {
+        if self.len() != other.len() {
+            return Err(RlstError::SingleDimensionError {
+                expected: self.len(),
+                actual: other.len(),
+            });
+        }
+        Ok(Self {
+            indices: self.indices.iter().map(|&index| other.get(index)).collect(),
+        })
+    }
+
+    /// Call `f(i, j)` for a sequence of transpositions that applies the permutation in place.
+    ///
+    /// Swapping the elements `i` and `j` of a sequence for each call in order
+    /// has the same effect as applying the permutation.
+    pub fn for_each_swap<F: FnMut(IndexType, IndexType)>(&self, mut f: F) {
+        let mut visited = vec![false; self.len()];
+        for start in 0..self.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut current = start;
+            loop {
+                let next = self.indices[current];
+                if next == start {
+                    break;
+                }
+                f(current, next);
+                visited[next] = true;
+                current = next;
+            }
+        }
+    }
+
+    /// Apply the permutation in place to a slice.
+    pub fn apply_in_place<T>(&self, data: &mut [T]) {
+        assert_eq!(
+            data.len(),
+            self.len(),
+            "Slice of length {} does not match permutation of length {}.",
+            data.len(),
+            self.len()
+        );
+        self.for_each_swap(|first, second| data.swap(first, second));
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_permutation() {
+        let perm = Permutation::from_indices(vec![2, 0, 3, 1]).unwrap();
+        let inverse = perm.inverse();
+        assert_eq!(inverse.indices(), &[1, 3, 0, 2]);
+        assert_eq!(perm.compose(&inverse).unwrap(), Permutation::identity(4));
+
+        let other = Permutation::from_indices(vec![1, 0, 2, 3]).unwrap();
+        let mut data = vec![0, 1, 2, 3];
+        other.apply_in_place(&mut data);
+        perm.apply_in_place(&mut data);
+        let mut expected = vec![0, 1, 2, 3];
+        perm.compose(&other).unwrap().apply_in_place(&mut expected);
+        assert_eq!(data, expected);
+
+        assert!(Permutation::from_indices(vec![0, 0, 1]).is_err());
+        assert!(Permutation::from_indices(vec![0, 3, 1]).is_err());
+    }
+
+    #[test]
+    fn test_from_ipiv() {
+        // Swap rows 0 and 2, then rows 1 and 2.
+        let perm = Permutation::from_ipiv(&[3, 3], 3).unwrap();
+        let mut data = vec![0, 1, 2];
+        perm.apply_in_place(&mut data);
+        assert_eq!(data, vec![2, 0, 1]);
+
+        assert!(Permutation::from_ipiv(&[0], 3).is_err());
+    }
+}
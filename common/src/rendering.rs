@@ -0,0 +1,300 @@
+//! Rendering of matrix structure to images and text.
+//!
+//! A [Rendering] is a grid of pixel intensities in `[0, 1]` that summarises the entries of a
+//! matrix. An intensity of zero marks a pixel without entries. Large matrices are downsampled
+//! so that the longer side of the rendering has at most `max_size` pixels, with each pixel
+//! covering a square block of matrix entries.
+//!
+//! Two kinds of renderings are supported.
+//! - [sparsity_pattern](Rendering::sparsity_pattern) shows the positions of stored entries. The
+//!   intensity of a pixel grows with the fraction of its block that is occupied.
+//! - [heatmap](Rendering::heatmap) shows the largest magnitude within each block on a
+//!   logarithmic scale.
+//!
+//! A rendering can be written as a binary PGM or PPM image, as SVG, or converted into a
+//! Unicode text rendering for terminal output with [to_text](Rendering::to_text).
+
+use crate::types::{IndexType, RlstError, RlstResult};
+use std::io::Write;
+use std::path::Path;
+
+/// The smallest intensity of a pixel that contains at least one entry.
+const MIN_INTENSITY: f64 = 0.15;
+
+/// Unicode shades used by the text rendering, from empty to full.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// A downsampled rendering of the entries of a matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rendering {
+    shape: (IndexType, IndexType),
+    block_size: IndexType,
+    pixels: Vec<f64>,
+}
+
+impl Rendering {
+    /// Render the sparsity pattern of a matrix of the given shape from the positions of its entries.
+    pub fn sparsity_pattern<Iter: IntoIterator<Item = (IndexType, IndexType)>>(
+        shape: (IndexType, IndexType),
+        max_size: IndexType,
+        entries: Iter,
+    ) -> Self {
+        let mut rendering = Self::empty(shape, max_size);
+        let mut counts = vec![0; rendering.pixels.len()];
+        for (row, col) in entries {
+            counts[rendering.pixel_index(row, col)] += 1;
+        }
+
+        let block_size = rendering.block_size;
+        let (pixel_rows, pixel_cols) = rendering.shape;
+        for pixel_row in 0..pixel_rows {
+            for pixel_col in 0..pixel_cols {
+                let index = pixel_row * pixel_cols + pixel_col;
+                if counts[index] == 0 {
+                    continue;
+                }
+                let height = block_extent(pixel_row, block_size, shape.0);
+                let width = block_extent(pixel_col, block_size, shape.1);
+                let fraction = f64::min(1.0, counts[index] as f64 / (height * width) as f64);
+                rendering.pixels[index] = MIN_INTENSITY + (1.0 - MIN_INTENSITY) * fraction;
+            }
+        }
+        rendering
+    }
+
+    /// Render a log-scaled heatmap of the magnitudes of the entries of a matrix of the given shape.
+    ///
+    /// Each pixel shows the largest magnitude within its block. Entries with magnitude zero
+    /// are treated as absent.
+    pub fn heatmap<Iter: IntoIterator<Item = (IndexType, IndexType, f64)>>(
+        shape: (IndexType, IndexType),
+        max_size: IndexType,
+        entries: Iter,
+    ) -> Self {
+        let mut rendering = Self::empty(shape, max_size);
+        let mut magnitudes = vec![0.0; rendering.pixels.len()];
+        for (row, col, value) in entries {
+            let index = rendering.pixel_index(row, col);
+            magnitudes[index] = f64::max(magnitudes[index], value.abs());
+        }
+
+        let logs = magnitudes
+            .iter()
+            .filter(|&&magnitude| magnitude > 0.0)
+            .map(|magnitude| magnitude.log10());
+        let min_log = logs.clone().fold(f64::INFINITY, f64::min);
+        let max_log = logs.fold(f64::NEG_INFINITY, f64::max);
+
+        for (pixel, magnitude) in rendering.pixels.iter_mut().zip(magnitudes) {
+            if magnitude > 0.0 {
+                let scaled = if max_log > min_log {
+                    (magnitude.log10() - min_log) / (max_log - min_log)
+                } else {
+                    1.0
+                };
+                *pixel = MIN_INTENSITY + (1.0 - MIN_INTENSITY) * scaled;
+            }
+        }
+        rendering
+    }
+
+    fn empty(shape: (IndexType, IndexType), max_size: IndexType) -> Self {
+        assert!(
+            max_size > 0,
+            "The maximum size of a rendering must be positive."
+        );
+        let block_size = std::cmp::max(
+            1,
+            std::cmp::max(shape.0.div_ceil(max_size), shape.1.div_ceil(max_size)),
+        );
+        let pixel_shape = (shape.0.div_ceil(block_size), shape.1.div_ceil(block_size));
+        Self {
+            shape: pixel_shape,
+            block_size,
+            pixels: vec![0.0; pixel_shape.0 * pixel_shape.1],
+        }
+    }
+
+    fn pixel_index(&self, row: IndexType, col: IndexType) -> IndexType {
+        let pixel = (row / self.block_size, col / self.block_size);
+        assert!(
+            pixel.0 < self.shape.0 && pixel.1 < self.shape.1,
+            "Entry ({}, {}) out of bounds.",
+            row,
+            col
+        );
+        pixel.0 * self.shape.1 + pixel.1
+    }
+
+    /// Return the number of pixel rows and columns.
+    pub fn shape(&self) -> (IndexType, IndexType) {
+        self.shape
+    }
+
+    /// Return the number of matrix rows and columns covered by a pixel.
+    pub fn block_size(&self) -> IndexType {
+        self.block_size
+    }
+
+    /// Return the intensity of a pixel.
+    pub fn intensity(&self, row: IndexType, col: IndexType) -> f64 {
+        self.pixels[row * self.shape.1 + col]
+    }
+
+    /// Return a text rendering with one Unicode shade character per pixel.
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.shape.0 * (3 * self.shape.1 + 1));
+        for row in 0..self.shape.0 {
+            for col in 0..self.shape.1 {
+                let intensity = self.intensity(row, col);
+                let shade = (intensity * (SHADES.len() - 1) as f64).ceil() as usize;
+                text.push(SHADES[std::cmp::min(shade, SHADES.len() - 1)]);
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Write the rendering as a binary PGM grayscale image. Entries are dark on a white background.
+    pub fn write_pgm<W: Write>(&self, writer: &mut W) -> RlstResult<()> {
+        write!(writer, "P5\n{} {}\n255\n", self.shape.1, self.shape.0)?;
+        let bytes: Vec<u8> = self.pixels.iter().map(|&pixel| gray(pixel)).collect();
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Write the rendering as a binary PPM color image.
+    pub fn write_ppm<W: Write>(&self, writer: &mut W) -> RlstResult<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.shape.1, self.shape.0)?;
+        let bytes: Vec<u8> = self.pixels.iter().flat_map(|&pixel| color(pixel)).collect();
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Write the rendering as an SVG image with one square per non-empty pixel.
+    pub fn write_svg<W: Write>(&self, writer: &mut W) -> RlstResult<()> {
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+            self.shape.1, self.shape.0
+        )?;
+        writeln!(
+            writer,
+            "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>",
+            self.shape.1, self.shape.0
+        )?;
+        for row in 0..self.shape.0 {
+            for col in 0..self.shape.1 {
+                let intensity = self.intensity(row, col);
+                if intensity > 0.0 {
+                    let [red, green, blue] = color(intensity);
+                    writeln!(
+                        writer,
+                        "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                        col, row, red, green, blue
+                    )?;
+                }
+            }
+        }
+        writeln!(writer, "</svg>")?;
+        Ok(())
+    }
+
+    /// Save the rendering to a file. The format is chosen from the extension, which must be
+    /// one of `pgm`, `ppm`, `svg` or `txt`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> RlstResult<()> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        let write: fn(&Self, &mut std::io::BufWriter<std::fs::File>) -> RlstResult<()> =
+            match extension.as_deref() {
+                Some("pgm") => Self::write_pgm,
+                Some("ppm") => Self::write_ppm,
+                Some("svg") => Self::write_svg,
+                Some("txt") => |rendering, writer| {
+                    writer.write_all(rendering.to_text().as_bytes())?;
+                    Ok(())
+                },
+                _ => {
+                    return Err(RlstError::GeneralError(format!(
+                        "Unsupported rendering format for file {}.",
+                        path.display()
+                    )))
+                }
+            };
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write(self, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn block_extent(pixel: IndexType, block_size: IndexType, size: IndexType) -> IndexType {
+    std::cmp::min(block_size, size - pixel * block_size)
+}
+
+fn gray(intensity: f64) -> u8 {
+    (255.0 * (1.0 - intensity)).round() as u8
+}
+
+// Interpolate from white through yellow to dark red.
+fn color(intensity: f64) -> [u8; 3] {
+    if intensity <= 0.0 {
+        return [255, 255, 255];
+    }
+    let low = [255.0, 237.0, 160.0];
+    let high = [128.0, 0.0, 38.0];
+    let mut rgb = [0; 3];
+    for (channel, value) in rgb.iter_mut().enumerate() {
+        *value = (low[channel] + intensity * (high[channel] - low[channel])).round() as u8;
+    }
+    rgb
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_text_shades() {
+        let rendering = Rendering {
+            shape: (1, 6),
+            block_size: 1,
+            pixels: vec![0.0, 0.15, 0.3, 0.5, 0.6, 1.0],
+        };
+        assert_eq!(rendering.to_text(), " ░▒▒▓█\n");
+    }
+
+    #[test]
+    fn test_sparsity_pattern_blocks() {
+        let rendering = Rendering::sparsity_pattern((4, 3), 2, [(0, 0), (1, 1), (3, 2)]);
+        assert_eq!(rendering.shape(), (2, 2));
+        assert_eq!(rendering.block_size(), 2);
+
+        // Two of the four entries of the top left block are occupied.
+        assert_eq!(
+            rendering.intensity(0, 0),
+            MIN_INTENSITY + 0.5 * (1.0 - MIN_INTENSITY)
+        );
+        // The bottom right block only covers two entries.
+        assert_eq!(
+            rendering.intensity(1, 1),
+            MIN_INTENSITY + 0.5 * (1.0 - MIN_INTENSITY)
+        );
+        assert_eq!(rendering.intensity(0, 1), 0.0);
+        assert_eq!(rendering.to_text(), "▓ \n ▓\n");
+    }
+
+    #[test]
+    fn test_save_unsupported_extension() {
+        let rendering = Rendering::sparsity_pattern((2, 2), 2, [(0, 0)]);
+        let path = std::env::temp_dir().join("rlst_rendering_test.png");
+
+        let result = rendering.save(&path);
+        assert!(matches!(result, Err(RlstError::GeneralError(_))));
+        assert!(!path.exists());
+    }
+}
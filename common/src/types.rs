@@ -1,35 +1,86 @@
 //! Basic types
 
 // The scalar type used in the library.
-pub use cauchy::{c32, c64, Scalar};
-use thiserror::Error;
+pub use crate::scalar::{c32, c64, Scalar};
+
+use alloc::string::String;
+use core::fmt;
 
 // The `IndexType` is used whenever we use an integer counting type.
 //
 // By default it should be `usize`.
 pub type IndexType = usize;
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum RlstError {
-    #[error("Method {0} is not implemented.")]
     NotImplemented(String),
-    #[error("Operation {0} failed.")]
     OperationFailed(String),
-    #[error("Dimension mismatch. Expected {expected:}. Actual {actual:}")]
     SingleDimensionError {
         expected: IndexType,
         actual: IndexType,
     },
-    #[error("Index Layout error: {0}")]
     IndexLayoutError(String),
-    #[error("MPI Rank does not exist. {0}")]
     MpiRankError(i32),
-    #[error("Incompatible stride for Lapack.")]
     IncompatibleStride,
-    #[error("Lapack error: {0}")]
     LapackError(i32),
-    #[error("{0}")]
+    SingularMatrix,
+    SingularBatchMatrix(IndexType),
+    NotPositiveDefinite(IndexType),
+    InvalidPermutation(String),
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
+    InvalidFileHeader(String),
     GeneralError(String),
 }
 
-pub type RlstResult<T> = std::result::Result<T, RlstError>;
+// The messages are implemented by hand, so that they are also available without `std`.
+impl fmt::Display for RlstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotImplemented(method) => write!(f, "Method {} is not implemented.", method),
+            Self::OperationFailed(operation) => write!(f, "Operation {} failed.", operation),
+            Self::SingleDimensionError { expected, actual } => write!(
+                f,
+                "Dimension mismatch. Expected {}. Actual {}",
+                expected, actual
+            ),
+            Self::IndexLayoutError(message) => write!(f, "Index Layout error: {}", message),
+            Self::MpiRankError(rank) => write!(f, "MPI Rank does not exist. {}", rank),
+            Self::IncompatibleStride => write!(f, "Incompatible stride for Lapack."),
+            Self::LapackError(info) => write!(f, "Lapack error: {}", info),
+            Self::SingularMatrix => write!(f, "Matrix is singular."),
+            Self::SingularBatchMatrix(index) => {
+                write!(f, "Matrix {} in batch is singular.", index)
+            }
+            Self::NotPositiveDefinite(order) => write!(
+                f,
+                "Matrix is not positive definite. The leading minor of order {} is not positive.",
+                order
+            ),
+            Self::InvalidPermutation(message) => write!(f, "Invalid permutation: {}", message),
+            #[cfg(feature = "std")]
+            Self::IoError(error) => write!(f, "I/O error: {}", error),
+            Self::InvalidFileHeader(message) => write!(f, "Invalid file header: {}", message),
+            Self::GeneralError(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RlstError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IoError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for RlstError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+pub type RlstResult<T> = core::result::Result<T, RlstError>;
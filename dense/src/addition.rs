@@ -10,7 +10,7 @@ use crate::traits::*;
 use crate::types::*;
 use crate::DefaultLayout;
 
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// A type that represents the sum of two matrices.
 pub type AdditionMat<Item, MatImpl1, MatImpl2, RS, CS> =
@@ -115,7 +115,7 @@ impl<
         MatImpl2: MatrixTrait<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::Add<Matrix<Item, MatImpl2, RS, CS>> for Matrix<Item, MatImpl1, RS, CS>
+    > core::ops::Add<Matrix<Item, MatImpl2, RS, CS>> for Matrix<Item, MatImpl1, RS, CS>
 {
     type Output = AdditionMat<Item, MatImpl1, MatImpl2, RS, CS>;
 
@@ -131,7 +131,7 @@ impl<
         MatImpl2: MatrixTrait<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::Add<&'a Matrix<Item, MatImpl2, RS, CS>> for Matrix<Item, MatImpl1, RS, CS>
+    > core::ops::Add<&'a Matrix<Item, MatImpl2, RS, CS>> for Matrix<Item, MatImpl1, RS, CS>
 {
     type Output = AdditionMat<Item, MatImpl1, MatrixRef<'a, Item, MatImpl2, RS, CS>, RS, CS>;
 
@@ -147,7 +147,7 @@ impl<
         MatImpl2: MatrixTrait<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::Add<Matrix<Item, MatImpl2, RS, CS>> for &'a Matrix<Item, MatImpl1, RS, CS>
+    > core::ops::Add<Matrix<Item, MatImpl2, RS, CS>> for &'a Matrix<Item, MatImpl1, RS, CS>
 {
     type Output = AdditionMat<Item, MatrixRef<'a, Item, MatImpl1, RS, CS>, MatImpl2, RS, CS>;
 
@@ -163,7 +163,7 @@ impl<
         MatImpl2: MatrixTrait<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::Add<&'a Matrix<Item, MatImpl2, RS, CS>> for &'a Matrix<Item, MatImpl1, RS, CS>
+    > core::ops::Add<&'a Matrix<Item, MatImpl2, RS, CS>> for &'a Matrix<Item, MatImpl1, RS, CS>
 {
     type Output = AdditionMat<
         Item,
@@ -11,7 +11,7 @@
 use crate::data_container::{DataContainer, DataContainerMut};
 use crate::types::{IndexType, Scalar};
 use crate::{traits::*, DefaultLayout};
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 pub struct BaseMatrix<
     Item: Scalar,
@@ -44,6 +44,18 @@ impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: Siz
     }
 }
 
+impl<
+        Item: Scalar,
+        Data: DataContainer<Item = Item> + Clone,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > Clone for BaseMatrix<Item, Data, RS, CS>
+{
+    fn clone(&self) -> Self {
+        Self::new(self.data.clone(), self.layout)
+    }
+}
+
 impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: SizeIdentifier>
     BaseMatrix<Item, Data, RS, CS>
 {
@@ -52,6 +64,16 @@ impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: Siz
         self.data.get_pointer()
     }
 
+    #[inline]
+    pub fn data_container(&self) -> &Data {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_parts(self) -> (Data, DefaultLayout) {
+        (self.data, self.layout)
+    }
+
     #[inline]
     pub fn get_slice(&self, first: IndexType, last: IndexType) -> &[Item] {
         self.data.get_slice(first, last)
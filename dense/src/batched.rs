@@ -0,0 +1,720 @@
+//! Batched operations on small matrices.
+//!
+//! Applications such as finite element assembly need to perform the same
+//! operation on a large number of small matrices. The routines in this module
+//! take a whole batch of matrices and write the results into a preallocated
+//! output batch.
+//!
+//! A batch is anything that implements [BatchedMatrices] (read access) or
+//! [BatchedMatricesMut] (write access). Two kinds of batches are provided.
+//! - Slices of fixed size matrices, e.g. `&[Matrix33<f64>]`.
+//! - A [Batch], which is a strided three-dimensional buffer described by a
+//!   [BatchLayout] and stored in any [DataContainer]. The matrix with index `i`
+//!   has its entry `(row, col)` at raw position
+//!   `i * stride.2 + row * stride.0 + col * stride.1`.
+//!
+//! The following operations are available.
+//! - [matmul]: `c[i] = alpha * a[i] * b[i] + beta * c[i]`.
+//! - [det]: `out[i] = det(a[i])`.
+//! - [inverse]: `out[i] = inv(a[i])`.
+//! - [solve]: `out[i] = inv(a[i]) * rhs[i]`.
+//!
+//! Determinants and inverses of 2x2 and 3x3 matrices use closed-form expressions.
+//! Larger matrices use an LU decomposition with partial pivoting. All kernels work
+//! on a single matrix at a time without heap allocation for dimensions up to 8, so
+//! that the compiler can unroll and vectorise the inner loops.
+//!
+//! With the `rayon` feature enabled the matrices of a batch are processed in parallel.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_dense::batched::*;
+//! let mut mats = vec![Matrix22::<f64>::zeros_from_dim(), Matrix22::<f64>::zeros_from_dim()];
+//! mats[0][[0, 0]] = 2.0;
+//! mats[0][[1, 1]] = 4.0;
+//! mats[1][[0, 1]] = 1.0;
+//! mats[1][[1, 0]] = 1.0;
+//!
+//! let mut dets = vec![0.0; 2];
+//! det(&mats[..], &mut dets);
+//! assert_eq!(dets, vec![8.0, -1.0]);
+//!
+//! let mut inverses = vec![Matrix22::<f64>::zeros_from_dim(), Matrix22::<f64>::zeros_from_dim()];
+//! inverse(&mats[..], &mut inverses[..]).unwrap();
+//! assert_eq!(inverses[0][[1, 1]], 0.25);
+//! ```
+
+pub(crate) mod kernels;
+
+use crate::base_matrix::BaseMatrix;
+use crate::data_container::{
+    ArrayContainer, DataContainer, DataContainerMut, SliceContainer, SliceContainerMut,
+    VectorContainer,
+};
+use crate::matrix::{Matrix, SliceMatrix, SliceMatrixMut};
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use crate::DefaultLayout;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use rlst_common::types::{RlstError, RlstResult};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Read access to a batch of matrices of identical dimension.
+pub trait BatchedMatrices {
+    type Item: Scalar;
+
+    /// Return the number of matrices in the batch.
+    fn batch_size(&self) -> IndexType;
+
+    /// Return the dimension `(rows, cols)` shared by all matrices in the batch.
+    fn matrix_dim(&self) -> (IndexType, IndexType);
+
+    /// Return a view onto the matrix at position `index` of the batch.
+    fn matrix(&self, index: IndexType) -> SliceMatrix<'_, Self::Item, Dynamic, Dynamic>;
+}
+
+/// Write access to a batch of matrices of identical dimension.
+pub trait BatchedMatricesMut: BatchedMatrices {
+    /// Call `f` with the index and a mutable view of each matrix in the batch.
+    ///
+    /// With the `rayon` feature enabled the matrices are visited in parallel.
+    fn for_each_matrix_mut<F>(&mut self, f: F)
+    where
+        F: Fn(IndexType, SliceMatrixMut<'_, Self::Item, Dynamic, Dynamic>) + Send + Sync;
+}
+
+/// Layout of a batch of matrices in a strided three-dimensional buffer.
+pub struct BatchLayout {
+    dim: (IndexType, IndexType),
+    count: IndexType,
+    stride: (IndexType, IndexType, IndexType),
+}
+
+impl BatchLayout {
+    /// Create a new layout for `count` matrices of dimension `dim`.
+    ///
+    /// The stride tuple `(r, c, b)` gives the distance in memory between rows,
+    /// columns and consecutive matrices of the batch.
+    pub fn new(
+        dim: (IndexType, IndexType),
+        count: IndexType,
+        stride: (IndexType, IndexType, IndexType),
+    ) -> Self {
+        Self { dim, count, stride }
+    }
+
+    /// Create a layout for `count` column-major matrices stored contiguously one after another.
+    pub fn from_dimension(dim: (IndexType, IndexType), count: IndexType) -> Self {
+        Self::new(dim, count, (1, dim.0, dim.0 * dim.1))
+    }
+
+    /// Return the dimension of each matrix in the batch.
+    pub fn dim(&self) -> (IndexType, IndexType) {
+        self.dim
+    }
+
+    /// Return the number of matrices in the batch.
+    pub fn count(&self) -> IndexType {
+        self.count
+    }
+
+    /// Return the stride tuple `(r, c, b)`.
+    pub fn stride(&self) -> (IndexType, IndexType, IndexType) {
+        self.stride
+    }
+
+    /// Return the layout of a single matrix in the batch.
+    pub fn matrix_layout(&self) -> DefaultLayout {
+        DefaultLayout::new(self.dim, (self.stride.0, self.stride.1))
+    }
+
+    /// Convert a `(index, row, col)` triple into a raw memory index.
+    #[inline]
+    pub fn convert_3d_raw(&self, index: IndexType, row: IndexType, col: IndexType) -> IndexType {
+        index * self.stride.2 + row * self.stride.0 + col * self.stride.1
+    }
+
+    /// Return the number of raw memory locations spanned by a single matrix.
+    pub fn matrix_extent(&self) -> IndexType {
+        if self.dim.0 == 0 || self.dim.1 == 0 {
+            0
+        } else {
+            self.convert_3d_raw(0, self.dim.0 - 1, self.dim.1 - 1) + 1
+        }
+    }
+
+    /// Return the number of raw memory locations spanned by the whole batch.
+    pub fn extent(&self) -> IndexType {
+        if self.count == 0 {
+            0
+        } else {
+            (self.count - 1) * self.stride.2 + self.matrix_extent()
+        }
+    }
+}
+
+/// A batch of matrices stored in a strided three-dimensional buffer.
+pub struct Batch<Item: Scalar, Data: DataContainer<Item = Item>> {
+    data: Data,
+    layout: BatchLayout,
+}
+
+/// A batch of matrices that owns its memory.
+pub type BatchD<Item> = Batch<Item, VectorContainer<Item>>;
+
+/// A batch of matrices that references a memory slice.
+pub type SliceBatch<'a, Item> = Batch<Item, SliceContainer<'a, Item>>;
+
+/// Like [SliceBatch] but with mutable access.
+pub type SliceBatchMut<'a, Item> = Batch<Item, SliceContainerMut<'a, Item>>;
+
+impl<Item: Scalar, Data: DataContainer<Item = Item>> Batch<Item, Data> {
+    /// Create a new batch from a data container and a layout.
+    pub fn new(data: Data, layout: BatchLayout) -> Self {
+        assert!(
+            layout.extent() <= data.number_of_elements(),
+            "Number of elements in data: {}. But batch layout requires {} elements.",
+            data.number_of_elements(),
+            layout.extent()
+        );
+        Self { data, layout }
+    }
+
+    /// Return the layout of the batch.
+    pub fn layout(&self) -> &BatchLayout {
+        &self.layout
+    }
+}
+
+impl<Item: Scalar> BatchD<Item> {
+    /// Create a batch of `count` zero matrices of dimension `dim`.
+    pub fn zeros_from_dim(dim: (IndexType, IndexType), count: IndexType) -> Self {
+        let layout = BatchLayout::from_dimension(dim, count);
+        Self::new(VectorContainer::new(layout.extent()), layout)
+    }
+}
+
+impl<Item: Scalar, Data: DataContainerMut<Item = Item>> Batch<Item, Data> {
+    /// Return a mutable view onto the matrix at position `index` of the batch.
+    pub fn matrix_mut(&mut self, index: IndexType) -> SliceMatrixMut<'_, Item, Dynamic, Dynamic> {
+        assert_batch_index(index, self.layout.count);
+        let start = self.layout.convert_3d_raw(index, 0, 0);
+        unsafe {
+            SliceMatrixMut::from_pointer(
+                self.data.get_pointer_mut().add(start),
+                self.layout.dim,
+                (self.layout.stride.0, self.layout.stride.1),
+            )
+        }
+    }
+}
+
+fn assert_batch_index(index: IndexType, count: IndexType) {
+    assert!(
+        index < count,
+        "Batch index {} out of bounds for batch of size {}",
+        index,
+        count
+    );
+}
+
+impl<Item: Scalar, Data: DataContainer<Item = Item>> BatchedMatrices for Batch<Item, Data> {
+    type Item = Item;
+
+    fn batch_size(&self) -> IndexType {
+        self.layout.count
+    }
+
+    fn matrix_dim(&self) -> (IndexType, IndexType) {
+        self.layout.dim
+    }
+
+    fn matrix(&self, index: IndexType) -> SliceMatrix<'_, Item, Dynamic, Dynamic> {
+        assert_batch_index(index, self.layout.count);
+        let start = self.layout.convert_3d_raw(index, 0, 0);
+        unsafe {
+            SliceMatrix::from_pointer(
+                self.data.get_pointer().add(start),
+                self.layout.dim,
+                (self.layout.stride.0, self.layout.stride.1),
+            )
+        }
+    }
+}
+
+impl<Item: Scalar + Send + Sync, Data: DataContainerMut<Item = Item>> BatchedMatricesMut
+    for Batch<Item, Data>
+{
+    fn for_each_matrix_mut<F>(&mut self, f: F)
+    where
+        F: Fn(IndexType, SliceMatrixMut<'_, Item, Dynamic, Dynamic>) + Send + Sync,
+    {
+        let count = self.layout.count;
+        let extent = self.layout.extent();
+        if count == 0 || extent == 0 {
+            return;
+        }
+        let batch_stride = self.layout.stride.2;
+        assert!(
+            batch_stride >= self.layout.matrix_extent(),
+            "Matrices of a mutable batch must not overlap. Batch stride {} is smaller than matrix extent {}.",
+            batch_stride,
+            self.layout.matrix_extent()
+        );
+
+        let dim = self.layout.dim;
+        let stride = (self.layout.stride.0, self.layout.stride.1);
+        let data = self.data.get_slice_mut(0, extent);
+
+        let visit = |(index, chunk): (IndexType, &mut [Item])| {
+            f(index, unsafe {
+                SliceMatrixMut::from_pointer(chunk.as_mut_ptr(), dim, stride)
+            })
+        };
+
+        #[cfg(feature = "rayon")]
+        data.par_chunks_mut(batch_stride)
+            .take(count)
+            .enumerate()
+            .for_each(visit);
+        #[cfg(not(feature = "rayon"))]
+        data.chunks_mut(batch_stride)
+            .take(count)
+            .enumerate()
+            .for_each(visit);
+    }
+}
+
+impl<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier, const N: usize> BatchedMatrices
+    for [Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, N>, RS, CS>, RS, CS>]
+{
+    type Item = Item;
+
+    fn batch_size(&self) -> IndexType {
+        self.len()
+    }
+
+    fn matrix_dim(&self) -> (IndexType, IndexType) {
+        (RS::N, CS::N)
+    }
+
+    fn matrix(&self, index: IndexType) -> SliceMatrix<'_, Item, Dynamic, Dynamic> {
+        assert_batch_index(index, self.len());
+        let mat = &self[index];
+        unsafe {
+            SliceMatrix::from_pointer(mat.get_pointer(), mat.layout().dim(), mat.layout().stride())
+        }
+    }
+}
+
+impl<
+        Item: Scalar + Send + Sync,
+        RS: SizeIdentifier + Send + Sync,
+        CS: SizeIdentifier + Send + Sync,
+        const N: usize,
+    > BatchedMatricesMut
+    for [Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, N>, RS, CS>, RS, CS>]
+{
+    fn for_each_matrix_mut<F>(&mut self, f: F)
+    where
+        F: Fn(IndexType, SliceMatrixMut<'_, Item, Dynamic, Dynamic>) + Send + Sync,
+    {
+        let visit = |(index, mat): (IndexType, &mut Matrix<Item, _, RS, CS>)| {
+            let dim = mat.layout().dim();
+            let stride = mat.layout().stride();
+            f(index, unsafe {
+                SliceMatrixMut::from_pointer(mat.get_pointer_mut(), dim, stride)
+            })
+        };
+
+        #[cfg(feature = "rayon")]
+        self.par_iter_mut().enumerate().for_each(visit);
+        #[cfg(not(feature = "rayon"))]
+        self.iter_mut().enumerate().for_each(visit);
+    }
+}
+
+/// Record the smallest batch index at which a singular matrix was encountered.
+struct SingularIndex(AtomicUsize);
+
+impl SingularIndex {
+    fn new() -> Self {
+        Self(AtomicUsize::new(IndexType::MAX))
+    }
+
+    fn record(&self, index: IndexType) {
+        self.0.fetch_min(index, Ordering::Relaxed);
+    }
+
+    fn into_result(self) -> RlstResult<()> {
+        match self.0.into_inner() {
+            IndexType::MAX => Ok(()),
+            index => Err(RlstError::SingularBatchMatrix(index)),
+        }
+    }
+}
+
+fn assert_same_batch_size(first: IndexType, second: IndexType) {
+    assert_eq!(
+        first, second,
+        "Batch sizes do not match: {} and {}",
+        first, second
+    );
+}
+
+/// Batched matrix product `mat_c[i] = alpha * mat_a[i] * mat_b[i] + beta * mat_c[i]`.
+///
+/// If `beta` is zero then `mat_c` is not read.
+pub fn matmul<Item, BatchA, BatchB, BatchC>(
+    alpha: Item,
+    mat_a: &BatchA,
+    mat_b: &BatchB,
+    beta: Item,
+    mat_c: &mut BatchC,
+) where
+    Item: Scalar + Send + Sync,
+    BatchA: BatchedMatrices<Item = Item> + Sync + ?Sized,
+    BatchB: BatchedMatrices<Item = Item> + Sync + ?Sized,
+    BatchC: BatchedMatricesMut<Item = Item> + ?Sized,
+{
+    let dim1 = mat_a.matrix_dim();
+    let dim2 = mat_b.matrix_dim();
+    let dim3 = mat_c.matrix_dim();
+
+    assert!(
+        (dim1.1 == dim2.0) & (dim3.0 == dim1.0) & (dim3.1 == dim2.1),
+        "Batched matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
+        dim1,
+        dim2,
+        dim3
+    );
+    assert_same_batch_size(mat_a.batch_size(), mat_b.batch_size());
+    assert_same_batch_size(mat_a.batch_size(), mat_c.batch_size());
+
+    mat_c.for_each_matrix_mut(|index, mut out| {
+        kernels::matmul(
+            alpha,
+            &mat_a.matrix(index),
+            &mat_b.matrix(index),
+            beta,
+            &mut out,
+        )
+    });
+}
+
+/// Compute the determinants `out[i] = det(mat[i])` of a batch of square matrices.
+pub fn det<Item, BatchA>(mat: &BatchA, out: &mut [Item])
+where
+    Item: Scalar + Send + Sync,
+    BatchA: BatchedMatrices<Item = Item> + Sync + ?Sized,
+{
+    let dim = mat.matrix_dim();
+    assert_eq!(
+        dim.0, dim.1,
+        "Matrices must be square. Dimension: {:#?}",
+        dim
+    );
+    assert_same_batch_size(mat.batch_size(), out.len());
+
+    let visit = |(index, elem): (IndexType, &mut Item)| {
+        *elem = kernels::det(dim.0, &mat.matrix(index));
+    };
+
+    #[cfg(feature = "rayon")]
+    out.par_iter_mut().enumerate().for_each(visit);
+    #[cfg(not(feature = "rayon"))]
+    out.iter_mut().enumerate().for_each(visit);
+}
+
+/// Compute the inverses `out[i] = inv(mat[i])` of a batch of square matrices.
+///
+/// Returns [RlstError::SingularBatchMatrix] with the smallest index of a singular
+/// matrix if one is encountered. All other matrices are still inverted.
+pub fn inverse<Item, BatchA, BatchOut>(mat: &BatchA, out: &mut BatchOut) -> RlstResult<()>
+where
+    Item: Scalar + Send + Sync,
+    BatchA: BatchedMatrices<Item = Item> + Sync + ?Sized,
+    BatchOut: BatchedMatricesMut<Item = Item> + ?Sized,
+{
+    let dim = mat.matrix_dim();
+    assert_eq!(
+        dim.0, dim.1,
+        "Matrices must be square. Dimension: {:#?}",
+        dim
+    );
+    assert_eq!(
+        dim,
+        out.matrix_dim(),
+        "Output dimension {:#?} does not match input dimension {:#?}",
+        out.matrix_dim(),
+        dim
+    );
+    assert_same_batch_size(mat.batch_size(), out.batch_size());
+
+    let singular = SingularIndex::new();
+    out.for_each_matrix_mut(|index, mut out| {
+        if !kernels::inverse(dim.0, &mat.matrix(index), &mut out) {
+            singular.record(index);
+        }
+    });
+    singular.into_result()
+}
+
+/// Solve the linear systems `mat[i] * out[i] = rhs[i]` for a batch of square matrices.
+///
+/// The right-hand sides may have more than one column. Returns
+/// [RlstError::SingularBatchMatrix] with the smallest index of a singular
+/// matrix if one is encountered. All other systems are still solved.
+pub fn solve<Item, BatchA, BatchRhs, BatchOut>(
+    mat: &BatchA,
+    rhs: &BatchRhs,
+    out: &mut BatchOut,
+) -> RlstResult<()>
+where
+    Item: Scalar + Send + Sync,
+    BatchA: BatchedMatrices<Item = Item> + Sync + ?Sized,
+    BatchRhs: BatchedMatrices<Item = Item> + Sync + ?Sized,
+    BatchOut: BatchedMatricesMut<Item = Item> + ?Sized,
+{
+    let dim = mat.matrix_dim();
+    let rhs_dim = rhs.matrix_dim();
+    assert_eq!(
+        dim.0, dim.1,
+        "Matrices must be square. Dimension: {:#?}",
+        dim
+    );
+    assert_eq!(
+        dim.1, rhs_dim.0,
+        "Right-hand side dimension {:#?} incompatible with matrix dimension {:#?}",
+        rhs_dim, dim
+    );
+    assert_eq!(
+        rhs_dim,
+        out.matrix_dim(),
+        "Output dimension {:#?} does not match right-hand side dimension {:#?}",
+        out.matrix_dim(),
+        rhs_dim
+    );
+    assert_same_batch_size(mat.batch_size(), rhs.batch_size());
+    assert_same_batch_size(mat.batch_size(), out.batch_size());
+
+    let singular = SingularIndex::new();
+    out.for_each_matrix_mut(|index, mut out| {
+        if !kernels::solve(
+            dim.0,
+            rhs_dim.1,
+            &mat.matrix(index),
+            &rhs.matrix(index),
+            &mut out,
+        ) {
+            singular.record(index);
+        }
+    });
+    singular.into_result()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+
+    use super::*;
+    use crate::matrix::*;
+    use crate::Dot;
+    use approx::assert_relative_eq;
+    use rand::prelude::*;
+
+    fn rand_batch(dim: (IndexType, IndexType), count: IndexType) -> BatchD<f64> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut batch = BatchD::<f64>::zeros_from_dim(dim, count);
+        for index in 0..count {
+            batch
+                .matrix_mut(index)
+                .for_each(|val| *val = rng.gen_range(-1.0..1.0));
+        }
+        batch
+    }
+
+    fn copy_to_matrixd(mat: &SliceMatrix<'_, f64, Dynamic, Dynamic>) -> MatrixD<f64> {
+        let dim = mat.dim();
+        let mut res = MatrixD::<f64>::zeros_from_dim(dim.0, dim.1);
+        for col in 0..dim.1 {
+            for row in 0..dim.0 {
+                res[[row, col]] = mat.get_value(row, col);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_batch_layout() {
+        let layout = BatchLayout::new((2, 3), 4, (3, 1, 7));
+        assert_eq!(layout.convert_3d_raw(2, 1, 2), 19);
+        assert_eq!(layout.matrix_extent(), 6);
+        assert_eq!(layout.extent(), 27);
+    }
+
+    #[test]
+    fn test_batched_matmul() {
+        let mat_a = rand_batch((3, 4), 5);
+        let mat_b = rand_batch((4, 2), 5);
+        let mut mat_c = BatchD::<f64>::zeros_from_dim((3, 2), 5);
+
+        matmul(1.0, &mat_a, &mat_b, 0.0, &mut mat_c);
+
+        for index in 0..5 {
+            let expected =
+                copy_to_matrixd(&mat_a.matrix(index)).dot(&copy_to_matrixd(&mat_b.matrix(index)));
+            for row in 0..3 {
+                for col in 0..2 {
+                    assert_relative_eq!(
+                        mat_c.matrix(index).get_value(row, col),
+                        expected[[row, col]],
+                        max_relative = 1E-13
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_batched_matmul_fixed() {
+        let mut mat_a = [
+            Matrix33::<f64>::zeros_from_dim(),
+            Matrix33::<f64>::zeros_from_dim(),
+        ];
+        let mut mat_c = [
+            Matrix33::<f64>::zeros_from_dim(),
+            Matrix33::<f64>::zeros_from_dim(),
+        ];
+        for (index, mat) in mat_a.iter_mut().enumerate() {
+            for row in 0..3 {
+                mat[[row, row]] = (1 + index) as f64;
+                mat_c[index][[row, row]] = 1.0;
+            }
+            mat[[0, 2]] = 1.0;
+        }
+
+        matmul(2.0, &mat_a[..], &mat_a[..], 1.0, &mut mat_c[..]);
+
+        assert_eq!(mat_c[0][[0, 0]], 3.0);
+        assert_eq!(mat_c[0][[0, 2]], 4.0);
+        assert_eq!(mat_c[1][[1, 1]], 9.0);
+        assert_eq!(mat_c[1][[0, 2]], 8.0);
+    }
+
+    #[test]
+    fn test_batched_det() {
+        for n in 1..7 {
+            let mat = rand_batch((n, n), 3);
+            let mut dets = vec![0.0; 3];
+            det(&mat, &mut dets);
+
+            for (index, &actual) in dets.iter().enumerate() {
+                // Compare with a Laplace expansion along the first column
+                // of a copy of the matrix.
+                let expected = laplace_det(&copy_to_matrixd(&mat.matrix(index)));
+                assert_relative_eq!(actual, expected, max_relative = 1E-12);
+            }
+        }
+    }
+
+    fn laplace_det(mat: &MatrixD<f64>) -> f64 {
+        let n = mat.dim().0;
+        if n == 1 {
+            return mat[[0, 0]];
+        }
+        let mut result = 0.0;
+        for row in 0..n {
+            let mut minor = MatrixD::<f64>::zeros_from_dim(n - 1, n - 1);
+            for (minor_row, orig_row) in (0..n).filter(|&r| r != row).enumerate() {
+                for col in 1..n {
+                    minor[[minor_row, col - 1]] = mat[[orig_row, col]];
+                }
+            }
+            let sign = if row % 2 == 0 { 1.0 } else { -1.0 };
+            result += sign * mat[[row, 0]] * laplace_det(&minor);
+        }
+        result
+    }
+
+    #[test]
+    fn test_batched_inverse() {
+        for n in [2, 3, 6] {
+            let mat = rand_batch((n, n), 4);
+            let mut inv = BatchD::<f64>::zeros_from_dim((n, n), 4);
+            let mut ident = BatchD::<f64>::zeros_from_dim((n, n), 4);
+
+            inverse(&mat, &mut inv).unwrap();
+            matmul(1.0, &mat, &inv, 0.0, &mut ident);
+
+            for index in 0..4 {
+                for row in 0..n {
+                    for col in 0..n {
+                        let expected = if row == col { 1.0 } else { 0.0 };
+                        assert_relative_eq!(
+                            ident.matrix(index).get_value(row, col),
+                            expected,
+                            epsilon = 1E-10
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_batched_inverse_singular() {
+        let mut mat = rand_batch((4, 4), 3);
+        for col in 0..4 {
+            let val = mat.matrix(1).get_value(0, col);
+            *mat.matrix_mut(1).get_mut(2, col).unwrap() = val;
+        }
+        let mut inv = BatchD::<f64>::zeros_from_dim((4, 4), 3);
+
+        assert!(matches!(
+            inverse(&mat, &mut inv),
+            Err(RlstError::SingularBatchMatrix(1))
+        ));
+    }
+
+    #[test]
+    fn test_batched_solve() {
+        for n in [3, 6] {
+            let mat = rand_batch((n, n), 4);
+            let rhs = rand_batch((n, 2), 4);
+            let mut sol = BatchD::<f64>::zeros_from_dim((n, 2), 4);
+            let mut res = BatchD::<f64>::zeros_from_dim((n, 2), 4);
+
+            solve(&mat, &rhs, &mut sol).unwrap();
+            matmul(1.0, &mat, &sol, 0.0, &mut res);
+
+            for index in 0..4 {
+                for row in 0..n {
+                    for col in 0..2 {
+                        assert_relative_eq!(
+                            res.matrix(index).get_value(row, col),
+                            rhs.matrix(index).get_value(row, col),
+                            epsilon = 1E-10
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strided_batch() {
+        // Two 2x2 matrices stored row-major and interleaved with a batch stride of 1.
+        let data = vec![1.0, 5.0, 2.0, 6.0, 3.0, 7.0, 4.0, 8.0];
+        let batch = SliceBatch::new(
+            SliceContainer::new(&data),
+            BatchLayout::new((2, 2), 2, (4, 2, 1)),
+        );
+        let mut dets = vec![0.0; 2];
+        det(&batch, &mut dets);
+
+        assert_eq!(batch.matrix(1).get_value(1, 0), 7.0);
+        assert_eq!(dets, vec![-2.0, -2.0]);
+    }
+}
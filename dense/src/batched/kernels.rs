@@ -0,0 +1,345 @@
+//! Kernels for single small matrices.
+//!
+//! The kernels in this module operate on one matrix at a time and are
+//! generic over the random access traits, so that they can be called
+//! on views into a batch as well as directly on fixed size matrices.
+//! Dimensions are not checked. It is the responsibility of the caller
+//! to pass matrices of compatible size.
+//!
+//! The closed-form routines for dimension 2 and 3 are fully unrolled. Larger
+//! matrices are handled through an LU decomposition with partial pivoting
+//! on a column-major workspace.
+
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use alloc::vec;
+
+/// Largest dimension for which the LU workspace is kept on the stack.
+const STACK_DIM: usize = 8;
+
+/// Compute `mat_c = alpha * mat_a * mat_b + beta * mat_c`.
+///
+/// If `beta` is zero then `mat_c` is not read.
+pub(crate) fn matmul<
+    Item: Scalar,
+    MatA: UnsafeRandomAccessByValue<Item = Item> + Layout,
+    MatB: UnsafeRandomAccessByValue<Item = Item> + Layout,
+    MatC: UnsafeRandomAccessMut<Item = Item> + Layout,
+>(
+    alpha: Item,
+    mat_a: &MatA,
+    mat_b: &MatB,
+    beta: Item,
+    mat_c: &mut MatC,
+) {
+    let (m, k) = mat_a.layout().dim();
+    let n = mat_b.layout().dim().1;
+
+    for col in 0..n {
+        for row in 0..m {
+            let mut acc = <Item as num::Zero>::zero();
+            for inner in 0..k {
+                acc += unsafe {
+                    mat_a.get_value_unchecked(row, inner) * mat_b.get_value_unchecked(inner, col)
+                };
+            }
+            let elem = unsafe { mat_c.get_unchecked_mut(row, col) };
+            if beta == <Item as num::Zero>::zero() {
+                *elem = alpha * acc;
+            } else {
+                *elem = alpha * acc + beta * *elem;
+            }
+        }
+    }
+}
+
+/// Determinant of a 2x2 matrix.
+#[inline]
+pub(crate) fn det2<Item: Scalar, Mat: UnsafeRandomAccessByValue<Item = Item>>(mat: &Mat) -> Item {
+    let a = |row, col| unsafe { mat.get_value_unchecked(row, col) };
+    a(0, 0) * a(1, 1) - a(0, 1) * a(1, 0)
+}
+
+/// Determinant of a 3x3 matrix.
+#[inline]
+pub(crate) fn det3<Item: Scalar, Mat: UnsafeRandomAccessByValue<Item = Item>>(mat: &Mat) -> Item {
+    let a = |row, col| unsafe { mat.get_value_unchecked(row, col) };
+    a(0, 0) * (a(1, 1) * a(2, 2) - a(1, 2) * a(2, 1))
+        - a(0, 1) * (a(1, 0) * a(2, 2) - a(1, 2) * a(2, 0))
+        + a(0, 2) * (a(1, 0) * a(2, 1) - a(1, 1) * a(2, 0))
+}
+
+/// Write the adjugate of a 2x2 matrix into `out`.
+#[inline]
+pub(crate) fn adjugate2<
+    Item: Scalar,
+    Mat: UnsafeRandomAccessByValue<Item = Item>,
+    Out: UnsafeRandomAccessMut<Item = Item>,
+>(
+    mat: &Mat,
+    out: &mut Out,
+) {
+    let a = |row, col| unsafe { mat.get_value_unchecked(row, col) };
+    let (a00, a01, a10, a11) = (a(0, 0), a(0, 1), a(1, 0), a(1, 1));
+    unsafe {
+        *out.get_unchecked_mut(0, 0) = a11;
+        *out.get_unchecked_mut(0, 1) = -a01;
+        *out.get_unchecked_mut(1, 0) = -a10;
+        *out.get_unchecked_mut(1, 1) = a00;
+    }
+}
+
+/// Write the adjugate of a 3x3 matrix into `out`.
+#[inline]
+pub(crate) fn adjugate3<
+    Item: Scalar,
+    Mat: UnsafeRandomAccessByValue<Item = Item>,
+    Out: UnsafeRandomAccessMut<Item = Item>,
+>(
+    mat: &Mat,
+    out: &mut Out,
+) {
+    let a = |row, col| unsafe { mat.get_value_unchecked(row, col) };
+    let (a00, a01, a02) = (a(0, 0), a(0, 1), a(0, 2));
+    let (a10, a11, a12) = (a(1, 0), a(1, 1), a(1, 2));
+    let (a20, a21, a22) = (a(2, 0), a(2, 1), a(2, 2));
+    unsafe {
+        *out.get_unchecked_mut(0, 0) = a11 * a22 - a12 * a21;
+        *out.get_unchecked_mut(0, 1) = a02 * a21 - a01 * a22;
+        *out.get_unchecked_mut(0, 2) = a01 * a12 - a02 * a11;
+        *out.get_unchecked_mut(1, 0) = a12 * a20 - a10 * a22;
+        *out.get_unchecked_mut(1, 1) = a00 * a22 - a02 * a20;
+        *out.get_unchecked_mut(1, 2) = a02 * a10 - a00 * a12;
+        *out.get_unchecked_mut(2, 0) = a10 * a21 - a11 * a20;
+        *out.get_unchecked_mut(2, 1) = a01 * a20 - a00 * a21;
+        *out.get_unchecked_mut(2, 2) = a00 * a11 - a01 * a10;
+    }
+}
+
+/// Multiply all entries of the leading `dim` block of `out` by `scalar`.
+#[inline]
+fn scale<Item: Scalar, Out: UnsafeRandomAccessMut<Item = Item>>(
+    dim: (IndexType, IndexType),
+    scalar: Item,
+    out: &mut Out,
+) {
+    for col in 0..dim.1 {
+        for row in 0..dim.0 {
+            unsafe { *out.get_unchecked_mut(row, col) *= scalar };
+        }
+    }
+}
+
+/// LU decomposition with partial pivoting of the `n x n` column-major matrix `lu`.
+///
+/// On exit `lu` contains the unit lower triangular factor below the diagonal and the
+/// upper triangular factor on and above the diagonal. `piv[k]` is the row interchanged
+/// with row `k` in step `k`. Returns `false` if an exactly zero pivot is encountered.
+fn lu_factor<Item: Scalar>(n: IndexType, lu: &mut [Item], piv: &mut [IndexType]) -> bool {
+    for k in 0..n {
+        let mut pivot_row = k;
+        let mut pivot_abs = lu[k + k * n].abs();
+        for row in (k + 1)..n {
+            let val = lu[row + k * n].abs();
+            if val > pivot_abs {
+                pivot_abs = val;
+                pivot_row = row;
+            }
+        }
+        piv[k] = pivot_row;
+        if pivot_abs == <Item::Real as num::Zero>::zero() {
+            return false;
+        }
+        if pivot_row != k {
+            for col in 0..n {
+                lu.swap(k + col * n, pivot_row + col * n);
+            }
+        }
+        let inv_pivot = <Item as num::One>::one() / lu[k + k * n];
+        for row in (k + 1)..n {
+            lu[row + k * n] *= inv_pivot;
+        }
+        for col in (k + 1)..n {
+            let factor = lu[k + col * n];
+            for row in (k + 1)..n {
+                let l = lu[row + k * n];
+                lu[row + col * n] -= l * factor;
+            }
+        }
+    }
+    true
+}
+
+/// Solve with the LU decomposition from [lu_factor] for column `col` of `rhs` in place.
+fn lu_solve_column<Item: Scalar, Out: UnsafeRandomAccessMut<Item = Item>>(
+    n: IndexType,
+    lu: &[Item],
+    piv: &[IndexType],
+    rhs: &mut Out,
+    col: IndexType,
+) {
+    unsafe {
+        for (k, &p) in piv.iter().enumerate().take(n) {
+            if p != k {
+                let tmp = *rhs.get_unchecked_mut(k, col);
+                *rhs.get_unchecked_mut(k, col) = *rhs.get_unchecked_mut(p, col);
+                *rhs.get_unchecked_mut(p, col) = tmp;
+            }
+        }
+        for k in 0..n {
+            let xk = *rhs.get_unchecked_mut(k, col);
+            for row in (k + 1)..n {
+                *rhs.get_unchecked_mut(row, col) -= lu[row + k * n] * xk;
+            }
+        }
+        for k in (0..n).rev() {
+            *rhs.get_unchecked_mut(k, col) /= lu[k + k * n];
+            let xk = *rhs.get_unchecked_mut(k, col);
+            for row in 0..k {
+                *rhs.get_unchecked_mut(row, col) -= lu[row + k * n] * xk;
+            }
+        }
+    }
+}
+
+/// Copy `mat` into a column-major workspace, factorize it and pass the factors to `f`.
+///
+/// `f` receives `None` if the matrix is singular.
+fn with_lu<Item: Scalar, Mat: UnsafeRandomAccessByValue<Item = Item>, R>(
+    n: IndexType,
+    mat: &Mat,
+    f: impl FnOnce(Option<(&[Item], &[IndexType])>) -> R,
+) -> R {
+    let mut stack_lu = [<Item as num::Zero>::zero(); STACK_DIM * STACK_DIM];
+    let mut stack_piv = [0; STACK_DIM];
+    let mut heap_lu;
+    let mut heap_piv;
+
+    let (lu, piv): (&mut [Item], &mut [IndexType]) = if n <= STACK_DIM {
+        (&mut stack_lu[..n * n], &mut stack_piv[..n])
+    } else {
+        heap_lu = vec![<Item as num::Zero>::zero(); n * n];
+        heap_piv = vec![0; n];
+        (&mut heap_lu, &mut heap_piv)
+    };
+
+    for col in 0..n {
+        for row in 0..n {
+            lu[row + col * n] = unsafe { mat.get_value_unchecked(row, col) };
+        }
+    }
+
+    if lu_factor(n, lu, piv) {
+        f(Some((lu, piv)))
+    } else {
+        f(None)
+    }
+}
+
+/// Determinant of an `n x n` matrix.
+pub(crate) fn det<Item: Scalar, Mat: UnsafeRandomAccessByValue<Item = Item>>(
+    n: IndexType,
+    mat: &Mat,
+) -> Item {
+    match n {
+        1 => unsafe { mat.get_value_unchecked(0, 0) },
+        2 => det2(mat),
+        3 => det3(mat),
+        _ => with_lu(n, mat, |factors| match factors {
+            None => <Item as num::Zero>::zero(),
+            Some((lu, piv)) => {
+                let mut result = <Item as num::One>::one();
+                for (k, &p) in piv.iter().enumerate() {
+                    result *= lu[k + k * n];
+                    if p != k {
+                        result = -result;
+                    }
+                }
+                result
+            }
+        }),
+    }
+}
+
+/// Write the inverse of an `n x n` matrix into `out`.
+///
+/// Returns `false` if the matrix is singular, in which case the content
+/// of `out` is unspecified.
+pub(crate) fn inverse<
+    Item: Scalar,
+    Mat: UnsafeRandomAccessByValue<Item = Item>,
+    Out: UnsafeRandomAccessMut<Item = Item>,
+>(
+    n: IndexType,
+    mat: &Mat,
+    out: &mut Out,
+) -> bool {
+    let zero = <Item as num::Zero>::zero();
+    let one = <Item as num::One>::one();
+    match n {
+        1 => {
+            let val = unsafe { mat.get_value_unchecked(0, 0) };
+            unsafe { *out.get_unchecked_mut(0, 0) = one / val };
+            val != zero
+        }
+        2 | 3 => {
+            let det = if n == 2 { det2(mat) } else { det3(mat) };
+            if det == zero {
+                return false;
+            }
+            if n == 2 {
+                adjugate2(mat, out);
+            } else {
+                adjugate3(mat, out);
+            }
+            scale((n, n), one / det, out);
+            true
+        }
+        _ => with_lu(n, mat, |factors| match factors {
+            None => false,
+            Some((lu, piv)) => {
+                for col in 0..n {
+                    for row in 0..n {
+                        unsafe {
+                            *out.get_unchecked_mut(row, col) = if row == col { one } else { zero }
+                        };
+                    }
+                    lu_solve_column(n, lu, piv, out, col);
+                }
+                true
+            }
+        }),
+    }
+}
+
+/// Solve `mat * out = rhs` for an `n x n` matrix and `n x nrhs` right-hand side.
+///
+/// Returns `false` if the matrix is singular, in which case the content
+/// of `out` is unspecified.
+pub(crate) fn solve<
+    Item: Scalar,
+    Mat: UnsafeRandomAccessByValue<Item = Item>,
+    Rhs: UnsafeRandomAccessByValue<Item = Item>,
+    Out: UnsafeRandomAccessMut<Item = Item>,
+>(
+    n: IndexType,
+    nrhs: IndexType,
+    mat: &Mat,
+    rhs: &Rhs,
+    out: &mut Out,
+) -> bool {
+    for col in 0..nrhs {
+        for row in 0..n {
+            unsafe { *out.get_unchecked_mut(row, col) = rhs.get_value_unchecked(row, col) };
+        }
+    }
+    with_lu(n, mat, |factors| match factors {
+        None => false,
+        Some((lu, piv)) => {
+            for col in 0..nrhs {
+                lu_solve_column(n, lu, piv, out, col);
+            }
+            true
+        }
+    })
+}
@@ -0,0 +1,420 @@
+//! Conversion between scalar types and access to complex components.
+//!
+//! This module provides lazy elementwise expressions that change the scalar type of a matrix.
+//! - [cast](Matrix::cast) converts between [f32], [f64], [c32] and [c64]. Real matrices can be
+//!   cast to complex ones, but not the other way round.
+//! - [to_complex](Matrix::to_complex) converts a real matrix to the complex matrix of the
+//!   same precision.
+//! - [real](Matrix::real), [imag](Matrix::imag) and [conj](Matrix::conj) return the real part,
+//!   the imaginary part and the complex conjugate of a complex matrix.
+//!
+//! As for the other expressions, the operation is performed on element access and the
+//! result can be evaluated into a new matrix with [eval](Matrix::eval).
+//!
+//! For complex base matrices the methods [real_view](Matrix::real_view) and
+//! [imag_view](Matrix::imag_view) (and their mutable counterparts) return zero-copy views
+//! onto the interleaved real and imaginary parts. These views have twice the stride
+//! of the complex matrix.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_dense::types::{c32, c64};
+//! let mut mat = MatrixD::<c64>::zeros_from_dim(2, 2);
+//! mat[[0, 1]] = c64::new(1.0, 2.0);
+//!
+//! assert_eq!(mat.real_view()[[0, 1]], 1.0);
+//! assert_eq!(mat.imag_view()[[0, 1]], 2.0);
+//!
+//! let single = mat.cast::<c32>().eval();
+//! assert_eq!(single[[0, 1]], c32::new(1.0, 2.0));
+//! assert_eq!(single.conj().eval()[[0, 1]], c32::new(1.0, -2.0));
+//! ```
+
+use crate::base_matrix::BaseMatrix;
+use crate::data_container::{DataContainer, DataContainerMut};
+use crate::matrix::{Matrix, SliceMatrix, SliceMatrixMut};
+use crate::traits::*;
+use crate::types::{c32, c64, IndexType, Scalar};
+use crate::DefaultLayout;
+use core::marker::PhantomData;
+
+/// Conversion of a scalar into the scalar type `Target`.
+pub trait ScalarCast<Target: Scalar>: Scalar {
+    /// Convert `self` into `Target`.
+    fn cast_to(self) -> Target;
+}
+
+macro_rules! scalar_cast_real_impl {
+    ($Source:ty, $Target:ty) => {
+        impl ScalarCast<$Target> for $Source {
+            #[inline]
+            fn cast_to(self) -> $Target {
+                self as $Target
+            }
+        }
+    };
+}
+
+macro_rules! scalar_cast_complex_impl {
+    ($Source:ty, $Target:ty, $TargetReal:ty) => {
+        impl ScalarCast<$Target> for $Source {
+            #[inline]
+            fn cast_to(self) -> $Target {
+                <$Target>::new(self.re as $TargetReal, self.im as $TargetReal)
+            }
+        }
+    };
+}
+
+macro_rules! scalar_cast_real_to_complex_impl {
+    ($Source:ty, $Target:ty, $TargetReal:ty) => {
+        impl ScalarCast<$Target> for $Source {
+            #[inline]
+            fn cast_to(self) -> $Target {
+                <$Target>::new(self as $TargetReal, 0.0)
+            }
+        }
+    };
+}
+
+scalar_cast_real_impl!(f32, f32);
+scalar_cast_real_impl!(f32, f64);
+scalar_cast_real_impl!(f64, f32);
+scalar_cast_real_impl!(f64, f64);
+
+scalar_cast_complex_impl!(c32, c32, f32);
+scalar_cast_complex_impl!(c32, c64, f64);
+scalar_cast_complex_impl!(c64, c32, f32);
+scalar_cast_complex_impl!(c64, c64, f64);
+
+scalar_cast_real_to_complex_impl!(f32, c32, f32);
+scalar_cast_real_to_complex_impl!(f32, c64, f64);
+scalar_cast_real_to_complex_impl!(f64, c32, f32);
+scalar_cast_real_to_complex_impl!(f64, c64, f64);
+
+/// An elementwise operation that maps scalars of type `Item` to scalars of type `Output`.
+pub trait UnaryOp<Item: Scalar> {
+    type Output: Scalar;
+
+    /// Apply the operation to a single element.
+    fn apply(value: Item) -> Self::Output;
+}
+
+/// Cast into the scalar type `Target`.
+pub struct CastOp<Target>(PhantomData<Target>);
+
+/// Take the real part.
+pub struct RealOp;
+
+/// Take the imaginary part.
+pub struct ImagOp;
+
+/// Take the complex conjugate.
+pub struct ConjOp;
+
+impl<Item: ScalarCast<Target>, Target: Scalar> UnaryOp<Item> for CastOp<Target> {
+    type Output = Target;
+
+    #[inline]
+    fn apply(value: Item) -> Target {
+        value.cast_to()
+    }
+}
+
+impl<Item: Scalar> UnaryOp<Item> for RealOp {
+    type Output = Item::Real;
+
+    #[inline]
+    fn apply(value: Item) -> Item::Real {
+        value.re()
+    }
+}
+
+impl<Item: Scalar> UnaryOp<Item> for ImagOp {
+    type Output = Item::Real;
+
+    #[inline]
+    fn apply(value: Item) -> Item::Real {
+        value.im()
+    }
+}
+
+impl<Item: Scalar> UnaryOp<Item> for ConjOp {
+    type Output = Item;
+
+    #[inline]
+    fn apply(value: Item) -> Item {
+        value.conj()
+    }
+}
+
+/// This type represents an elementwise operation applied to a matrix.
+pub type UnaryMat<Item, Op, MatImpl, RS, CS> =
+    Matrix<<Op as UnaryOp<Item>>::Output, Unary<Item, Op, MatImpl, RS, CS>, RS, CS>;
+
+/// A structure holding a matrix and an elementwise operation. On random access
+/// the operation is applied to the corresponding matrix entry.
+pub struct Unary<Item, Op, MatImpl, RS, CS>(
+    Matrix<Item, MatImpl, RS, CS>,
+    PhantomData<Op>,
+    PhantomData<RS>,
+    PhantomData<CS>,
+)
+where
+    Item: Scalar,
+    Op: UnaryOp<Item>,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    MatImpl: MatrixTrait<Item, RS, CS>;
+
+impl<
+        Item: Scalar,
+        Op: UnaryOp<Item>,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > Unary<Item, Op, MatImpl, RS, CS>
+{
+    pub fn new(mat: Matrix<Item, MatImpl, RS, CS>) -> Self {
+        Self(mat, PhantomData, PhantomData, PhantomData)
+    }
+}
+
+impl<
+        Item: Scalar,
+        Op: UnaryOp<Item>,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > SizeType for Unary<Item, Op, MatImpl, RS, CS>
+{
+    type R = RS;
+    type C = CS;
+}
+
+impl<
+        Item: Scalar,
+        Op: UnaryOp<Item>,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > Layout for Unary<Item, Op, MatImpl, RS, CS>
+{
+    type Impl = DefaultLayout;
+
+    #[inline]
+    fn layout(&self) -> &Self::Impl {
+        self.0.layout()
+    }
+}
+
+impl<
+        Item: Scalar,
+        Op: UnaryOp<Item>,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > UnsafeRandomAccessByValue for Unary<Item, Op, MatImpl, RS, CS>
+{
+    type Item = Op::Output;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, row: IndexType, col: IndexType) -> Self::Item {
+        Op::apply(self.0.get_value_unchecked(row, col))
+    }
+
+    #[inline]
+    unsafe fn get1d_value_unchecked(&self, index: IndexType) -> Self::Item {
+        Op::apply(self.0.get1d_value_unchecked(index))
+    }
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, MatImpl, RS, CS>
+{
+    /// Lazily convert the matrix into a matrix with scalar type `Target`.
+    pub fn cast<Target: Scalar>(self) -> UnaryMat<Item, CastOp<Target>, MatImpl, RS, CS>
+    where
+        Item: ScalarCast<Target>,
+    {
+        Matrix::new(Unary::new(self))
+    }
+}
+
+macro_rules! to_complex_impl {
+    ($Scalar:ty, $Complex:ty) => {
+        impl<MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+            Matrix<$Scalar, MatImpl, RS, CS>
+        {
+            /// Lazily convert the matrix into a complex matrix of the same precision.
+            pub fn to_complex(self) -> UnaryMat<$Scalar, CastOp<$Complex>, MatImpl, RS, CS> {
+                self.cast::<$Complex>()
+            }
+        }
+    };
+}
+
+to_complex_impl!(f32, c32);
+to_complex_impl!(f64, c64);
+
+macro_rules! complex_impl {
+    ($Scalar:ty, $Real:ty) => {
+        impl<MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+            Matrix<$Scalar, MatImpl, RS, CS>
+        {
+            /// Lazily take the real part of the matrix.
+            pub fn real(self) -> UnaryMat<$Scalar, RealOp, MatImpl, RS, CS> {
+                Matrix::new(Unary::new(self))
+            }
+
+            /// Lazily take the imaginary part of the matrix.
+            pub fn imag(self) -> UnaryMat<$Scalar, ImagOp, MatImpl, RS, CS> {
+                Matrix::new(Unary::new(self))
+            }
+
+            /// Lazily take the complex conjugate of the matrix.
+            pub fn conj(self) -> UnaryMat<$Scalar, ConjOp, MatImpl, RS, CS> {
+                Matrix::new(Unary::new(self))
+            }
+        }
+
+        impl<Data: DataContainer<Item = $Scalar>>
+            Matrix<$Scalar, BaseMatrix<$Scalar, Data, Dynamic, Dynamic>, Dynamic, Dynamic>
+        {
+            /// Return a view onto the real parts of the matrix without copying.
+            pub fn real_view(&self) -> SliceMatrix<'_, $Real, Dynamic, Dynamic> {
+                self.component_view(0)
+            }
+
+            /// Return a view onto the imaginary parts of the matrix without copying.
+            pub fn imag_view(&self) -> SliceMatrix<'_, $Real, Dynamic, Dynamic> {
+                self.component_view(1)
+            }
+
+            fn component_view(
+                &self,
+                offset: IndexType,
+            ) -> SliceMatrix<'_, $Real, Dynamic, Dynamic> {
+                let dim = self.layout().dim();
+                let stride = self.layout().stride();
+                // Complex numbers are stored as two consecutive real numbers (re, im).
+                unsafe {
+                    SliceMatrix::from_pointer(
+                        (self.get_pointer() as *const $Real).add(offset),
+                        dim,
+                        (2 * stride.0, 2 * stride.1),
+                    )
+                }
+            }
+        }
+
+        impl<Data: DataContainerMut<Item = $Scalar>>
+            Matrix<$Scalar, BaseMatrix<$Scalar, Data, Dynamic, Dynamic>, Dynamic, Dynamic>
+        {
+            /// Return a mutable view onto the real parts of the matrix without copying.
+            pub fn real_view_mut(&mut self) -> SliceMatrixMut<'_, $Real, Dynamic, Dynamic> {
+                self.component_view_mut(0)
+            }
+
+            /// Return a mutable view onto the imaginary parts of the matrix without copying.
+            pub fn imag_view_mut(&mut self) -> SliceMatrixMut<'_, $Real, Dynamic, Dynamic> {
+                self.component_view_mut(1)
+            }
+
+            fn component_view_mut(
+                &mut self,
+                offset: IndexType,
+            ) -> SliceMatrixMut<'_, $Real, Dynamic, Dynamic> {
+                let dim = self.layout().dim();
+                let stride = self.layout().stride();
+                unsafe {
+                    SliceMatrixMut::from_pointer(
+                        (self.get_pointer_mut() as *mut $Real).add(offset),
+                        dim,
+                        (2 * stride.0, 2 * stride.1),
+                    )
+                }
+            }
+        }
+    };
+}
+
+complex_impl!(c32, f32);
+complex_impl!(c64, f64);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::MatrixD;
+
+    fn example_matrix() -> MatrixD<c64> {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(3, 2);
+        for row in 0..3 {
+            for col in 0..2 {
+                mat[[row, col]] = c64::new(row as f64, col as f64 + 0.5);
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_cast() {
+        let mut mat = MatrixD::<f32>::zeros_from_dim(2, 2);
+        mat[[1, 0]] = 1.5;
+
+        let res = mat.cast::<f64>().eval();
+        assert_eq!(res[[1, 0]], 1.5);
+
+        let res = res.cast::<c32>().eval();
+        assert_eq!(res[[1, 0]], c32::new(1.5, 0.0));
+
+        let res = res.cast::<c64>().eval();
+        assert_eq!(res[[1, 0]], c64::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn test_to_complex() {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(2, 2);
+        mat[[0, 1]] = 2.0;
+        let res = mat.to_complex().eval();
+        assert_eq!(res[[0, 1]], c64::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn test_complex_parts() {
+        let mat = example_matrix();
+        let real = example_matrix().real().eval();
+        let imag = example_matrix().imag().eval();
+        let conj = example_matrix().conj().eval();
+
+        for row in 0..3 {
+            for col in 0..2 {
+                assert_eq!(real[[row, col]], mat[[row, col]].re);
+                assert_eq!(imag[[row, col]], mat[[row, col]].im);
+                assert_eq!(conj[[row, col]], mat[[row, col]].conj());
+            }
+        }
+    }
+
+    #[test]
+    fn test_component_views() {
+        let mut mat = example_matrix();
+        {
+            let real = mat.real_view();
+            let imag = mat.imag_view();
+            assert_eq!(real.layout().stride(), (2, 6));
+            for row in 0..3 {
+                for col in 0..2 {
+                    assert_eq!(real[[row, col]], row as f64);
+                    assert_eq!(imag[[row, col]], col as f64 + 0.5);
+                }
+            }
+        }
+
+        mat.imag_view_mut()[[2, 1]] = -1.0;
+        mat.real_view_mut()[[0, 0]] = 7.0;
+        assert_eq!(mat[[2, 1]], c64::new(2.0, -1.0));
+        assert_eq!(mat[[0, 0]], c64::new(7.0, 0.5));
+    }
+}
@@ -9,7 +9,28 @@
 
 use crate::types::IndexType;
 use crate::types::Scalar;
-
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Alignment in bytes of the memory allocated by an [AlignedVectorContainer].
+///
+/// This is the size of a cache line and of an AVX-512 register.
+pub const SIMD_ALIGNMENT: usize = 64;
+
+/// Low-level access to the memory of a matrix.
+///
+/// A matrix with a padded stride does not use every memory location of its container.
+/// The container therefore holds at least
+/// [data_extent](crate::GenericBaseMatrix::data_extent) elements, which can be more
+/// than the number of elements of the matrix. The slices returned by
+/// [data](crate::GenericBaseMatrix::data) and
+/// [data_mut](crate::GenericBaseMatrixMut::data_mut) span this extent, including the
+/// padding, and not only the elements of the matrix.
 pub trait DataContainer {
     type Item: Scalar;
 
@@ -38,7 +59,7 @@ pub trait DataContainer {
             self.number_of_elements()
         );
 
-        unsafe { std::slice::from_raw_parts(self.get_pointer().add(first), last - first) }
+        unsafe { core::slice::from_raw_parts(self.get_pointer().add(first), last - first) }
     }
 
     /// Return the number of elements in the container.
@@ -75,7 +96,7 @@ pub trait DataContainerMut: DataContainer {
             self.number_of_elements()
         );
 
-        unsafe { std::slice::from_raw_parts_mut(self.get_pointer_mut().add(first), last - first) }
+        unsafe { core::slice::from_raw_parts_mut(self.get_pointer_mut().add(first), last - first) }
     }
 }
 
@@ -84,6 +105,24 @@ pub struct VectorContainer<Item: Scalar> {
     data: Vec<Item>,
 }
 
+/// A reference-counted container that is shared between clones.
+///
+/// Cloning the container is O(1). Mutable access copies the data first
+/// if the buffer is shared with another container (copy-on-write).
+#[cfg(target_has_atomic = "ptr")]
+pub struct SharedContainer<Item: Scalar> {
+    data: Arc<Vec<Item>>,
+    // True if the buffer is known not to be shared, so that mutable access does not need
+    // to check the reference count. Cleared whenever the container is cloned.
+    unique: AtomicBool,
+}
+
+/// A container that allocates memory aligned to [SIMD_ALIGNMENT] bytes.
+pub struct AlignedVectorContainer<Item: Scalar> {
+    ptr: NonNull<Item>,
+    nelems: IndexType,
+}
+
 pub struct ArrayContainer<Item: Scalar, const N: usize> {
     data: [Item; N],
 }
@@ -109,6 +148,118 @@ impl<Item: Scalar> VectorContainer<Item> {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar> SharedContainer<Item> {
+    /// New shared container by specifying the number of elements.
+    ///
+    /// The container is initialized with zeros.
+    pub fn new(nelems: IndexType) -> SharedContainer<Item> {
+        SharedContainer::<Item> {
+            data: Arc::new(vec![num::cast::<f64, Item>(0.0).unwrap(); nelems]),
+            unique: AtomicBool::new(true),
+        }
+    }
+
+    /// Return true if the buffer is shared with another container.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.data) > 1
+    }
+
+    // Copy the buffer if it is shared. The reference count is only checked once after
+    // each clone, and not on every mutable access.
+    fn make_unique(&mut self) -> &mut Vec<Item> {
+        let unique = self.unique.get_mut();
+        if !*unique {
+            Arc::make_mut(&mut self.data);
+            *unique = true;
+        }
+        // The buffer is unique, since other references to it can only be created through
+        // `clone`, which clears the flag. This avoids the atomic check of `Arc::get_mut`.
+        unsafe { &mut *(Arc::as_ptr(&self.data) as *mut Vec<Item>) }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar> Clone for SharedContainer<Item> {
+    fn clone(&self) -> Self {
+        self.unique.store(false, Ordering::Relaxed);
+        SharedContainer::<Item> {
+            data: Arc::clone(&self.data),
+            unique: AtomicBool::new(false),
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar> From<VectorContainer<Item>> for SharedContainer<Item> {
+    fn from(container: VectorContainer<Item>) -> Self {
+        SharedContainer::<Item> {
+            data: Arc::new(container.data),
+            unique: AtomicBool::new(true),
+        }
+    }
+}
+
+impl<Item: Scalar> AlignedVectorContainer<Item> {
+    /// New aligned container by specifying the number of elements.
+    ///
+    /// The container is initialized with zeros.
+    pub fn new(nelems: IndexType) -> AlignedVectorContainer<Item> {
+        let ptr = if nelems == 0 {
+            NonNull::dangling()
+        } else {
+            let layout = Self::memory_layout(nelems);
+            let ptr = unsafe { alloc::alloc::alloc(layout) } as *mut Item;
+            let Some(ptr) = NonNull::new(ptr) else {
+                alloc::alloc::handle_alloc_error(layout)
+            };
+            let zero = num::cast::<f64, Item>(0.0).unwrap();
+            for index in 0..nelems {
+                unsafe { ptr.as_ptr().add(index).write(zero) };
+            }
+            ptr
+        };
+        AlignedVectorContainer::<Item> { ptr, nelems }
+    }
+
+    /// Return the number of elements of type `Item` that fit into [SIMD_ALIGNMENT] bytes.
+    pub fn simd_width() -> IndexType {
+        1.max(SIMD_ALIGNMENT / core::mem::size_of::<Item>())
+    }
+
+    /// Round `dim` up to the next multiple of [simd_width](Self::simd_width).
+    ///
+    /// Using this as leading dimension ensures that every column of a column-major
+    /// matrix starts at an aligned address.
+    pub fn padded_dim(dim: IndexType) -> IndexType {
+        let width = Self::simd_width();
+        dim.div_ceil(width) * width
+    }
+
+    fn memory_layout(nelems: IndexType) -> alloc::alloc::Layout {
+        alloc::alloc::Layout::array::<Item>(nelems)
+            .and_then(|layout| layout.align_to(SIMD_ALIGNMENT))
+            .expect("Requested size of aligned container is too large.")
+    }
+}
+
+impl<Item: Scalar> Drop for AlignedVectorContainer<Item> {
+    fn drop(&mut self) {
+        if self.nelems > 0 {
+            unsafe {
+                alloc::alloc::dealloc(
+                    self.ptr.as_ptr() as *mut u8,
+                    Self::memory_layout(self.nelems),
+                )
+            };
+        }
+    }
+}
+
+// The container uniquely owns its memory, like a `Vec<Item>`.
+unsafe impl<Item: Scalar + Send> Send for AlignedVectorContainer<Item> {}
+unsafe impl<Item: Scalar + Sync> Sync for AlignedVectorContainer<Item> {}
+
 impl<Item: Scalar, const N: usize> ArrayContainer<Item, N> {
     pub fn new() -> ArrayContainer<Item, N> {
         ArrayContainer::<Item, N> {
@@ -167,6 +318,68 @@ impl<Item: Scalar> DataContainerMut for VectorContainer<Item> {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar> DataContainer for SharedContainer<Item> {
+    type Item = Item;
+
+    unsafe fn get_unchecked_value(&self, index: IndexType) -> Self::Item {
+        *self.data.get_unchecked(index)
+    }
+
+    unsafe fn get_unchecked(&self, index: IndexType) -> &Self::Item {
+        self.data.get_unchecked(index)
+    }
+
+    fn get_pointer(&self) -> *const Self::Item {
+        self.data.as_ptr()
+    }
+
+    fn number_of_elements(&self) -> IndexType {
+        self.data.len()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar> DataContainerMut for SharedContainer<Item> {
+    unsafe fn get_unchecked_mut(&mut self, index: IndexType) -> &mut Self::Item {
+        self.make_unique().get_unchecked_mut(index)
+    }
+
+    fn get_pointer_mut(&mut self) -> *mut Self::Item {
+        self.make_unique().as_mut_ptr()
+    }
+}
+
+impl<Item: Scalar> DataContainer for AlignedVectorContainer<Item> {
+    type Item = Item;
+
+    unsafe fn get_unchecked_value(&self, index: IndexType) -> Self::Item {
+        *self.ptr.as_ptr().add(index)
+    }
+
+    unsafe fn get_unchecked(&self, index: IndexType) -> &Self::Item {
+        &*self.ptr.as_ptr().add(index)
+    }
+
+    fn get_pointer(&self) -> *const Self::Item {
+        self.ptr.as_ptr()
+    }
+
+    fn number_of_elements(&self) -> IndexType {
+        self.nelems
+    }
+}
+
+impl<Item: Scalar> DataContainerMut for AlignedVectorContainer<Item> {
+    unsafe fn get_unchecked_mut(&mut self, index: IndexType) -> &mut Self::Item {
+        &mut *self.ptr.as_ptr().add(index)
+    }
+
+    fn get_pointer_mut(&mut self) -> *mut Self::Item {
+        self.ptr.as_ptr()
+    }
+}
+
 impl<Item: Scalar, const N: usize> DataContainer for ArrayContainer<Item, N> {
     type Item = Item;
 
@@ -246,3 +459,112 @@ impl<'a, Item: Scalar> DataContainerMut for SliceContainerMut<'a, Item> {
         self.data.as_mut_ptr()
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::SharedMatrixD;
+    #[cfg(feature = "std")]
+    use crate::matrix::{AlignedMatrixD, MatrixD};
+    #[cfg(feature = "std")]
+    use crate::traits::*;
+    #[cfg(feature = "std")]
+    use crate::Dot;
+    #[cfg(feature = "std")]
+    use approx::assert_relative_eq;
+    #[cfg(feature = "std")]
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_aligned_container() {
+        let container = AlignedVectorContainer::<f64>::new(13);
+        assert_eq!(container.get_pointer() as usize % SIMD_ALIGNMENT, 0);
+        assert_eq!(container.number_of_elements(), 13);
+        assert!(container.get_slice(0, 13).iter().all(|&elem| elem == 0.0));
+
+        assert_eq!(AlignedVectorContainer::<f64>::simd_width(), 8);
+        assert_eq!(AlignedVectorContainer::<f64>::padded_dim(13), 16);
+        assert_eq!(AlignedVectorContainer::<f32>::padded_dim(16), 16);
+
+        let empty = AlignedVectorContainer::<f64>::new(0);
+        assert_eq!(empty.number_of_elements(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_aligned_matrix() {
+        let mut mat = AlignedMatrixD::<f64>::zeros_aligned_from_dim(5, 3);
+        assert_eq!(mat.layout().stride(), (1, 8));
+        assert_eq!(mat.data_extent(), 21);
+        assert_eq!(mat.data().len(), mat.data_extent());
+        assert_eq!(mat.layout().number_of_elements(), 15);
+        for col in 0..3 {
+            assert_eq!(
+                unsafe { mat.get_pointer().add(mat.layout().convert_2d_raw(0, col)) } as usize
+                    % SIMD_ALIGNMENT,
+                0
+            );
+        }
+
+        let mut other = MatrixD::<f64>::zeros_from_dim(5, 3);
+        let mut rhs = MatrixD::<f64>::zeros_from_dim(3, 4);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        other.fill_from_rand_standard_normal(&mut rng);
+        rhs.fill_from_rand_standard_normal(&mut rng);
+        for row in 0..5 {
+            for col in 0..3 {
+                mat[[row, col]] = other[[row, col]];
+            }
+        }
+
+        let actual = mat.dot(&rhs);
+        let expected = other.dot(&rhs);
+        for row in 0..5 {
+            for col in 0..4 {
+                assert_relative_eq!(actual[[row, col]], expected[[row, col]], epsilon = 1E-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_copy_on_write() {
+        let mut mat = SharedMatrixD::<f64>::zeros_from_dim(3, 2);
+        mat[[1, 1]] = 1.0;
+        let ptr = mat.get_pointer();
+
+        let mut other = mat.clone();
+        assert_eq!(other.get_pointer(), ptr);
+        assert!(mat.data_container().is_shared());
+
+        other[[1, 1]] = 2.0;
+        assert_ne!(other.get_pointer(), ptr);
+        assert_eq!(mat[[1, 1]], 1.0);
+        assert_eq!(other[[1, 1]], 2.0);
+        assert!(!mat.data_container().is_shared());
+
+        // A buffer that is not shared is modified in place.
+        mat[[0, 0]] = 3.0;
+        assert_eq!(mat.get_pointer(), ptr);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_across_threads() {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(2, 2);
+        mat[[0, 1]] = 5.0;
+        let ptr = mat.get_pointer();
+        let shared = mat.into_shared();
+        assert_eq!(shared.get_pointer(), ptr);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mat = shared.clone();
+                std::thread::spawn(move || mat[[0, 1]])
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 5.0);
+        }
+    }
+}
@@ -2,7 +2,12 @@
 
 pub use crate::data_container::*;
 pub use crate::layouts::*;
+pub use crate::matrix::fixed_size::Solve;
+pub use crate::matrix::structured::{
+    CirculantMatrix, DiagonalMatrix, LowRankMatrix, ToeplitzMatrix,
+};
 pub use crate::matrix::*;
 pub use crate::matrix_multiply::{Dot, MatMul};
+#[cfg(feature = "std")]
 pub use crate::tools::*;
 pub use crate::traits::*;
@@ -7,6 +7,7 @@
 use crate::traits::*;
 use crate::types::IndexType;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DefaultLayout {
     dim: (IndexType, IndexType),
     stride: (IndexType, IndexType),
@@ -69,7 +69,7 @@ impl LayoutType for UpperTriangular {
 
     #[inline]
     fn stride(&self) -> (IndexType, IndexType) {
-        std::unimplemented!("method 'stride' not implemented for UpperTriangular layout.")
+        core::unimplemented!("method 'stride' not implemented for UpperTriangular layout.")
     }
 }
 
@@ -31,22 +31,57 @@
 //! - [Matrix storage layouts](crate::layouts)
 //! - [The Matrix type](crate::matrix)
 //! - [Examples](crate::examples)
+//!
+//! # `no_std` support
+//!
+//! The default `std` feature can be disabled to use the crate in a `no_std` environment
+//! with a global allocator. Fixed size matrices backed by an
+//! [ArrayContainer](crate::data_container::ArrayContainer), their arithmetic,
+//! [eval](crate::matrix::Matrix::eval) and the
+//! [closed-form operations](crate::matrix::fixed_size) remain available. The following
+//! parts of the crate require `std`:
+//!
+//! - random matrices and the [tools] module, which depend on `rand`,
+//! - the [rendering] module and the `mmap` and `rayon` features,
+//! - the `std::error::Error` implementation of [RlstError](rlst_common::types::RlstError).
+//!
+//! The [SharedContainer](crate::data_container::SharedContainer) additionally requires
+//! a target with atomic pointer operations. The `no_std` build is checked for an embedded
+//! target with
+//!
+//! ```text
+//! cargo build -p rlst-dense --no-default-features --target thumbv7em-none-eabihf
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod data_container;
 pub mod examples;
 pub mod layouts;
 pub mod macros;
 pub mod matrix;
+#[cfg(feature = "std")]
 pub mod tools;
 pub mod traits;
 pub mod types;
 
 pub mod addition;
 pub mod base_matrix;
+pub mod batched;
+pub mod conversion;
 pub mod global;
 pub mod matrix_multiply;
 pub mod matrix_ref;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod permutation;
+#[cfg(feature = "std")]
+pub mod rendering;
 pub mod scalar_mult;
 pub mod subtraction;
+pub mod tensor;
+pub mod transpose;
 
 pub use global::*;
@@ -13,6 +13,7 @@ macro_rules! rlst_mat {
     };
 }
 
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! rlst_rand_mat {
     ($ScalarType:ty, $dim:expr) => {{
@@ -36,6 +37,7 @@ macro_rules! rlst_vec {
     };
 }
 
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! rlst_rand_vec {
     ($ScalarType:ty, $dim:expr) => {
@@ -60,6 +62,7 @@ mod test {
         assert_eq!(mat.dim(), (2, 3));
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn create_random_matrix() {
         let dim = (2, 3);
@@ -84,6 +87,7 @@ mod test {
         assert_eq!(vec.dim(), (1, 5));
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn create_random_vector() {
         let length = 5;
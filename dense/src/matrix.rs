@@ -22,17 +22,24 @@
 pub mod base_methods;
 pub mod common_impl;
 pub mod constructors;
+pub mod fixed_size;
 pub mod matrix_slices;
+#[cfg(feature = "std")]
 pub mod random;
+pub mod structured;
+pub mod vector_impl;
 
 use crate::base_matrix::BaseMatrix;
+#[cfg(target_has_atomic = "ptr")]
+use crate::data_container::SharedContainer;
 use crate::data_container::{
-    ArrayContainer, DataContainer, SliceContainer, SliceContainerMut, VectorContainer,
+    AlignedVectorContainer, ArrayContainer, DataContainer, SliceContainer, SliceContainerMut,
+    VectorContainer,
 };
 use crate::matrix_ref::MatrixRef;
 use crate::types::Scalar;
 use crate::{traits::*, DefaultLayout};
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// A [RefMat] is a matrix whose implementation is a reference to another matrix.
 /// This is used to convert a reference to a matrix to an owned matrix whose implementation
@@ -60,6 +67,22 @@ pub type SliceMatrixMut<'a, Item, RS, CS> =
 pub type MatrixD<Item> =
     Matrix<Item, BaseMatrix<Item, VectorContainer<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>;
 
+/// A dynamic matrix whose data is reference counted and shared between clones.
+///
+/// Cloning is O(1). Modifying a matrix whose data is shared copies the data first.
+#[cfg(target_has_atomic = "ptr")]
+pub type SharedMatrixD<Item> =
+    Matrix<Item, BaseMatrix<Item, SharedContainer<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>;
+
+/// A dynamic matrix with [SIMD_ALIGNMENT](crate::data_container::SIMD_ALIGNMENT)-byte
+/// aligned columns. See [zeros_aligned_from_dim](AlignedMatrixD::zeros_aligned_from_dim).
+pub type AlignedMatrixD<Item> = Matrix<
+    Item,
+    BaseMatrix<Item, AlignedVectorContainer<Item>, Dynamic, Dynamic>,
+    Dynamic,
+    Dynamic,
+>;
+
 /// A dynamic column vector. This means that the row dimension is dynamic and the column
 /// dimension is [Fixed1].
 pub type ColumnVectorD<Item> = GenericBaseMatrixMut<Item, VectorContainer<Item>, Dynamic, Fixed1>;
@@ -84,6 +107,22 @@ pub type Matrix32<Item> =
 pub type Matrix23<Item> =
     Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 6>, Fixed2, Fixed3>, Fixed2, Fixed3>;
 
+/// A fixed column vector with two elements.
+pub type ColumnVector2<Item> =
+    Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 2>, Fixed2, Fixed1>, Fixed2, Fixed1>;
+
+/// A fixed column vector with three elements.
+pub type ColumnVector3<Item> =
+    Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 3>, Fixed3, Fixed1>, Fixed3, Fixed1>;
+
+/// A fixed row vector with two elements.
+pub type RowVector2<Item> =
+    Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 2>, Fixed1, Fixed2>, Fixed1, Fixed2>;
+
+/// A fixed row vector with three elements.
+pub type RowVector3<Item> =
+    Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 3>, Fixed1, Fixed3>, Fixed1, Fixed3>;
+
 /// The basic tuple type defining a matrix. It is given as `(MatImpl, _, _, _, _)`.
 /// The only relevant member is the first one `MatImpl`, an implementation type to which
 /// all calls are forwarded. The other members are of type [PhantomData] and are necessary
@@ -114,6 +153,18 @@ impl<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier, MatImpl: MatrixTrait<
     }
 }
 
+impl<
+        Item: Scalar,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+        MatImpl: MatrixTrait<Item, RS, CS> + Clone,
+    > Clone for Matrix<Item, MatImpl, RS, CS>
+{
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
 impl<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier, Data: DataContainer<Item = Item>>
     Matrix<Item, BaseMatrix<Item, Data, RS, CS>, RS, CS>
 {
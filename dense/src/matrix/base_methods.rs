@@ -31,6 +31,12 @@ impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: Siz
         self.0.get_pointer()
     }
 
+    /// Return a reference to the underlying data container.
+    #[inline]
+    pub fn data_container(&self) -> &Data {
+        self.0.data_container()
+    }
+
     /// Return a region of the matrix as memory slice.
     ///
     /// The parameters `first` and `last` are with respect to raw
@@ -44,10 +50,26 @@ impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: Siz
         self.0.get_slice(first, last)
     }
 
+    /// Return the number of raw memory locations spanned by the matrix.
+    #[inline]
+    pub fn data_extent(&self) -> IndexType {
+        let dim = self.layout().dim();
+        if dim.0 == 0 || dim.1 == 0 {
+            0
+        } else {
+            self.layout().convert_2d_raw(dim.0 - 1, dim.1 - 1) + 1
+        }
+    }
+
     /// Get a slice of the whole data.
+    ///
+    /// The slice has length [data_extent](Self::data_extent). For matrices with a
+    /// non-contiguous stride it spans the memory from the first to the last element,
+    /// including any padding between rows or columns, and is longer than the number of
+    /// elements of the matrix.
     #[inline]
     pub fn data(&self) -> &[Item] {
-        self.0.get_slice(0, self.layout().number_of_elements())
+        self.0.get_slice(0, self.data_extent())
     }
 }
 
@@ -74,8 +96,10 @@ impl<Item: Scalar, Data: DataContainerMut<Item = Item>, RS: SizeIdentifier, CS:
     }
 
     /// Get a mutable slice of the whole data.
+    ///
+    /// See [data](Self::data) for matrices with a non-contiguous stride.
     #[inline]
     pub fn data_mut(&mut self) -> &mut [Item] {
-        self.0.get_slice_mut(0, self.layout().number_of_elements())
+        self.0.get_slice_mut(0, self.data_extent())
     }
 }
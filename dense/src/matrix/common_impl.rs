@@ -1,6 +1,7 @@
 //! Implementation of common matrix traits and methods.
 
 use crate::matrix::{Matrix, MatrixD};
+use crate::matrix_multiply::MatMulOperand;
 use crate::types::{IndexType, Scalar};
 use crate::{traits::*, DefaultLayout};
 
@@ -36,6 +37,19 @@ impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: S
     }
 }
 
+impl<
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS> + MatMulOperand,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for Matrix<Item, MatImpl, RS, CS>
+{
+    #[inline]
+    fn strided_pointer(&self) -> Option<*const Item> {
+        self.0.strided_pointer()
+    }
+}
+
 impl<
         Item: Scalar,
         MatImpl: MatrixTraitMut<Item, RS, CS>,
@@ -81,7 +95,7 @@ impl<
         MatImpl: MatrixTraitAccessByRef<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::Index<[IndexType; 2]> for Matrix<Item, MatImpl, RS, CS>
+    > core::ops::Index<[IndexType; 2]> for Matrix<Item, MatImpl, RS, CS>
 {
     type Output = Item;
 
@@ -95,7 +109,7 @@ impl<
         MatImpl: MatrixTraitMut<Item, RS, CS> + MatrixTraitAccessByRef<Item, RS, CS>,
         RS: SizeIdentifier,
         CS: SizeIdentifier,
-    > std::ops::IndexMut<[IndexType; 2]> for Matrix<Item, MatImpl, RS, CS>
+    > core::ops::IndexMut<[IndexType; 2]> for Matrix<Item, MatImpl, RS, CS>
 {
     fn index_mut(&mut self, index: [IndexType; 2]) -> &mut Self::Output {
         self.get_mut(index[0], index[1]).unwrap()
@@ -1,7 +1,11 @@
 //! A collection of routines to construct matrix objects from scratch or existing data.
 
 use crate::base_matrix::BaseMatrix;
-use crate::data_container::{ArrayContainer, SliceContainer, SliceContainerMut, VectorContainer};
+#[cfg(target_has_atomic = "ptr")]
+use crate::data_container::SharedContainer;
+use crate::data_container::{
+    AlignedVectorContainer, ArrayContainer, SliceContainer, SliceContainerMut, VectorContainer,
+};
 use crate::layouts::*;
 use crate::matrix::{Matrix, SliceMatrix, SliceMatrixMut};
 use crate::traits::*;
@@ -55,6 +59,50 @@ impl<Item: Scalar>
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar>
+    Matrix<Item, BaseMatrix<Item, SharedContainer<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>
+{
+    /// Create a new shared zero matrix with given number of rows and columns.
+    pub fn zeros_from_dim(rows: IndexType, cols: IndexType) -> Self {
+        let layout = DefaultLayout::from_dimension((rows, cols), (1, rows));
+        Self::from_data(
+            SharedContainer::<Item>::new(layout.number_of_elements()),
+            layout,
+        )
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<Item: Scalar, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, BaseMatrix<Item, VectorContainer<Item>, RS, CS>, RS, CS>
+{
+    /// Convert into a matrix with shared data without copying.
+    pub fn into_shared(
+        self,
+    ) -> Matrix<Item, BaseMatrix<Item, SharedContainer<Item>, RS, CS>, RS, CS> {
+        let (data, layout) = self.0.into_parts();
+        Matrix::from_data(data.into(), layout)
+    }
+}
+
+impl<Item: Scalar>
+    Matrix<Item, BaseMatrix<Item, AlignedVectorContainer<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>
+{
+    /// Create a new zero matrix with aligned columns.
+    ///
+    /// The memory is aligned to [SIMD_ALIGNMENT](crate::data_container::SIMD_ALIGNMENT)
+    /// bytes and the column stride is padded to a multiple of the SIMD width, so that
+    /// every column starts at an aligned address.
+    pub fn zeros_aligned_from_dim(rows: IndexType, cols: IndexType) -> Self {
+        let ld = AlignedVectorContainer::<Item>::padded_dim(rows);
+        Self::from_data(
+            AlignedVectorContainer::<Item>::new(ld * cols),
+            DefaultLayout::from_dimension((rows, cols), (1, ld)),
+        )
+    }
+}
+
 impl<Item: Scalar>
     Matrix<Item, BaseMatrix<Item, VectorContainer<Item>, Dynamic, Fixed1>, Dynamic, Fixed1>
 {
@@ -91,8 +139,12 @@ macro_rules! from_pointer_strided {
                 stride: (IndexType, IndexType),
             ) -> Self {
                 let new_layout = DefaultLayout::new(dim, stride);
-                let nindices = new_layout.convert_2d_raw(dim.0 - 1, dim.1 - 1) + 1;
-                let slice = std::slice::from_raw_parts_mut(ptr, nindices);
+                let nindices = if dim.0 == 0 || dim.1 == 0 {
+                    0
+                } else {
+                    new_layout.convert_2d_raw(dim.0 - 1, dim.1 - 1) + 1
+                };
+                let slice = core::slice::from_raw_parts_mut(ptr, nindices);
                 let data = SliceContainerMut::<'a, Item>::new(slice);
 
                 SliceMatrixMut::<'a, Item, $RS, $CS>::from_data(data, new_layout)
@@ -107,8 +159,12 @@ macro_rules! from_pointer_strided {
                 stride: (IndexType, IndexType),
             ) -> Self {
                 let new_layout = DefaultLayout::new(dim, stride);
-                let nindices = new_layout.convert_2d_raw(dim.0 - 1, dim.1 - 1) + 1;
-                let slice = std::slice::from_raw_parts(ptr, nindices);
+                let nindices = if dim.0 == 0 || dim.1 == 0 {
+                    0
+                } else {
+                    new_layout.convert_2d_raw(dim.0 - 1, dim.1 - 1) + 1
+                };
+                let slice = core::slice::from_raw_parts(ptr, nindices);
                 let data = SliceContainer::<'a, Item>::new(slice);
 
                 SliceMatrix::<'a, Item, $RS, $CS>::from_data(data, new_layout)
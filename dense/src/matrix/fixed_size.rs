@@ -0,0 +1,326 @@
+//! Closed-form operations on fixed size matrices.
+//!
+//! For 2x2 and 3x3 matrices the determinant, adjugate, inverse and the solution
+//! of linear systems are computed with explicit formulas. No memory is allocated
+//! and no Lapack routines are called. The methods are available on any fixed size
+//! matrix, including lazy expressions such as `(&mat1 + &mat2).det()`.
+//!
+//! A matrix is considered singular if `|det(A)| <= tol * prod_j ||a_j||`, where
+//! `a_j` are the columns of `A`. By Hadamard's inequality the ratio on the
+//! left is bounded by one, so `tol` is a relative tolerance that does not depend
+//! on the scaling of the matrix. The default tolerance is `n * eps` for an `n x n`
+//! matrix, with `eps` the machine epsilon of the underlying real type.
+
+use crate::base_matrix::BaseMatrix;
+use crate::batched::kernels;
+use crate::data_container::ArrayContainer;
+use crate::matrix::Matrix;
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use num::Float;
+use rlst_common::types::{RlstError, RlstResult};
+
+/// Solve a linear system with a right-hand side `Rhs`.
+pub trait Solve<Rhs> {
+    type Output;
+
+    /// Return the solution `x` of `self * x = rhs`.
+    fn solve(&self, rhs: &Rhs) -> RlstResult<Self::Output>;
+}
+
+/// Check the determinant `det` of the `n x n` matrix `mat` against the relative tolerance `tol`.
+fn check_singular<Item: Scalar, Mat: UnsafeRandomAccessByValue<Item = Item>>(
+    n: IndexType,
+    mat: &Mat,
+    det: Item,
+    tol: <Item as Scalar>::Real,
+) -> RlstResult<()> {
+    let mut bound = <<Item as Scalar>::Real as num::One>::one();
+    for col in 0..n {
+        let mut col_norm = <<Item as Scalar>::Real as num::Zero>::zero();
+        for row in 0..n {
+            col_norm += unsafe { mat.get_value_unchecked(row, col) }.square();
+        }
+        bound *= Float::sqrt(col_norm);
+    }
+    if det.abs() <= tol * bound {
+        Err(RlstError::SingularMatrix)
+    } else {
+        Ok(())
+    }
+}
+
+macro_rules! fixed_square_impl {
+    ($Size:ident, $det:ident, $adjugate:ident) => {
+        impl<Item: Scalar, MatImpl: MatrixTrait<Item, $Size, $Size>>
+            Matrix<Item, MatImpl, $Size, $Size>
+        {
+            /// Return the determinant of the matrix.
+            pub fn det(&self) -> Item {
+                kernels::$det(self)
+            }
+
+            /// Return the adjugate (transposed cofactor matrix) of the matrix.
+            pub fn adjugate(
+                &self,
+            ) -> Matrix<
+                Item,
+                BaseMatrix<Item, ArrayContainer<Item, { $Size::N * $Size::N }>, $Size, $Size>,
+                $Size,
+                $Size,
+            > {
+                let mut result = Matrix::<
+                    Item,
+                    BaseMatrix<Item, ArrayContainer<Item, { $Size::N * $Size::N }>, $Size, $Size>,
+                    $Size,
+                    $Size,
+                >::zeros_from_dim();
+                kernels::$adjugate(self, &mut result);
+                result
+            }
+
+            /// Return the inverse of the matrix.
+            ///
+            /// Returns [RlstError::SingularMatrix] if the matrix is singular
+            /// with respect to the default tolerance. See the
+            /// [module documentation](crate::matrix::fixed_size) for details.
+            pub fn inverse(
+                &self,
+            ) -> RlstResult<
+                Matrix<
+                    Item,
+                    BaseMatrix<Item, ArrayContainer<Item, { $Size::N * $Size::N }>, $Size, $Size>,
+                    $Size,
+                    $Size,
+                >,
+            > {
+                self.inverse_with_tolerance(Self::default_tolerance())
+            }
+
+            /// Return the inverse of the matrix using the relative singularity tolerance `tol`.
+            pub fn inverse_with_tolerance(
+                &self,
+                tol: <Item as Scalar>::Real,
+            ) -> RlstResult<
+                Matrix<
+                    Item,
+                    BaseMatrix<Item, ArrayContainer<Item, { $Size::N * $Size::N }>, $Size, $Size>,
+                    $Size,
+                    $Size,
+                >,
+            > {
+                let det = self.det();
+                check_singular($Size::N, self, det, tol)?;
+                let mut result = self.adjugate();
+                let inv_det = <Item as num::One>::one() / det;
+                result.for_each(|elem| *elem *= inv_det);
+                Ok(result)
+            }
+
+            /// The default relative singularity tolerance `n * eps`.
+            fn default_tolerance() -> <Item as Scalar>::Real {
+                <<Item as Scalar>::Real as Float>::epsilon()
+                    * num::cast::<usize, <Item as Scalar>::Real>($Size::N).unwrap()
+            }
+        }
+    };
+}
+
+macro_rules! fixed_solve_impl {
+    ($Size:ident, $RhsCols:ident) => {
+        impl<
+                Item: Scalar,
+                MatImpl: MatrixTrait<Item, $Size, $Size>,
+                RhsImpl: MatrixTrait<Item, $Size, $RhsCols>,
+            > Solve<Matrix<Item, RhsImpl, $Size, $RhsCols>>
+            for Matrix<Item, MatImpl, $Size, $Size>
+        {
+            type Output = Matrix<
+                Item,
+                BaseMatrix<Item, ArrayContainer<Item, { $Size::N * $RhsCols::N }>, $Size, $RhsCols>,
+                $Size,
+                $RhsCols,
+            >;
+
+            /// Solve the system through the adjugate, `x = adj(A) * rhs / det(A)`.
+            ///
+            /// Returns [RlstError::SingularMatrix] if the matrix is singular with respect
+            /// to the default tolerance.
+            fn solve(
+                &self,
+                rhs: &Matrix<Item, RhsImpl, $Size, $RhsCols>,
+            ) -> RlstResult<Self::Output> {
+                let det = self.det();
+                check_singular($Size::N, self, det, Self::default_tolerance())?;
+                let adjugate = self.adjugate();
+                let mut result = Self::Output::zeros_from_dim();
+                kernels::matmul(
+                    <Item as num::One>::one() / det,
+                    &adjugate,
+                    rhs,
+                    <Item as num::Zero>::zero(),
+                    &mut result,
+                );
+                Ok(result)
+            }
+        }
+    };
+}
+
+fixed_square_impl!(Fixed2, det2, adjugate2);
+fixed_square_impl!(Fixed3, det3, adjugate3);
+
+fixed_solve_impl!(Fixed2, Fixed1);
+fixed_solve_impl!(Fixed2, Fixed2);
+fixed_solve_impl!(Fixed2, Fixed3);
+fixed_solve_impl!(Fixed3, Fixed1);
+fixed_solve_impl!(Fixed3, Fixed2);
+fixed_solve_impl!(Fixed3, Fixed3);
+
+macro_rules! cross_impl {
+    ($RS:ident, $CS:ident) => {
+        impl<Item: Scalar, MatImpl: MatrixTrait<Item, $RS, $CS>> Matrix<Item, MatImpl, $RS, $CS> {
+            /// Return the cross product of two vectors with three elements.
+            pub fn cross<OtherImpl: MatrixTrait<Item, $RS, $CS>>(
+                &self,
+                other: &Matrix<Item, OtherImpl, $RS, $CS>,
+            ) -> Matrix<Item, BaseMatrix<Item, ArrayContainer<Item, 3>, $RS, $CS>, $RS, $CS> {
+                let a = |index| unsafe { self.get1d_value_unchecked(index) };
+                let b = |index| unsafe { other.get1d_value_unchecked(index) };
+                let mut result = Matrix::<
+                    Item,
+                    BaseMatrix<Item, ArrayContainer<Item, 3>, $RS, $CS>,
+                    $RS,
+                    $CS,
+                >::zeros_from_dim();
+                unsafe {
+                    *result.get1d_unchecked_mut(0) = a(1) * b(2) - a(2) * b(1);
+                    *result.get1d_unchecked_mut(1) = a(2) * b(0) - a(0) * b(2);
+                    *result.get1d_unchecked_mut(2) = a(0) * b(1) - a(1) * b(0);
+                }
+                result
+            }
+        }
+    };
+}
+
+cross_impl!(Fixed3, Fixed1);
+cross_impl!(Fixed1, Fixed3);
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::*;
+    use approx::assert_relative_eq;
+
+    fn example_matrix33() -> Matrix33<f64> {
+        let mut mat = Matrix33::<f64>::zeros_from_dim();
+        let values = [[2.0, -1.0, 0.5], [1.0, 3.0, -2.0], [0.0, 4.0, 1.0]];
+        for (row, row_values) in values.iter().enumerate() {
+            for (col, &value) in row_values.iter().enumerate() {
+                mat[[row, col]] = value;
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_det() {
+        let mut mat = Matrix22::<f64>::zeros_from_dim();
+        mat[[0, 0]] = 1.0;
+        mat[[0, 1]] = 2.0;
+        mat[[1, 0]] = 3.0;
+        mat[[1, 1]] = 4.0;
+        assert_eq!(mat.det(), -2.0);
+
+        assert_relative_eq!(example_matrix33().det(), 25.0, max_relative = 1E-14);
+    }
+
+    #[test]
+    fn test_det_of_expression() {
+        let mat = example_matrix33();
+        assert_relative_eq!((2.0 * &mat).det(), 8.0 * 25.0, max_relative = 1E-14);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mat = example_matrix33();
+        let inv = mat.inverse().unwrap();
+        for row in 0..3 {
+            for col in 0..3 {
+                let mut value = 0.0;
+                for inner in 0..3 {
+                    value += mat[[row, inner]] * inv[[inner, col]];
+                }
+                let expected = if row == col { 1.0 } else { 0.0 };
+                assert_relative_eq!(value, expected, epsilon = 1E-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjugate() {
+        let mat = example_matrix33();
+        let adj = mat.adjugate();
+        let inv = mat.inverse().unwrap();
+        for index in 0..9 {
+            assert_relative_eq!(
+                adj.get1d_value(index),
+                mat.det() * inv.get1d_value(index),
+                max_relative = 1E-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_singular() {
+        let mut mat = Matrix22::<f64>::zeros_from_dim();
+        mat[[0, 0]] = 1.0;
+        mat[[0, 1]] = 2.0;
+        mat[[1, 0]] = 2.0;
+        mat[[1, 1]] = 4.0 + 1E-10;
+
+        assert!(mat.inverse().is_ok());
+        assert!(matches!(
+            mat.inverse_with_tolerance(1E-8),
+            Err(RlstError::SingularMatrix)
+        ));
+
+        mat[[1, 1]] = 4.0;
+        assert!(matches!(mat.inverse(), Err(RlstError::SingularMatrix)));
+    }
+
+    #[test]
+    fn test_solve() {
+        let mat = example_matrix33();
+        let mut rhs = ColumnVector3::<f64>::zeros_from_dim();
+        rhs[[0, 0]] = 1.0;
+        rhs[[1, 0]] = 2.0;
+        rhs[[2, 0]] = 3.0;
+
+        let sol = mat.solve(&rhs).unwrap();
+
+        for row in 0..3 {
+            let mut value = 0.0;
+            for col in 0..3 {
+                value += mat[[row, col]] * sol[[col, 0]];
+            }
+            assert_relative_eq!(value, rhs[[row, 0]], max_relative = 1E-14);
+        }
+    }
+
+    #[test]
+    fn test_cross() {
+        let mut vec1 = ColumnVector3::<f64>::zeros_from_dim();
+        let mut vec2 = ColumnVector3::<f64>::zeros_from_dim();
+        vec1[[0, 0]] = 1.0;
+        vec2[[1, 0]] = 1.0;
+
+        let res = vec1.cross(&vec2);
+
+        assert_eq!(res[[0, 0]], 0.0);
+        assert_eq!(res[[1, 0]], 0.0);
+        assert_eq!(res[[2, 0]], 1.0);
+    }
+}
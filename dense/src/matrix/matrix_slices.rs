@@ -134,7 +134,7 @@ impl<Item: Scalar, Data: DataContainerMut<Item = Item>>
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
 
     use super::*;
@@ -0,0 +1,574 @@
+//! Structured matrices that store only their defining data.
+//!
+//! The following matrix types are available. All of them are square or rectangular
+//! matrices with dynamic dimensions.
+//!
+//! - [DiagonalMatrix]: a square matrix defined by its diagonal.
+//! - [ToeplitzMatrix]: a matrix that is constant along its diagonals, defined by its
+//!   first column and first row.
+//! - [CirculantMatrix]: a square Toeplitz matrix in which each column is the cyclic shift
+//!   of the previous one, defined by its first column.
+//! - [LowRankMatrix]: a matrix of the form `U V^H` with `U` of dimension `m x k` and
+//!   `V` of dimension `n x k`, defined by its two factors.
+//!
+//! Elements are computed on access, and [eval](Matrix::eval) converts a structured
+//! matrix into a dense [MatrixD]. Matrix-vector products through
+//! [Apply](rlst_common::basic_traits::Apply) exploit the structure. Products with
+//! Toeplitz and circulant matrices are computed as convolutions with a fast Fourier
+//! transform in `O((m + n) log(m + n))` operations per column. The spectrum of the
+//! defining sequence is computed once when the matrix is created.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_dense::structured::*;
+//! # use rlst_common::basic_traits::Apply;
+//! let circulant = CirculantMatrix::<f64>::from_first_column(vec![1.0, 2.0, 3.0]);
+//! assert_eq!(circulant.get_value(0, 1), 3.0);
+//!
+//! let mut x = ColumnVectorD::<f64>::zeros_from_length(3);
+//! x[[1, 0]] = 1.0;
+//! let mut y = ColumnVectorD::<f64>::zeros_from_length(3);
+//! circulant.apply(1.0, &x, &mut y, 0.0);
+//!
+//! // The product with a unit vector is the corresponding column.
+//! let dense = circulant.eval();
+//! for row in 0..3 {
+//!     assert!((y[[row, 0]] - dense[[row, 1]]).abs() < 1E-14);
+//! }
+//! ```
+
+mod fft;
+
+use crate::matrix::{ColumnVectorD, Matrix, MatrixD};
+use crate::traits::*;
+use crate::types::{c32, c64, IndexType, Scalar};
+use crate::DefaultLayout;
+use alloc::vec::Vec;
+use num::Zero;
+use rlst_common::basic_traits::Apply;
+
+/// A square matrix defined by its diagonal.
+pub type DiagonalMatrix<Item> = Matrix<Item, Diagonal<Item>, Dynamic, Dynamic>;
+
+/// A Toeplitz matrix defined by its first column and first row.
+pub type ToeplitzMatrix<Item> = Matrix<Item, Toeplitz<Item>, Dynamic, Dynamic>;
+
+/// A circulant matrix defined by its first column.
+pub type CirculantMatrix<Item> = Matrix<Item, Circulant<Item>, Dynamic, Dynamic>;
+
+/// A low-rank matrix `U V^H` defined by its factors `U` and `V`.
+pub type LowRankMatrix<Item> = Matrix<Item, LowRank<Item>, Dynamic, Dynamic>;
+
+/// Scalar types for which FFT-based products are available.
+pub trait FftScalar: Scalar {
+    /// Convert a complex number into the scalar type. For real types the
+    /// imaginary part is discarded.
+    fn from_complex(value: Self::Complex) -> Self;
+}
+
+macro_rules! fft_scalar_impl {
+    ($Scalar:ty, real) => {
+        impl FftScalar for $Scalar {
+            fn from_complex(value: Self::Complex) -> Self {
+                value.re
+            }
+        }
+    };
+    ($Scalar:ty, complex) => {
+        impl FftScalar for $Scalar {
+            fn from_complex(value: Self::Complex) -> Self {
+                value
+            }
+        }
+    };
+}
+
+fft_scalar_impl!(f32, real);
+fft_scalar_impl!(f64, real);
+fft_scalar_impl!(c32, complex);
+fft_scalar_impl!(c64, complex);
+
+/// Matrix-vector product of a structured matrix implementation.
+trait MatVec<Item: Scalar> {
+    /// Return the product of the matrix with the vector `x`.
+    fn matvec(&self, x: &[Item]) -> Vec<Item>;
+}
+
+/// Implementation of a diagonal matrix.
+pub struct Diagonal<Item: Scalar> {
+    diag: Vec<Item>,
+    layout: DefaultLayout,
+}
+
+/// Implementation of a Toeplitz matrix.
+pub struct Toeplitz<Item: Scalar> {
+    first_column: Vec<Item>,
+    first_row: Vec<Item>,
+    convolution: fft::Convolution<Item::Complex>,
+    layout: DefaultLayout,
+}
+
+/// Implementation of a circulant matrix.
+pub struct Circulant<Item: Scalar> {
+    first_column: Vec<Item>,
+    convolution: fft::Convolution<Item::Complex>,
+    layout: DefaultLayout,
+}
+
+/// Implementation of a low-rank matrix.
+pub struct LowRank<Item: Scalar> {
+    u: MatrixD<Item>,
+    v: MatrixD<Item>,
+    layout: DefaultLayout,
+}
+
+fn column_major_layout(dim: (IndexType, IndexType)) -> DefaultLayout {
+    DefaultLayout::from_dimension(dim, (1, dim.0))
+}
+
+impl<Item: Scalar> DiagonalMatrix<Item> {
+    /// Create a square matrix with the given diagonal.
+    pub fn from_diagonal(diag: Vec<Item>) -> Self {
+        let n = diag.len();
+        Matrix::new(Diagonal {
+            diag,
+            layout: column_major_layout((n, n)),
+        })
+    }
+
+    /// Return the diagonal.
+    pub fn diagonal(&self) -> &[Item] {
+        &self.0.diag
+    }
+}
+
+impl<Item: Scalar> ToeplitzMatrix<Item> {
+    /// Create a Toeplitz matrix from its first column and first row.
+    ///
+    /// The matrix has dimension `first_column.len() x first_row.len()`. The first entries
+    /// of `first_column` and `first_row` must coincide.
+    pub fn from_first_column_and_row(first_column: Vec<Item>, first_row: Vec<Item>) -> Self {
+        assert!(
+            !first_column.is_empty() && !first_row.is_empty(),
+            "First column and first row of a Toeplitz matrix must not be empty."
+        );
+        assert!(
+            first_column[0] == first_row[0],
+            "First column and first row of a Toeplitz matrix must start with the same entry."
+        );
+        let dim = (first_column.len(), first_row.len());
+        // The sequence of diagonals from the top right to the bottom left corner.
+        let diagonals: Vec<Item::Complex> = first_row[1..]
+            .iter()
+            .rev()
+            .chain(first_column.iter())
+            .map(|value| value.as_c())
+            .collect();
+        Matrix::new(Toeplitz {
+            convolution: fft::Convolution::new(&diagonals, dim.1),
+            first_column,
+            first_row,
+            layout: column_major_layout(dim),
+        })
+    }
+}
+
+impl<Item: Scalar> CirculantMatrix<Item> {
+    /// Create a square circulant matrix from its first column.
+    pub fn from_first_column(first_column: Vec<Item>) -> Self {
+        let n = first_column.len();
+        let column: Vec<Item::Complex> = first_column.iter().map(|value| value.as_c()).collect();
+        Matrix::new(Circulant {
+            convolution: fft::Convolution::new(&column, n),
+            first_column,
+            layout: column_major_layout((n, n)),
+        })
+    }
+}
+
+impl<Item: Scalar> LowRankMatrix<Item> {
+    /// Create the matrix `U V^H` from an `m x k` matrix `U` and an `n x k` matrix `V`.
+    pub fn from_factors(u: MatrixD<Item>, v: MatrixD<Item>) -> Self {
+        assert_eq!(
+            u.layout().dim().1,
+            v.layout().dim().1,
+            "Factors of a low-rank matrix must have the same number of columns."
+        );
+        let dim = (u.layout().dim().0, v.layout().dim().0);
+        Matrix::new(LowRank {
+            u,
+            v,
+            layout: column_major_layout(dim),
+        })
+    }
+
+    /// Return the number of columns `k` of the factors.
+    pub fn rank(&self) -> IndexType {
+        self.0.u.layout().dim().1
+    }
+}
+
+macro_rules! structured_impl {
+    ($Impl:ident) => {
+        impl<Item: Scalar> SizeType for $Impl<Item> {
+            type R = Dynamic;
+            type C = Dynamic;
+        }
+
+        impl<Item: Scalar> Layout for $Impl<Item> {
+            type Impl = DefaultLayout;
+
+            #[inline]
+            fn layout(&self) -> &Self::Impl {
+                &self.layout
+            }
+        }
+    };
+}
+
+structured_impl!(Diagonal);
+structured_impl!(Toeplitz);
+structured_impl!(Circulant);
+structured_impl!(LowRank);
+
+macro_rules! random_access_impl {
+    ($Impl:ident, |$mat:ident, $row:ident, $col:ident| $body:expr) => {
+        impl<Item: Scalar> UnsafeRandomAccessByValue for $Impl<Item> {
+            type Item = Item;
+
+            #[inline]
+            unsafe fn get_value_unchecked(&self, row: IndexType, col: IndexType) -> Self::Item {
+                let ($mat, $row, $col) = (self, row, col);
+                $body
+            }
+
+            #[inline]
+            unsafe fn get1d_value_unchecked(&self, index: IndexType) -> Self::Item {
+                let (row, col) = self.layout.convert_1d_2d(index);
+                self.get_value_unchecked(row, col)
+            }
+        }
+    };
+}
+
+random_access_impl!(Diagonal, |mat, row, col| if row == col {
+    *mat.diag.get_unchecked(row)
+} else {
+    <Item as Zero>::zero()
+});
+
+random_access_impl!(Toeplitz, |mat, row, col| if row >= col {
+    *mat.first_column.get_unchecked(row - col)
+} else {
+    *mat.first_row.get_unchecked(col - row)
+});
+
+random_access_impl!(Circulant, |mat, row, col| {
+    let n = mat.first_column.len();
+    *mat.first_column.get_unchecked((n + row - col) % n)
+});
+
+random_access_impl!(LowRank, |mat, row, col| {
+    let mut result = <Item as Zero>::zero();
+    for index in 0..mat.u.layout().dim().1 {
+        result +=
+            mat.u.get_value_unchecked(row, index) * mat.v.get_value_unchecked(col, index).conj();
+    }
+    result
+});
+
+impl<Item: Scalar> MatVec<Item> for Diagonal<Item> {
+    fn matvec(&self, x: &[Item]) -> Vec<Item> {
+        self.diag
+            .iter()
+            .zip(x.iter())
+            .map(|(&diag, &value)| diag * value)
+            .collect()
+    }
+}
+
+impl<Item: FftScalar> MatVec<Item> for Toeplitz<Item> {
+    fn matvec(&self, x: &[Item]) -> Vec<Item> {
+        let (rows, cols) = self.layout.dim();
+        let x: Vec<Item::Complex> = x.iter().map(|value| value.as_c()).collect();
+        let conv = self.convolution.apply(&x);
+        (0..rows)
+            .map(|row| Item::from_complex(conv[row + cols - 1]))
+            .collect()
+    }
+}
+
+impl<Item: FftScalar> MatVec<Item> for Circulant<Item> {
+    fn matvec(&self, x: &[Item]) -> Vec<Item> {
+        let n = self.first_column.len();
+        let x: Vec<Item::Complex> = x.iter().map(|value| value.as_c()).collect();
+        // Fold the linear convolution of length 2n - 1 into a cyclic one.
+        let conv = self.convolution.apply(&x);
+        (0..n)
+            .map(|row| {
+                let wrapped = conv
+                    .get(row + n)
+                    .copied()
+                    .unwrap_or(<Item::Complex as Zero>::zero());
+                Item::from_complex(conv[row] + wrapped)
+            })
+            .collect()
+    }
+}
+
+impl<Item: Scalar> MatVec<Item> for LowRank<Item> {
+    fn matvec(&self, x: &[Item]) -> Vec<Item> {
+        let (rows, rank) = self.u.layout().dim();
+        let coefficients: Vec<Item> = (0..rank)
+            .map(|index| {
+                x.iter()
+                    .enumerate()
+                    .fold(<Item as Zero>::zero(), |acc, (row, &value)| {
+                        acc + unsafe { self.v.get_value_unchecked(row, index) }.conj() * value
+                    })
+            })
+            .collect();
+        (0..rows)
+            .map(|row| {
+                coefficients.iter().enumerate().fold(
+                    <Item as Zero>::zero(),
+                    |acc, (index, &coefficient)| {
+                        acc + unsafe { self.u.get_value_unchecked(row, index) } * coefficient
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Compute `y = alpha * op * x + beta * y` column by column.
+fn apply_columns<
+    Item: Scalar,
+    Op: MatVec<Item>,
+    XImpl: MatrixTrait<Item, Dynamic, CS>,
+    YImpl: MatrixTraitMut<Item, Dynamic, CS>,
+    CS: SizeIdentifier,
+>(
+    op: &Op,
+    dim: (IndexType, IndexType),
+    alpha: Item,
+    x: &Matrix<Item, XImpl, Dynamic, CS>,
+    y: &mut Matrix<Item, YImpl, Dynamic, CS>,
+    beta: Item,
+) {
+    let dim_x = x.layout().dim();
+    let dim_y = y.layout().dim();
+
+    assert!(
+        (dim.1 == dim_x.0) & (dim_y.0 == dim.0) & (dim_y.1 == dim_x.1),
+        "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
+        dim,
+        dim_x,
+        dim_y
+    );
+
+    for col in 0..dim_x.1 {
+        let column: Vec<Item> = (0..dim_x.0)
+            .map(|row| unsafe { x.get_value_unchecked(row, col) })
+            .collect();
+        for (row, value) in op.matvec(&column).into_iter().enumerate() {
+            unsafe {
+                let elem = y.get_unchecked_mut(row, col);
+                *elem = alpha * value + beta * *elem;
+            }
+        }
+    }
+}
+
+macro_rules! structured_apply_impl {
+    ($Impl:ident, $Bound:ident, $Domain:ident) => {
+        impl<Item: $Bound> Apply<$Domain<Item>> for Matrix<Item, $Impl<Item>, Dynamic, Dynamic> {
+            type T = Item;
+            type Range = $Domain<Item>;
+
+            fn apply(&self, alpha: Self::T, x: &$Domain<Item>, y: &mut Self::Range, beta: Self::T) {
+                apply_columns(&self.0, self.layout().dim(), alpha, x, y, beta);
+            }
+        }
+    };
+}
+
+structured_apply_impl!(Diagonal, Scalar, ColumnVectorD);
+structured_apply_impl!(Diagonal, Scalar, MatrixD);
+structured_apply_impl!(Toeplitz, FftScalar, ColumnVectorD);
+structured_apply_impl!(Toeplitz, FftScalar, MatrixD);
+structured_apply_impl!(Circulant, FftScalar, ColumnVectorD);
+structured_apply_impl!(Circulant, FftScalar, MatrixD);
+structured_apply_impl!(LowRank, Scalar, ColumnVectorD);
+structured_apply_impl!(LowRank, Scalar, MatrixD);
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::prelude::*;
+
+    /// Check `get_value`, `eval` and `apply` of a structured matrix against a dense reference.
+    fn check_against_dense<Item: Scalar + RandScalarSample, Mat>(
+        mat: &Mat,
+        expected: &MatrixD<Item>,
+    ) where
+        Mat: Apply<ColumnVectorD<Item>, T = Item, Range = ColumnVectorD<Item>>
+            + Apply<MatrixD<Item>, T = Item, Range = MatrixD<Item>>
+            + MatrixTrait<Item, Dynamic, Dynamic>,
+    {
+        let (rows, cols) = expected.layout().dim();
+        assert_eq!(mat.layout().dim(), (rows, cols));
+
+        for row in 0..rows {
+            for col in 0..cols {
+                assert_eq!(mat.get_value(row, col), expected[[row, col]]);
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut x = ColumnVectorD::<Item>::zeros_from_length(cols);
+        let mut y = ColumnVectorD::<Item>::zeros_from_length(rows);
+        x.for_each(|elem| *elem = Item::sample(&mut rng));
+        y.for_each(|elem| *elem = Item::sample(&mut rng));
+        let alpha = Item::sample(&mut rng);
+        let beta = Item::sample(&mut rng);
+
+        let mut expected_y = ColumnVectorD::<Item>::zeros_from_length(rows);
+        for row in 0..rows {
+            let mut sum = <Item as Zero>::zero();
+            for col in 0..cols {
+                sum += expected[[row, col]] * x[[col, 0]];
+            }
+            expected_y[[row, 0]] = alpha * sum + beta * y[[row, 0]];
+        }
+
+        mat.apply(alpha, &x, &mut y, beta);
+        for row in 0..rows {
+            assert_relative_eq!(y[[row, 0]], expected_y[[row, 0]], epsilon = 1E-12);
+        }
+
+        let mut x_mat = MatrixD::<Item>::zeros_from_dim(cols, 2);
+        x_mat.for_each(|elem| *elem = Item::sample(&mut rng));
+        let mut y_mat = MatrixD::<Item>::zeros_from_dim(rows, 2);
+        mat.apply(
+            <Item as num::One>::one(),
+            &x_mat,
+            &mut y_mat,
+            <Item as Zero>::zero(),
+        );
+        for col in 0..2 {
+            for row in 0..rows {
+                let sum: Item = (0..cols)
+                    .map(|index| expected[[row, index]] * x_mat[[index, col]])
+                    .sum();
+                assert_relative_eq!(y_mat[[row, col]], sum, epsilon = 1E-12);
+            }
+        }
+    }
+
+    trait RandScalarSample: Scalar + approx::RelativeEq<Epsilon = f64> {
+        fn sample<R: Rng>(rng: &mut R) -> Self;
+    }
+
+    impl RandScalarSample for f64 {
+        fn sample<R: Rng>(rng: &mut R) -> Self {
+            rng.gen_range(-1.0..1.0)
+        }
+    }
+
+    impl RandScalarSample for c64 {
+        fn sample<R: Rng>(rng: &mut R) -> Self {
+            c64::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+        }
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let mat = DiagonalMatrix::from_diagonal(vec![1.0, -2.0, 3.0]);
+        assert_eq!(mat.diagonal(), &[1.0, -2.0, 3.0]);
+
+        let expected = Matrix::from_ref(&mat).eval();
+        assert_eq!(expected[[1, 1]], -2.0);
+        assert_eq!(expected[[0, 2]], 0.0);
+        check_against_dense(&mat, &expected);
+    }
+
+    #[test]
+    fn test_toeplitz() {
+        let first_column = vec![c64::new(1.0, 1.0), c64::new(2.0, 0.0), c64::new(3.0, -1.0)];
+        let first_row = vec![
+            c64::new(1.0, 1.0),
+            c64::new(0.0, 4.0),
+            c64::new(5.0, 0.0),
+            c64::new(6.0, 2.0),
+            c64::new(-7.0, 0.0),
+        ];
+        let mat = ToeplitzMatrix::from_first_column_and_row(first_column, first_row);
+
+        let mut expected = MatrixD::<c64>::zeros_from_dim(3, 5);
+        for row in 0..3 {
+            for col in 0..5 {
+                expected[[row, col]] = if row >= col {
+                    mat.0.first_column[row - col]
+                } else {
+                    mat.0.first_row[col - row]
+                };
+            }
+        }
+        check_against_dense(&mat, &expected);
+
+        // A tall Toeplitz matrix.
+        let tall = ToeplitzMatrix::from_first_column_and_row(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            vec![1.0, -1.0],
+        );
+        let expected = Matrix::from_ref(&tall).eval();
+        assert_eq!(expected[[5, 1]], 5.0);
+        check_against_dense(&tall, &expected);
+    }
+
+    #[test]
+    fn test_circulant() {
+        for n in [1, 5, 8] {
+            let first_column: Vec<f64> = (0..n).map(|index| (index * index) as f64 - 2.0).collect();
+            let mat = CirculantMatrix::from_first_column(first_column.clone());
+            let mut expected = MatrixD::<f64>::zeros_from_dim(n, n);
+            for row in 0..n {
+                for col in 0..n {
+                    expected[[row, col]] = first_column[(n + row - col) % n];
+                }
+            }
+            check_against_dense(&mat, &expected);
+        }
+    }
+
+    #[test]
+    fn test_low_rank() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut u = MatrixD::<c64>::zeros_from_dim(4, 2);
+        let mut v = MatrixD::<c64>::zeros_from_dim(3, 2);
+        u.for_each(|elem| *elem = c64::sample(&mut rng));
+        v.for_each(|elem| *elem = c64::sample(&mut rng));
+
+        let mut expected = MatrixD::<c64>::zeros_from_dim(4, 3);
+        for row in 0..4 {
+            for col in 0..3 {
+                expected[[row, col]] = (0..2)
+                    .map(|index| u[[row, index]] * v[[col, index]].conj())
+                    .sum();
+            }
+        }
+
+        let mat = LowRankMatrix::from_factors(u, v);
+        assert_eq!(mat.rank(), 2);
+        let evaluated = Matrix::from_ref(&mat).eval();
+        for row in 0..4 {
+            for col in 0..3 {
+                assert_relative_eq!(evaluated[[row, col]], expected[[row, col]], epsilon = 1E-14);
+            }
+        }
+        check_against_dense(&mat, &evaluated);
+    }
+}
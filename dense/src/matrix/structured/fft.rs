@@ -0,0 +1,179 @@
+//! Fast Fourier transforms for the matrix-vector products of structured matrices.
+//!
+//! Only power-of-two lengths are supported. Circulant and Toeplitz products are
+//! computed as linear convolutions, which can always be zero-padded to a power of two.
+
+use crate::types::{IndexType, Scalar};
+use alloc::vec;
+use alloc::vec::Vec;
+use num::{Float, One, Zero};
+
+/// In-place radix-2 FFT of a sequence whose length is a power of two.
+///
+/// The forward transform uses the kernel `exp(-2 pi i jk / n)`. The inverse transform
+/// is not normalised.
+fn fft_in_place<C: Scalar<Complex = C>>(data: &mut [C], inverse: bool) {
+    let n = data.len();
+    assert!(
+        n.is_power_of_two(),
+        "FFT length {} is not a power of two.",
+        n
+    );
+
+    // Bit reversal permutation.
+    let mut target = 0;
+    for index in 1..n {
+        let mut bit = n >> 1;
+        while target & bit != 0 {
+            target ^= bit;
+            bit >>= 1;
+        }
+        target |= bit;
+        if index < target {
+            data.swap(index, target);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let twiddles: Vec<C> = (0..half)
+            .map(|k| {
+                let angle = sign * 2.0 * core::f64::consts::PI * (k as f64) / (len as f64);
+                C::complex(Float::cos(angle), Float::sin(angle))
+            })
+            .collect();
+        for start in (0..n).step_by(len) {
+            for (k, &twiddle) in twiddles.iter().enumerate() {
+                let first = data[start + k];
+                let second = data[start + k + half] * twiddle;
+                data[start + k] = first + second;
+                data[start + k + half] = first - second;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Linear convolutions `c[k] = sum_j a[k - j] * b[j]` of a fixed sequence `a` with
+/// sequences `b` of a fixed length, computed in the complex type `C`.
+///
+/// The spectrum of the zero-padded sequence `a` is computed once on construction, so
+/// that each convolution only requires one forward and one inverse transform.
+pub(crate) struct Convolution<C: Scalar<Complex = C>> {
+    spectrum: Vec<C>,
+    other_len: IndexType,
+    len: IndexType,
+}
+
+impl<C: Scalar<Complex = C>> Convolution<C> {
+    /// Precompute the spectrum of `a` for convolutions with sequences of length `other_len`.
+    pub(crate) fn new(a: &[C], other_len: IndexType) -> Self {
+        if a.is_empty() || other_len == 0 {
+            return Self {
+                spectrum: Vec::new(),
+                other_len,
+                len: 0,
+            };
+        }
+        let len = a.len() + other_len - 1;
+        let size: IndexType = len.next_power_of_two();
+
+        let mut spectrum = vec![<C as Zero>::zero(); size];
+        spectrum[..a.len()].copy_from_slice(a);
+        fft_in_place(&mut spectrum, false);
+
+        Self {
+            spectrum,
+            other_len,
+            len,
+        }
+    }
+
+    /// Return the convolution with `b` of length `a.len() + b.len() - 1`.
+    pub(crate) fn apply(&self, b: &[C]) -> Vec<C> {
+        assert_eq!(
+            b.len(),
+            self.other_len,
+            "Sequence has length {}, expected {}.",
+            b.len(),
+            self.other_len
+        );
+        if self.len == 0 {
+            return Vec::new();
+        }
+        let size = self.spectrum.len();
+
+        let mut fb = vec![<C as Zero>::zero(); size];
+        fb[..b.len()].copy_from_slice(b);
+
+        fft_in_place(&mut fb, false);
+        for (value, &coefficient) in fb.iter_mut().zip(self.spectrum.iter()) {
+            *value *= coefficient;
+        }
+        fft_in_place(&mut fb, true);
+
+        let scale = <C::Real as One>::one() / C::real(size);
+        fb.truncate(self.len);
+        for value in fb.iter_mut() {
+            *value = value.mul_real(scale);
+        }
+        fb
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::types::c64;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_fft_against_dft() {
+        let n = 8;
+        let data: Vec<c64> = (0..n)
+            .map(|k| c64::new(k as f64, (k * k) as f64 - 3.0))
+            .collect();
+
+        let mut transformed = data.clone();
+        fft_in_place(&mut transformed, false);
+
+        for (j, &value) in transformed.iter().enumerate() {
+            let expected: c64 = (0..n)
+                .map(|k| {
+                    let angle = -2.0 * core::f64::consts::PI * ((j * k) as f64) / (n as f64);
+                    data[k] * c64::new(angle.cos(), angle.sin())
+                })
+                .sum();
+            assert_relative_eq!(value, expected, epsilon = 1E-12);
+        }
+    }
+
+    #[test]
+    fn test_convolution() {
+        let a = [c64::new(1.0, 0.0), c64::new(2.0, 0.0), c64::new(3.0, 0.0)];
+        let b = [c64::new(0.0, 1.0), c64::new(-1.0, 0.0)];
+
+        let convolution = Convolution::new(&a, b.len());
+        let result = convolution.apply(&b);
+        let expected = [
+            c64::new(0.0, 1.0),
+            c64::new(-1.0, 2.0),
+            c64::new(-2.0, 3.0),
+            c64::new(-3.0, 0.0),
+        ];
+
+        assert_eq!(result.len(), expected.len());
+        for (value, expected) in result.iter().zip(expected.iter()) {
+            assert_relative_eq!(*value, *expected, epsilon = 1E-14);
+        }
+
+        // The spectrum is reused for further sequences of the same length.
+        let result = convolution.apply(&[c64::new(1.0, 0.0), c64::new(0.0, 0.0)]);
+        for (value, expected) in result.iter().zip(a.iter()) {
+            assert_relative_eq!(*value, *expected, epsilon = 1E-14);
+        }
+    }
+}
@@ -1,69 +1,353 @@
-//! Specialisation for vectors
+//! Specialisation for vectors.
 //!
+//! This module implements the vector traits from [rlst_common::basic_traits] for
+//! [ColumnVectorD] and [RowVectorD], so that dense vectors can be used by generic
+//! algorithms such as Arnoldi iterations. The inner product is linear in the first
+//! argument and conjugate linear in the second argument.
+//!
+//! In addition, vectors provide the methods `axpy`, `dot`, `dotu`, `dotc` and `normalize`.
+//! Following BLAS, `dotu` returns the unconjugated and `dotc` the conjugated sum of
+//! products of two vectors of the same orientation. The method `dot` agrees with `dotu`
+//! for two vectors of the same orientation and forwards all other products, such as the
+//! outer product of a column vector with a row vector, to [Dot::dot](crate::Dot::dot).
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_common::basic_traits::*;
+//! let mut x = ColumnVectorD::<f64>::zeros_from_length(2);
+//! let mut y = ColumnVectorD::<f64>::zeros_from_length(2);
+//! x[[0, 0]] = 3.0;
+//! x[[1, 0]] = 4.0;
+//! y[[1, 0]] = 1.0;
+//!
+//! assert_eq!(x.dot(&y), 4.0);
+//! assert_eq!(x.norm2(), 5.0);
+//!
+//! y.axpy(2.0, &x);
+//! assert_eq!(y[[1, 0]], 9.0);
+//! ```
 
 use crate::base_matrix::BaseMatrix;
-use crate::data_container::{ArrayContainer, DataContainer, VectorContainer};
-use crate::layouts::*;
-use crate::matrix::Matrix;
+use crate::data_container::{DataContainer, DataContainerMut};
+use crate::matrix::{ColumnVectorD, Matrix, RowVectorD};
+use crate::matrix_multiply::Dot;
 use crate::traits::*;
 use crate::types::{IndexType, Scalar};
+use num::{Float, Zero};
+use rlst_common::basic_traits::{
+    FillFrom, Inner, MultSomeInto, NewFromZero, Norm1, NormInf, Scale,
+};
 
-macro_rules! vec_dynamic {
-    ($MatrixType:ident, $BaseType:ident, $Layout:ident, $RS:ident, $CS:ident) => {
-        impl<Item: Scalar>
-            $MatrixType<
-                Item,
-                $BaseType<Item, VectorContainer<Item>, $Layout, $RS, $CS>,
-                $Layout,
-                $RS,
-                $CS,
-            >
-        {
-            pub fn from_data(data: VectorContainer<Item>) -> Self {
-                Self::new($BaseType::<Item, VectorContainer<Item>, $Layout, $RS, $CS>::new(data))
+/// Product of a vector with another vector or a matrix, as computed by the `dot` method of vectors.
+///
+/// Two vectors of the same orientation give the sum of the products of their entries
+/// without complex conjugation. All other products are computed by [Dot].
+pub trait VectorDot<Rhs> {
+    type Output;
+
+    fn vector_dot(&self, rhs: &Rhs) -> Self::Output;
+}
+
+macro_rules! vector_traits_impl {
+    ($VectorType:ident) => {
+        impl<Item: Scalar> Inner for $VectorType<Item> {
+            type T = Item;
+
+            fn inner(&self, other: &Self) -> Self::T {
+                self.fold_products(other, |first, second| first * second.conj())
             }
+        }
+
+        impl<Item: Scalar> Norm1 for $VectorType<Item> {
+            type T = Item;
+
+            fn norm1(&self) -> <Self::T as Scalar>::Real {
+                self.fold_values(<Item::Real as Zero>::zero(), |acc, elem| acc + elem.abs())
+            }
+        }
+
+        impl<Item: Scalar> NormInf for $VectorType<Item> {
+            type T = Item;
+
+            fn norm_inf(&self) -> <Self::T as Scalar>::Real {
+                self.fold_values(<Item::Real as Zero>::zero(), |acc, elem| {
+                    Float::max(acc, elem.abs())
+                })
+            }
+        }
+
+        impl<Item: Scalar> Scale for $VectorType<Item> {
+            type T = Item;
 
-            pub fn from_zeros(length: IndexType) -> Self {
-                Self::from_data(VectorContainer::<Item>::new(length))
+            fn scale(&mut self, alpha: Self::T) {
+                for index in 0..self.layout().number_of_elements() {
+                    unsafe { *self.get1d_unchecked_mut(index) *= alpha };
+                }
+            }
+        }
+
+        impl<Item: Scalar> MultSomeInto for $VectorType<Item> {
+            type T = Item;
+
+            fn mult_sum_into(&mut self, alpha: Self::T, x: &Self) {
+                self.axpy(alpha, x);
+            }
+        }
+
+        impl<Item: Scalar> NewFromZero for $VectorType<Item> {
+            fn new_from_zero(&self) -> Self {
+                Self::zeros_from_length(self.length())
+            }
+        }
+
+        impl<Item: Scalar> FillFrom for $VectorType<Item> {
+            fn fill_from(&mut self, other: &Self) {
+                assert_same_length(self.length(), other.length());
+                for index in 0..self.length() {
+                    unsafe {
+                        *self.get1d_unchecked_mut(index) = other.get1d_value_unchecked(index)
+                    };
+                }
             }
         }
     };
 }
 
-macro_rules! vec_fixed {
-    ($MatrixType:ident, $BaseType:ident, $Layout:ident, $RS:ident, $CS:ident, $N:expr) => {
-        impl<Item: Scalar>
-            $MatrixType<
-                Item,
-                $BaseType<Item, ArrayContainer<Item, $N>, $Layout, $RS, $CS>,
-                $Layout,
-                $RS,
-                $CS,
-            >
+vector_traits_impl!(ColumnVectorD);
+vector_traits_impl!(RowVectorD);
+
+fn assert_same_length(first: IndexType, second: IndexType) {
+    assert_eq!(
+        first, second,
+        "Vectors have incompatible lengths {} and {}.",
+        first, second
+    );
+}
+
+macro_rules! vector_methods_impl {
+    ($RS:ty, $CS:ty) => {
+        impl<Item: Scalar, Data: DataContainer<Item = Item>>
+            Matrix<Item, BaseMatrix<Item, Data, $RS, $CS>, $RS, $CS>
         {
-            pub fn from_data(data: ArrayContainer<Item, $N>) -> Self {
-                Self::new($BaseType::<Item, ArrayContainer<Item, $N>, $Layout, $RS, $CS>::new(data))
+            /// Return the product of the vector with `other`.
+            ///
+            /// For two vectors of the same orientation this is the same as [dotu](Self::dotu).
+            /// All other products are computed by [Dot::dot](crate::Dot::dot).
+            pub fn dot<Rhs>(&self, other: &Rhs) -> <Self as VectorDot<Rhs>>::Output
+            where
+                Self: VectorDot<Rhs>,
+            {
+                self.vector_dot(other)
             }
 
-            pub fn from_zeros() -> Self {
-                Self::from_data(ArrayContainer::<Item, $N>::new())
+            /// Return the sum of the products of the entries of two vectors without complex conjugation.
+            pub fn dotu<MatImpl: MatrixTrait<Item, $RS, $CS>>(
+                &self,
+                other: &Matrix<Item, MatImpl, $RS, $CS>,
+            ) -> Item {
+                self.fold_products(other, |first, second| first * second)
+            }
+
+            /// Return the sum of the products of the entries of two vectors, where the entries
+            /// of `self` are complex conjugated.
+            pub fn dotc<MatImpl: MatrixTrait<Item, $RS, $CS>>(
+                &self,
+                other: &Matrix<Item, MatImpl, $RS, $CS>,
+            ) -> Item {
+                self.fold_products(other, |first, second| first.conj() * second)
+            }
+
+            fn fold_products<MatImpl: MatrixTrait<Item, $RS, $CS>, F: Fn(Item, Item) -> Item>(
+                &self,
+                other: &Matrix<Item, MatImpl, $RS, $CS>,
+                op: F,
+            ) -> Item {
+                assert_same_length(self.length(), other.length());
+                let mut result = <Item as Zero>::zero();
+                for index in 0..self.length() {
+                    result += unsafe {
+                        op(
+                            self.get1d_value_unchecked(index),
+                            other.get1d_value_unchecked(index),
+                        )
+                    };
+                }
+                result
+            }
+
+            fn fold_values<F: Fn(Item::Real, Item) -> Item::Real>(
+                &self,
+                init: Item::Real,
+                op: F,
+            ) -> Item::Real {
+                (0..self.length()).fold(init, |acc, index| {
+                    op(acc, unsafe { self.get1d_value_unchecked(index) })
+                })
+            }
+        }
+
+        impl<Item: Scalar, Data: DataContainerMut<Item = Item>>
+            Matrix<Item, BaseMatrix<Item, Data, $RS, $CS>, $RS, $CS>
+        {
+            /// Perform the operation `self = alpha * x + self`.
+            pub fn axpy<MatImpl: MatrixTrait<Item, $RS, $CS>>(
+                &mut self,
+                alpha: Item,
+                x: &Matrix<Item, MatImpl, $RS, $CS>,
+            ) {
+                assert_same_length(self.length(), x.length());
+                for index in 0..self.length() {
+                    unsafe {
+                        *self.get1d_unchecked_mut(index) += alpha * x.get1d_value_unchecked(index)
+                    };
+                }
+            }
+
+            /// Scale the vector to unit 2-norm and return its original norm.
+            ///
+            /// A zero vector is left unchanged.
+            pub fn normalize(&mut self) -> Item::Real {
+                let norm =
+                    Float::sqrt(self.fold_values(<Item::Real as Zero>::zero(), |acc, elem| {
+                        acc + elem.square()
+                    }));
+                if norm > <Item::Real as Zero>::zero() {
+                    for index in 0..self.length() {
+                        unsafe {
+                            let value = self.get1d_value_unchecked(index);
+                            *self.get1d_unchecked_mut(index) = value.div_real(norm);
+                        }
+                    }
+                }
+                norm
+            }
+        }
+    };
+}
+
+vector_methods_impl!(Dynamic, Fixed1);
+vector_methods_impl!(Fixed1, Dynamic);
+
+macro_rules! vector_dot_impl {
+    (($RS:ty, $CS:ty); $(($RS2:ty, $CS2:ty)),*) => {
+        impl<Item: Scalar, Data: DataContainer<Item = Item>, MatImpl: MatrixTrait<Item, $RS, $CS>>
+            VectorDot<Matrix<Item, MatImpl, $RS, $CS>>
+            for Matrix<Item, BaseMatrix<Item, Data, $RS, $CS>, $RS, $CS>
+        {
+            type Output = Item;
+
+            fn vector_dot(&self, rhs: &Matrix<Item, MatImpl, $RS, $CS>) -> Self::Output {
+                self.dotu(rhs)
             }
         }
+
+        $(
+            impl<
+                    Item: Scalar,
+                    Data: DataContainer<Item = Item>,
+                    MatImpl: MatrixTrait<Item, $RS2, $CS2>,
+                > VectorDot<Matrix<Item, MatImpl, $RS2, $CS2>>
+                for Matrix<Item, BaseMatrix<Item, Data, $RS, $CS>, $RS, $CS>
+            where
+                Self: Dot<Matrix<Item, MatImpl, $RS2, $CS2>>,
+            {
+                type Output = <Self as Dot<Matrix<Item, MatImpl, $RS2, $CS2>>>::Output;
+
+                fn vector_dot(&self, rhs: &Matrix<Item, MatImpl, $RS2, $CS2>) -> Self::Output {
+                    Dot::dot(self, rhs)
+                }
+            }
+        )*
     };
 }
 
-vec_dynamic!(Matrix, BaseMatrix, RowMajor, Dynamic, Fixed1);
-vec_dynamic!(Matrix, BaseMatrix, RowMajor, Fixed1, Dynamic);
+vector_dot_impl!((Dynamic, Fixed1);
+    (Fixed1, Fixed1), (Fixed1, Fixed2), (Fixed1, Fixed3), (Fixed1, Dynamic),
+    (Fixed2, Fixed1), (Fixed2, Fixed2), (Fixed2, Fixed3), (Fixed2, Dynamic),
+    (Fixed3, Fixed1), (Fixed3, Fixed2), (Fixed3, Fixed3), (Fixed3, Dynamic),
+    (Dynamic, Fixed2), (Dynamic, Fixed3), (Dynamic, Dynamic));
+vector_dot_impl!((Fixed1, Dynamic);
+    (Fixed1, Fixed1), (Fixed1, Fixed2), (Fixed1, Fixed3),
+    (Fixed2, Fixed1), (Fixed2, Fixed2), (Fixed2, Fixed3), (Fixed2, Dynamic),
+    (Fixed3, Fixed1), (Fixed3, Fixed2), (Fixed3, Fixed3), (Fixed3, Dynamic),
+    (Dynamic, Fixed1), (Dynamic, Fixed2), (Dynamic, Fixed3), (Dynamic, Dynamic));
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::types::c64;
+    use approx::assert_ulps_eq;
+    use rlst_common::basic_traits::{Duplicate, Norm2};
+
+    fn example_vector() -> ColumnVectorD<c64> {
+        let mut vec = ColumnVectorD::<c64>::zeros_from_length(3);
+        vec[[0, 0]] = c64::new(1.0, 2.0);
+        vec[[1, 0]] = c64::new(-2.0, 0.0);
+        vec[[2, 0]] = c64::new(0.0, -4.0);
+        vec
+    }
+
+    #[test]
+    fn test_inner_and_norms() {
+        let vec = example_vector();
+        let mut other = ColumnVectorD::<c64>::zeros_from_length(3);
+        other[[0, 0]] = c64::new(0.0, 1.0);
+        other[[2, 0]] = c64::new(1.0, 0.0);
+
+        assert_ulps_eq!(vec.inner(&other), c64::new(2.0, -5.0));
+        assert_ulps_eq!(vec.dot(&other), c64::new(-2.0, -3.0));
+        assert_ulps_eq!(vec.dotu(&other), c64::new(-2.0, -3.0));
+        assert_ulps_eq!(vec.dotc(&other), c64::new(2.0, 5.0));
+
+        assert_ulps_eq!(vec.norm2(), 5.0);
+        assert_ulps_eq!(vec.norm1(), 5.0_f64.sqrt() + 6.0);
+        assert_ulps_eq!(vec.norm_inf(), 4.0);
+    }
+
+    #[test]
+    fn test_vector_updates() {
+        let vec = example_vector();
+        let mut copy = vec.duplicate();
+        copy.scale(c64::new(2.0, 0.0));
+        copy.mult_sum_into(c64::new(-1.0, 0.0), &vec);
+
+        for index in 0..3 {
+            assert_ulps_eq!(copy[[index, 0]], vec[[index, 0]]);
+        }
+
+        let norm = copy.normalize();
+        assert_ulps_eq!(norm, 5.0);
+        assert_ulps_eq!(copy.norm2(), 1.0);
+    }
+
+    #[test]
+    fn test_row_vector() {
+        let mut vec = RowVectorD::<f64>::zeros_from_length(2);
+        vec[[0, 0]] = 1.0;
+        vec[[0, 1]] = -3.0;
+
+        let mut other = vec.new_from_zero();
+        other.axpy(2.0, &vec);
+
+        assert_eq!(other[[0, 1]], -6.0);
+        assert_eq!(vec.dot(&other), 20.0);
+        assert_eq!(vec.norm_inf(), 3.0);
+    }
+
+    #[test]
+    fn test_dot_with_other_orientation() {
+        let mut col = ColumnVectorD::<f64>::zeros_from_length(2);
+        col[[0, 0]] = 2.0;
+        col[[1, 0]] = 3.0;
+        let mut row = RowVectorD::<f64>::zeros_from_length(2);
+        row[[0, 0]] = 1.0;
+        row[[0, 1]] = -1.0;
 
-vec_fixed!(Matrix, BaseMatrix, RowMajor, Fixed2, Fixed1, 2);
-vec_fixed!(Matrix, BaseMatrix, RowMajor, Fixed3, Fixed1, 2);
-vec_fixed!(Matrix, BaseMatrix, RowMajor, Fixed1, Fixed2, 2);
-vec_fixed!(Matrix, BaseMatrix, RowMajor, Fixed1, Fixed3, 2);
+        assert_eq!(row.dot(&col), -1.0);
 
-impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: SizeIdentifier>
-    Matrix<Item, BaseMatrix<Item, Data, VLayout, RS, CS>, VLayout, RS, CS>
-{
-    pub fn length(&self) -> IndexType {
-        self.0.length()
+        let outer = col.dot(&row);
+        assert_eq!(outer.layout().dim(), (2, 2));
+        assert_eq!(outer[[1, 1]], -3.0);
     }
 }
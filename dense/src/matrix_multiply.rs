@@ -9,17 +9,49 @@
 //! the operation `mat_c = mat_a.dot(&mat_b)`. The latter allocates new memory,
 //! while the former relies on suitable memory being allocated.
 //!
-//! The [MatMul] trait is currently implemented for the product of two dynamic matrices,
-//! the product of a dynamic matrix with a vector, and the product of a row vector
-//! with a dynamic matrix.
-
-use crate::data_container::{DataContainer, DataContainerMut, VectorContainer};
-use crate::matrix::{GenericBaseMatrix, GenericBaseMatrixMut};
+//! The [MatMul] trait is implemented for base matrices of any size type. The [Dot] trait
+//! is implemented for all matrices, including lazy expressions such as `&mat_a + &mat_b`.
+//! Operands that are backed by strided memory are passed directly to the multiplication
+//! kernel. Lazy expressions are first evaluated into a temporary matrix.
+//!
+//! The type of the result of [Dot] depends on the size types of the operands and is
+//! determined by the [MatMulShape] trait. Fixed size operands give fixed size results,
+//! the product of a row vector with a column vector is a scalar, and the product of a
+//! column vector with a row vector is a dynamic matrix.
+//!
+//! Dynamic matrices, including lazy transposes, implement the
+//! [Apply](rlst_common::basic_traits::Apply) trait for column vectors and dynamic matrices.
+//! This allows dense matrices to be used as operators in iterative solvers.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! let mut mat_a = MatrixD::<f64>::zeros_from_dim(2, 2);
+//! let mut mat_b = MatrixD::<f64>::zeros_from_dim(2, 2);
+//! mat_a[[0, 0]] = 1.0;
+//! mat_b[[1, 1]] = 2.0;
+//!
+//! let res = (&mat_a + &mat_b).dot(&mat_a);
+//! assert_eq!(res[[0, 0]], 1.0);
+//!
+//! let mut row = RowVectorD::<f64>::zeros_from_length(2);
+//! let mut col = ColumnVectorD::<f64>::zeros_from_length(2);
+//! row[[0, 1]] = 3.0;
+//! col[[1, 0]] = 4.0;
+//! let inner: f64 = row.dot(&col);
+//! assert_eq!(inner, 12.0);
+//! ```
+
+use crate::data_container::{ArrayContainer, DataContainer, DataContainerMut};
+use crate::matrix::{
+    ColumnVectorD, GenericBaseMatrix, GenericBaseMatrixMut, Matrix, MatrixD, RowVectorD,
+};
 use crate::traits::*;
 use crate::types::*;
+use crate::DefaultLayout;
 
 use matrixmultiply::{cgemm, dgemm, sgemm, zgemm, CGemmOption};
 use num;
+use rlst_common::basic_traits::Apply;
 
 /// This trait provides a high-level interface for the multiplication of a matrix
 /// with another matrix. The result is a new matrix, hence memory allocation takes place.
@@ -54,268 +86,453 @@ pub trait MatMul<
     );
 }
 
-macro_rules! dot_impl {
-    ($Scalar:ty) => {
-        // Matrix x Matrix = Matrix
-        impl<Data1: DataContainer<Item = $Scalar>, Data2: DataContainer<Item = $Scalar>>
-            Dot<GenericBaseMatrix<$Scalar, Data2, Dynamic, Dynamic>>
-            for GenericBaseMatrix<$Scalar, Data1, Dynamic, Dynamic>
-        {
-            type Output = GenericBaseMatrix<$Scalar, VectorContainer<$Scalar>, Dynamic, Dynamic>;
-
-            fn dot(
-                &self,
-                rhs: &GenericBaseMatrix<$Scalar, Data2, Dynamic, Dynamic>,
-            ) -> Self::Output {
-                let mut res =
-                    Self::Output::zeros_from_dim(self.layout().dim().0, rhs.layout().dim().1);
-                <$Scalar>::matmul(
-                    num::cast::<f64, $Scalar>(1.0).unwrap(),
-                    &self,
-                    rhs,
-                    num::cast::<f64, $Scalar>(0.0).unwrap(),
-                    &mut res,
-                );
-                res
-            }
-        }
+/// Scalar types for which a strided matrix multiplication kernel is available.
+pub trait Gemm: Scalar {
+    /// Perform `c = alpha * a * b + beta * c` for an `m x k` matrix `a`
+    /// and a `k x n` matrix `b`. The strides are given as (row stride, column stride).
+    ///
+    /// # Safety
+    /// The pointers must point to memory regions that are valid for the given
+    /// dimensions and strides, and `c` must not alias `a` or `b`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gemm(
+        m: IndexType,
+        k: IndexType,
+        n: IndexType,
+        alpha: Self,
+        a: *const Self,
+        a_stride: (IndexType, IndexType),
+        b: *const Self,
+        b_stride: (IndexType, IndexType),
+        beta: Self,
+        c: *mut Self,
+        c_stride: (IndexType, IndexType),
+    );
+}
 
-        // RowVector x Matrix = RowVector
-        impl<Data1: DataContainer<Item = $Scalar>, Data2: DataContainer<Item = $Scalar>>
-            Dot<GenericBaseMatrix<$Scalar, Data2, Dynamic, Dynamic>>
-            for GenericBaseMatrix<$Scalar, Data1, Fixed1, Dynamic>
-        {
-            type Output = GenericBaseMatrix<$Scalar, VectorContainer<$Scalar>, Fixed1, Dynamic>;
-
-            fn dot(
-                &self,
-                rhs: &GenericBaseMatrix<$Scalar, Data2, Dynamic, Dynamic>,
-            ) -> Self::Output {
-                let mut res = Self::Output::zeros_from_length(rhs.layout().dim().1);
-                <$Scalar>::matmul(
-                    num::cast::<f64, $Scalar>(1.0).unwrap(),
-                    &self,
-                    rhs,
-                    num::cast::<f64, $Scalar>(0.0).unwrap(),
-                    &mut res,
+macro_rules! gemm_impl {
+    ($Scalar:ty, $Blas:ident, real) => {
+        impl Gemm for $Scalar {
+            unsafe fn gemm(
+                m: IndexType,
+                k: IndexType,
+                n: IndexType,
+                alpha: Self,
+                a: *const Self,
+                a_stride: (IndexType, IndexType),
+                b: *const Self,
+                b_stride: (IndexType, IndexType),
+                beta: Self,
+                c: *mut Self,
+                c_stride: (IndexType, IndexType),
+            ) {
+                $Blas(
+                    m,
+                    k,
+                    n,
+                    alpha,
+                    a,
+                    a_stride.0 as isize,
+                    a_stride.1 as isize,
+                    b,
+                    b_stride.0 as isize,
+                    b_stride.1 as isize,
+                    beta,
+                    c,
+                    c_stride.0 as isize,
+                    c_stride.1 as isize,
                 );
-                res
             }
         }
+    };
 
-        // Matrix x ColumnVector = ColumnVector
-        impl<Data1: DataContainer<Item = $Scalar>, Data2: DataContainer<Item = $Scalar>>
-            Dot<GenericBaseMatrix<$Scalar, Data2, Dynamic, Fixed1>>
-            for GenericBaseMatrix<$Scalar, Data1, Dynamic, Dynamic>
-        {
-            type Output = GenericBaseMatrix<$Scalar, VectorContainer<$Scalar>, Dynamic, Fixed1>;
-
-            fn dot(
-                &self,
-                rhs: &GenericBaseMatrix<$Scalar, Data2, Dynamic, Fixed1>,
-            ) -> Self::Output {
-                let mut res = Self::Output::zeros_from_length(self.layout().dim().0);
-                <$Scalar>::matmul(
-                    num::cast::<f64, $Scalar>(1.0).unwrap(),
-                    &self,
-                    rhs,
-                    num::cast::<f64, $Scalar>(0.0).unwrap(),
-                    &mut res,
+    ($Scalar:ty, $Blas:ident, complex) => {
+        impl Gemm for $Scalar {
+            unsafe fn gemm(
+                m: IndexType,
+                k: IndexType,
+                n: IndexType,
+                alpha: Self,
+                a: *const Self,
+                a_stride: (IndexType, IndexType),
+                b: *const Self,
+                b_stride: (IndexType, IndexType),
+                beta: Self,
+                c: *mut Self,
+                c_stride: (IndexType, IndexType),
+            ) {
+                $Blas(
+                    CGemmOption::Standard,
+                    CGemmOption::Standard,
+                    m,
+                    k,
+                    n,
+                    [alpha.re(), alpha.im()],
+                    a as *const [<$Scalar as Scalar>::Real; 2],
+                    a_stride.0 as isize,
+                    a_stride.1 as isize,
+                    b as *const [<$Scalar as Scalar>::Real; 2],
+                    b_stride.0 as isize,
+                    b_stride.1 as isize,
+                    [beta.re(), beta.im()],
+                    c as *mut [<$Scalar as Scalar>::Real; 2],
+                    c_stride.0 as isize,
+                    c_stride.1 as isize,
                 );
-                res
             }
         }
     };
 }
 
-macro_rules! matmul_impl {
+gemm_impl!(f64, dgemm, real);
+gemm_impl!(f32, sgemm, real);
+gemm_impl!(c32, cgemm, complex);
+gemm_impl!(c64, zgemm, complex);
 
-    ($Scalar:ty, $Blas:ident, $RS1:ty, $CS1:ty, $RS2:ty, $CS2:ty, $RS3:ty, $CS3:ty, real) => {
+impl<
+        Item: Gemm,
+        Data1: DataContainer<Item = Item>,
+        Data2: DataContainer<Item = Item>,
+        Data3: DataContainerMut<Item = Item>,
+        RS1: SizeIdentifier,
+        CS1: SizeIdentifier,
+        RS2: SizeIdentifier,
+        CS2: SizeIdentifier,
+        RS3: SizeIdentifier,
+        CS3: SizeIdentifier,
+    > MatMul<Item, Data1, Data2, Data3, RS1, CS1, RS2, CS2, RS3, CS3> for Item
+{
+    fn matmul(
+        alpha: Item,
+        mat_a: &GenericBaseMatrix<Item, Data1, RS1, CS1>,
+        mat_b: &GenericBaseMatrix<Item, Data2, RS2, CS2>,
+        beta: Item,
+        mat_c: &mut GenericBaseMatrixMut<Item, Data3, RS3, CS3>,
+    ) {
+        let dim1 = mat_a.layout().dim();
+        let dim2 = mat_b.layout().dim();
+        let dim3 = mat_c.layout().dim();
+
+        assert!(
+            (dim1.1 == dim2.0) & (dim3.0 == dim1.0) & (dim3.1 == dim2.1),
+            "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
+            dim1,
+            dim2,
+            dim3
+        );
+
+        let a_stride = mat_a.layout().stride();
+        let b_stride = mat_b.layout().stride();
+        let c_stride = mat_c.layout().stride();
+
+        unsafe {
+            Item::gemm(
+                dim1.0,
+                dim1.1,
+                dim2.1,
+                alpha,
+                mat_a.get_pointer(),
+                a_stride,
+                mat_b.get_pointer(),
+                b_stride,
+                beta,
+                mat_c.get_pointer_mut(),
+                c_stride,
+            );
+        }
+    }
+}
 
-        impl<
-        Data1: DataContainer<Item = $Scalar>,
-        Data2: DataContainer<Item = $Scalar>,
-        Data3: DataContainerMut<Item = $Scalar>
->
+/// A matrix implementation that can be used as operand of [Dot].
+///
+/// Implementations backed by strided memory return a pointer to their data, which
+/// is then passed directly to the multiplication kernel. Lazy expressions use the
+/// default implementation and are evaluated into a temporary matrix.
+pub trait MatMulOperand: UnsafeRandomAccessByValue + Layout<Impl = DefaultLayout> {
+    /// Return a pointer to the strided memory of the matrix, or `None` if the
+    /// matrix needs to be evaluated first.
+    fn strided_pointer(&self) -> Option<*const Self::Item> {
+        None
+    }
+}
 
+impl<Item: Scalar, Data: DataContainer<Item = Item>, RS: SizeIdentifier, CS: SizeIdentifier>
+    MatMulOperand for crate::base_matrix::BaseMatrix<Item, Data, RS, CS>
+{
+    fn strided_pointer(&self) -> Option<*const Item> {
+        Some(self.get_pointer())
+    }
+}
 
-        MatMul<
-            $Scalar,
-            Data1,
-            Data2,
-            Data3,
-            $RS1,
-            $RS2,
-            $CS1,
-            $CS2,
-            $RS3,
-            $CS3>
+impl<
+        Item: Scalar,
+        MatImpl1: MatrixTrait<Item, RS, CS>,
+        MatImpl2: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for crate::addition::Addition<Item, MatImpl1, MatImpl2, RS, CS>
+{
+}
 
+impl<
+        Item: Scalar,
+        MatImpl1: MatrixTrait<Item, RS, CS>,
+        MatImpl2: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for crate::subtraction::Subtraction<Item, MatImpl1, MatImpl2, RS, CS>
+{
+}
 
-        for $Scalar {
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    MatMulOperand for crate::scalar_mult::ScalarMult<Item, MatImpl, RS, CS>
+{
+}
 
-            fn matmul(
-                alpha: $Scalar,
-                mat_a: &GenericBaseMatrix<$Scalar, Data1, $RS1, $CS1>,
-                mat_b: &GenericBaseMatrix<$Scalar, Data2, $RS2, $CS2>,
-                beta: $Scalar,
-                mat_c: &mut GenericBaseMatrixMut<$Scalar, Data3, $RS3, $CS3>
-            ) {
-                let dim1 = mat_a.layout().dim();
-                let dim2 = mat_b.layout().dim();
-                let dim3 = mat_c.layout().dim();
+impl<
+        Item: Scalar,
+        Op: crate::conversion::UnaryOp<Item>,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for crate::conversion::Unary<Item, Op, MatImpl, RS, CS>
+{
+}
 
-                assert!(
-                    (dim1.1 == dim2.0) & (dim3.0 == dim1.0) & (dim3.1 == dim2.1),
-                    "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
-                    dim1,
-                    dim2,
-                    dim3
-                );
+impl<
+        'a,
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for crate::permutation::Permuted<'a, Item, MatImpl, RS, CS>
+{
+}
 
-                let m = dim1.0 as usize;
-                let k = dim1.1 as usize;
-                let n = dim2.1 as usize;
-                let rsa = mat_a.layout().stride().0 as isize;
-                let csa = mat_a.layout().stride().1 as isize;
-                let rsb = mat_b.layout().stride().0 as isize;
-                let csb = mat_b.layout().stride().1 as isize;
-                let rsc = mat_c.layout().stride().0 as isize;
-                let csc = mat_c.layout().stride().1 as isize;
-
-                unsafe {
-                    $Blas(
-                        m,
-                        k,
-                        n,
-                        alpha,
-                        mat_a.get_pointer(),
-                        rsa,
-                        csa,
-                        mat_b.get_pointer(),
-                        rsb,
-                        csb,
-                        beta,
-                        mat_c.get_pointer_mut(),
-                        rsc,
-                        csc,
-                    );
-                }
-            }
+// The memory of an operand as seen by the multiplication kernel. If the operand
+// had to be evaluated the temporary is kept alive together with the pointer.
+struct Operand<Item: Scalar> {
+    ptr: *const Item,
+    stride: (IndexType, IndexType),
+    _temp: Option<MatrixD<Item>>,
+}
 
+impl<Item: Scalar> Operand<Item> {
+    fn new<Mat: MatMulOperand<Item = Item>>(mat: &Mat) -> Self {
+        if let Some(ptr) = mat.strided_pointer() {
+            return Self {
+                ptr,
+                stride: mat.layout().stride(),
+                _temp: None,
+            };
+        }
+
+        let dim = mat.layout().dim();
+        let mut temp = MatrixD::<Item>::zeros_from_dim(dim.0, dim.1);
+        unsafe {
+            for col in 0..dim.1 {
+                for row in 0..dim.0 {
+                    *temp.get_unchecked_mut(row, col) = mat.get_value_unchecked(row, col);
+                }
             }
+        }
+        Self {
+            ptr: temp.get_pointer(),
+            stride: temp.layout().stride(),
+            _temp: Some(temp),
+        }
+    }
+}
 
-        };
+/// Determines the type of the result of a matrix product from the row size type
+/// of the left operand and the column size type of the right operand.
+pub trait MatMulShape<Item: Scalar> {
+    type Output;
 
-        ($Scalar:ty, $Blas:ident, $RS1:ty, $CS1:ty, $RS2:ty, $CS2:ty, $RS3:ty, $CS3:ty, complex) => {
-
-            impl<
-            Data1: DataContainer<Item = $Scalar>,
-            Data2: DataContainer<Item = $Scalar>,
-            Data3: DataContainerMut<Item = $Scalar>
-    >
-
-
-            MatMul<
-                $Scalar,
-                Data1,
-                Data2,
-                Data3,
-                $RS1,
-                $RS2,
-                $CS1,
-                $CS2,
-                $RS3,
-                $CS3>
-
-
-            for $Scalar {
-
-                fn matmul(
-                    alpha: $Scalar,
-                    mat_a: &GenericBaseMatrix<$Scalar, Data1, $RS1, $CS1>,
-                    mat_b: &GenericBaseMatrix<$Scalar, Data2, $RS2, $CS2>,
-                    beta: $Scalar,
-                    mat_c: &mut GenericBaseMatrixMut<$Scalar, Data3, $RS3, $CS3>
-                ) {
-                    let dim1 = mat_a.layout().dim();
-                    let dim2 = mat_b.layout().dim();
-                    let dim3 = mat_c.layout().dim();
-
-                    assert!(
-                        (dim1.1 == dim2.0) & (dim3.0 == dim1.0) & (dim3.1 == dim2.1),
-                        "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
-                        dim1,
-                        dim2,
-                        dim3
-                    );
-
-                    let m = dim1.0 as usize;
-                    let k = dim1.1 as usize;
-                    let n = dim2.1 as usize;
-                    let rsa = mat_a.layout().stride().0 as isize;
-                    let csa = mat_a.layout().stride().1 as isize;
-                    let rsb = mat_b.layout().stride().0 as isize;
-                    let csb = mat_b.layout().stride().1 as isize;
-                    let rsc = mat_c.layout().stride().0 as isize;
-                    let csc = mat_c.layout().stride().1 as isize;
-
-                    let alpha = [alpha.re(), alpha.im()];
-                    let beta = [beta.re(), beta.im()];
-
-                    unsafe {
-                        $Blas(
-                            CGemmOption::Standard,
-                            CGemmOption::Standard,
-                            m,
-                            k,
-                            n,
-                            alpha,
-                            mat_a.get_pointer() as *const [<$Scalar as Scalar>::Real; 2],
-                            rsa,
-                            csa,
-                            mat_b.get_pointer() as *const [<$Scalar as Scalar>::Real; 2],
-                            rsb,
-                            csb,
-                            beta,
-                            mat_c.get_pointer_mut() as *mut [<$Scalar as Scalar>::Real; 2],
-                            rsc,
-                            csc,
-                        );
-                    }
-                }
+    /// Create a zero result with the given dimension.
+    fn zeros(dim: (IndexType, IndexType)) -> Self::Output;
 
-                }
+    /// Return a pointer to the memory of the result and its stride.
+    fn pointer_and_stride(result: &mut Self::Output) -> (*mut Item, (IndexType, IndexType));
+}
 
-            };
+macro_rules! matmul_shape_impl {
+    ($RS:ty, $CS:ty, $Output:ty, $dim:ident => $zeros:expr) => {
+        impl<Item: Scalar> MatMulShape<Item> for ($RS, $CS) {
+            type Output = $Output;
 
+            fn zeros($dim: (IndexType, IndexType)) -> Self::Output {
+                $zeros
+            }
 
+            fn pointer_and_stride(
+                result: &mut Self::Output,
+            ) -> (*mut Item, (IndexType, IndexType)) {
+                let stride = result.layout().stride();
+                (result.get_pointer_mut(), stride)
+            }
+        }
+    };
 }
 
-macro_rules! matmul_over_size_types {
-    ($RS1:ty, $CS1:ty, $RS2:ty, $CS2:ty, $RS3:ty, $CS3:ty) => {
-        matmul_impl!(f64, dgemm, $RS1, $CS1, $RS2, $CS2, $RS3, $CS3, real);
-        matmul_impl!(f32, sgemm, $RS1, $CS1, $RS2, $CS2, $RS3, $CS3, real);
-        matmul_impl!(c32, cgemm, $RS1, $CS1, $RS2, $CS2, $RS3, $CS3, complex);
-        matmul_impl!(c64, zgemm, $RS1, $CS1, $RS2, $CS2, $RS3, $CS3, complex);
+macro_rules! matmul_shape_fixed {
+    ($RS:ident, $CS:ident) => {
+        matmul_shape_impl!(
+            $RS,
+            $CS,
+            GenericBaseMatrixMut<Item, ArrayContainer<Item, { $RS::N * $CS::N }>, $RS, $CS>,
+            _dim => <Self::Output>::zeros_from_dim()
+        );
     };
 }
 
 // matrix x matrix = matrix
-matmul_over_size_types!(Dynamic, Dynamic, Dynamic, Dynamic, Dynamic, Dynamic);
+matmul_shape_impl!(Dynamic, Dynamic, MatrixD<Item>, dim => MatrixD::<Item>::zeros_from_dim(dim.0, dim.1));
 
 // matrix x col_vector = col_vector
-matmul_over_size_types!(Dynamic, Dynamic, Dynamic, Fixed1, Dynamic, Fixed1);
+matmul_shape_impl!(Dynamic, Fixed1, ColumnVectorD<Item>, dim => ColumnVectorD::<Item>::zeros_from_length(dim.0));
 
 // row_vector x matrix = row_vector
-matmul_over_size_types!(Fixed1, Dynamic, Dynamic, Dynamic, Fixed1, Dynamic);
+matmul_shape_impl!(Fixed1, Dynamic, RowVectorD<Item>, dim => RowVectorD::<Item>::zeros_from_length(dim.1));
+
+// Products of fixed and dynamic operands give dynamic matrices.
+matmul_shape_impl!(Fixed2, Dynamic, MatrixD<Item>, dim => MatrixD::<Item>::zeros_from_dim(dim.0, dim.1));
+matmul_shape_impl!(Fixed3, Dynamic, MatrixD<Item>, dim => MatrixD::<Item>::zeros_from_dim(dim.0, dim.1));
+matmul_shape_impl!(Dynamic, Fixed2, MatrixD<Item>, dim => MatrixD::<Item>::zeros_from_dim(dim.0, dim.1));
+matmul_shape_impl!(Dynamic, Fixed3, MatrixD<Item>, dim => MatrixD::<Item>::zeros_from_dim(dim.0, dim.1));
+
+matmul_shape_fixed!(Fixed2, Fixed2);
+matmul_shape_fixed!(Fixed1, Fixed2);
+
+matmul_shape_fixed!(Fixed3, Fixed3);
+matmul_shape_fixed!(Fixed1, Fixed3);
+
+matmul_shape_fixed!(Fixed2, Fixed3);
+matmul_shape_fixed!(Fixed3, Fixed2);
+
+matmul_shape_fixed!(Fixed2, Fixed1);
+matmul_shape_fixed!(Fixed3, Fixed1);
+
+// row_vector x col_vector = scalar
+impl<Item: Scalar> MatMulShape<Item> for (Fixed1, Fixed1) {
+    type Output = Item;
+
+    fn zeros(_dim: (IndexType, IndexType)) -> Self::Output {
+        <Item as num::Zero>::zero()
+    }
+
+    fn pointer_and_stride(result: &mut Self::Output) -> (*mut Item, (IndexType, IndexType)) {
+        (result as *mut Item, (1, 1))
+    }
+}
+
+// Compute `c = alpha * mat_a * mat_b + beta * c` for operands of compatible dimensions
+// and a result `c` of dimension `(mat_a.rows, mat_b.cols)` with the given stride.
+unsafe fn gemm_operands<
+    Item: Gemm,
+    Mat1: MatMulOperand<Item = Item>,
+    Mat2: MatMulOperand<Item = Item>,
+>(
+    alpha: Item,
+    mat_a: &Mat1,
+    mat_b: &Mat2,
+    beta: Item,
+    c: *mut Item,
+    c_stride: (IndexType, IndexType),
+) {
+    let dim1 = mat_a.layout().dim();
+    let dim2 = mat_b.layout().dim();
+    let mat_a = Operand::new(mat_a);
+    let mat_b = Operand::new(mat_b);
+
+    Item::gemm(
+        dim1.0,
+        dim1.1,
+        dim2.1,
+        alpha,
+        mat_a.ptr,
+        mat_a.stride,
+        mat_b.ptr,
+        mat_b.stride,
+        beta,
+        c,
+        c_stride,
+    );
+}
+
+impl<Item, MatImpl1, MatImpl2, RS1, CS1, RS2, CS2> Dot<Matrix<Item, MatImpl2, RS2, CS2>>
+    for Matrix<Item, MatImpl1, RS1, CS1>
+where
+    Item: Gemm,
+    MatImpl1: MatrixTrait<Item, RS1, CS1> + MatMulOperand,
+    MatImpl2: MatrixTrait<Item, RS2, CS2> + MatMulOperand,
+    RS1: SizeIdentifier,
+    CS1: SizeIdentifier,
+    RS2: SizeIdentifier,
+    CS2: SizeIdentifier,
+    (RS1, CS2): MatMulShape<Item>,
+{
+    type Output = <(RS1, CS2) as MatMulShape<Item>>::Output;
+
+    fn dot(&self, rhs: &Matrix<Item, MatImpl2, RS2, CS2>) -> Self::Output {
+        let dim1 = self.layout().dim();
+        let dim2 = rhs.layout().dim();
+
+        assert_eq!(
+            dim1.1, dim2.0,
+            "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}",
+            dim1, dim2
+        );
+
+        let mut res = <(RS1, CS2) as MatMulShape<Item>>::zeros((dim1.0, dim2.1));
+        let (ptr, stride) = <(RS1, CS2) as MatMulShape<Item>>::pointer_and_stride(&mut res);
+
+        unsafe {
+            gemm_operands(
+                num::cast::<f64, Item>(1.0).unwrap(),
+                self,
+                rhs,
+                num::cast::<f64, Item>(0.0).unwrap(),
+                ptr,
+                stride,
+            );
+        }
+        res
+    }
+}
+
+macro_rules! apply_impl {
+    ($Domain:ident) => {
+        impl<Item: Gemm, MatImpl: MatrixTrait<Item, Dynamic, Dynamic> + MatMulOperand>
+            Apply<$Domain<Item>> for Matrix<Item, MatImpl, Dynamic, Dynamic>
+        {
+            type T = Item;
+            type Range = $Domain<Item>;
+
+            fn apply(&self, alpha: Self::T, x: &$Domain<Item>, y: &mut Self::Range, beta: Self::T) {
+                let dim1 = self.layout().dim();
+                let dim2 = x.layout().dim();
+                let dim3 = y.layout().dim();
+
+                assert!(
+                    (dim1.1 == dim2.0) & (dim3.0 == dim1.0) & (dim3.1 == dim2.1),
+                    "Matrix multiply incompatible dimensions for C = A * B: A = {:#?}, B = {:#?}, C = {:#?}",
+                    dim1,
+                    dim2,
+                    dim3
+                );
+
+                let stride = y.layout().stride();
+                unsafe { gemm_operands(alpha, self, x, beta, y.get_pointer_mut(), stride) };
+            }
+        }
+    };
+}
 
-dot_impl!(f64);
-dot_impl!(f32);
-dot_impl!(c32);
-dot_impl!(c64);
+apply_impl!(ColumnVectorD);
+apply_impl!(MatrixD);
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
 
     use super::*;
@@ -482,4 +699,160 @@ mod test {
     col_matvec_test!(f32, test_col_matvec_f32);
     col_matvec_test!(c32, test_col_matvec_c32);
     col_matvec_test!(c64, test_col_matvec_c64);
+
+    #[test]
+    fn test_dot_expression() {
+        let mut mat_a = MatrixD::<f64>::zeros_from_dim(4, 3);
+        let mut mat_b = MatrixD::<f64>::zeros_from_dim(4, 3);
+        let mut mat_c = MatrixD::<f64>::zeros_from_dim(3, 5);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        mat_a.fill_from_rand_standard_normal(&mut rng);
+        mat_b.fill_from_rand_standard_normal(&mut rng);
+        mat_c.fill_from_rand_standard_normal(&mut rng);
+
+        let actual = (&mat_a + &mat_b).dot(&(2.0 * &mat_c));
+        let sum = (&mat_a + &mat_b).eval();
+        let scaled = (2.0 * &mat_c).eval();
+        let expected = sum.dot(&scaled);
+
+        for row in 0..4 {
+            for col in 0..5 {
+                assert_ulps_eq!(actual[[row, col]], expected[[row, col]], max_ulps = 10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dot_fixed() {
+        let mut mat_a = Matrix23::<f64>::zeros_from_dim();
+        let mut mat_b = Matrix32::<f64>::zeros_from_dim();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        mat_a.fill_from_rand_standard_normal(&mut rng);
+        mat_b.fill_from_rand_standard_normal(&mut rng);
+
+        let actual: Matrix22<f64> = mat_a.dot(&mat_b);
+        let mut expected = Matrix22::<f64>::zeros_from_dim();
+        matmul_expect(1.0, &mat_a, &mat_b, 0.0, &mut expected);
+
+        for row in 0..2 {
+            for col in 0..2 {
+                assert_ulps_eq!(actual[[row, col]], expected[[row, col]], max_ulps = 10);
+            }
+        }
+
+        let mut vec = ColumnVector3::<f64>::zeros_from_dim();
+        vec.fill_from_rand_standard_normal(&mut rng);
+        let actual: ColumnVector2<f64> = mat_a.dot(&vec);
+        let mut expected = ColumnVector2::<f64>::zeros_from_dim();
+        matmul_expect(1.0, &mat_a, &vec, 0.0, &mut expected);
+
+        for row in 0..2 {
+            assert_ulps_eq!(actual[[row, 0]], expected[[row, 0]], max_ulps = 10);
+        }
+    }
+
+    #[test]
+    fn test_dot_inner_outer() {
+        let mut row_vec = RowVectorD::<c64>::zeros_from_length(4);
+        let mut col_vec = ColumnVectorD::<c64>::zeros_from_length(4);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        row_vec.fill_from_rand_standard_normal(&mut rng);
+        col_vec.fill_from_rand_standard_normal(&mut rng);
+
+        let inner: c64 = row_vec.dot(&col_vec);
+        let mut expected = c64::new(0.0, 0.0);
+        for index in 0..4 {
+            expected += row_vec[[0, index]] * col_vec[[index, 0]];
+        }
+        assert_ulps_eq!(inner, expected, max_ulps = 10);
+
+        let outer: MatrixD<c64> = col_vec.dot(&row_vec);
+        assert_eq!(outer.layout().dim(), (4, 4));
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_ulps_eq!(
+                    outer[[row, col]],
+                    col_vec[[row, 0]] * row_vec[[0, col]],
+                    max_ulps = 10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dot_strided_block() {
+        let mut mat_a = MatrixD::<f32>::zeros_from_dim(5, 5);
+        let mut vec = ColumnVectorD::<f32>::zeros_from_length(2);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        mat_a.fill_from_rand_standard_normal(&mut rng);
+        vec.fill_from_rand_standard_normal(&mut rng);
+
+        let block = mat_a.block((1, 2), (3, 2));
+        let actual = block.dot(&vec);
+        let expected = block.eval().dot(&vec);
+
+        for row in 0..3 {
+            assert_ulps_eq!(actual[[row, 0]], expected[[row, 0]], max_ulps = 10);
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut mat_a = MatrixD::<c64>::zeros_from_dim(3, 4);
+        let mut mat_x = MatrixD::<c64>::zeros_from_dim(4, 2);
+        let mut mat_y = MatrixD::<c64>::zeros_from_dim(3, 2);
+
+        let dist = StandardNormal;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        mat_a.fill_from_rand_standard_normal(&mut rng);
+        mat_x.fill_from_rand_standard_normal(&mut rng);
+        mat_y.fill_from_rand_standard_normal(&mut rng);
+
+        let alpha = c64::random_scalar(&mut rng, &dist);
+        let beta = c64::random_scalar(&mut rng, &dist);
+
+        let mut expected = MatrixD::<c64>::zeros_from_dim(3, 2);
+        for index in 0..6 {
+            *expected.get1d_mut(index).unwrap() = mat_y.get1d_value(index);
+        }
+        matmul_expect(alpha, &mat_a, &mat_x, beta, &mut expected);
+        mat_a.apply(alpha, &mat_x, &mut mat_y, beta);
+
+        for index in 0..6 {
+            assert_ulps_eq!(
+                mat_y.get1d_value(index),
+                expected.get1d_value(index),
+                max_ulps = 100
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_transpose() {
+        let mut mat_a = MatrixD::<f64>::zeros_from_dim(4, 3);
+        let mut vec_x = ColumnVectorD::<f64>::zeros_from_length(4);
+        let mut vec_y = ColumnVectorD::<f64>::zeros_from_length(3);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        mat_a.fill_from_rand_standard_normal(&mut rng);
+        vec_x.fill_from_rand_standard_normal(&mut rng);
+        vec_y.fill_from_rand_standard_normal(&mut rng);
+
+        let mut expected = ColumnVectorD::<f64>::zeros_from_length(3);
+        for row in 0..3 {
+            let sum: f64 = (0..4).map(|k| mat_a[[k, row]] * vec_x[[k, 0]]).sum();
+            expected[[row, 0]] = 2.0 * sum - vec_y[[row, 0]];
+        }
+
+        let transpose = Matrix::from_ref(&mat_a).transpose();
+        transpose.apply(2.0, &vec_x, &mut vec_y, -1.0);
+
+        for row in 0..3 {
+            assert_ulps_eq!(vec_y[[row, 0]], expected[[row, 0]], max_ulps = 100);
+        }
+    }
 }
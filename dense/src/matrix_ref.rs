@@ -15,10 +15,11 @@
 //! both terms passed onto the addition type, which takes ownership of both terms.
 
 use crate::matrix::Matrix;
+use crate::matrix_multiply::MatMulOperand;
 use crate::traits::*;
 use crate::types::{IndexType, Scalar};
 use crate::DefaultLayout;
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 // A struct that implements [MatrixTrait] by holding a reference
 // to a matrix and forwarding all matrix operations to the held reference.
@@ -122,6 +123,20 @@ macro_rules! matrix_ref_traits {
                 self.0.get1d_value_unchecked(index)
             }
         }
+
+        impl<
+                'a,
+                Item: Scalar,
+                MatImpl: MatrixTrait<Item, RS, CS> + MatMulOperand,
+                RS: SizeIdentifier,
+                CS: SizeIdentifier,
+            > MatMulOperand for $MatrixRefType<'a, Item, MatImpl, RS, CS>
+        {
+            #[inline]
+            fn strided_pointer(&self) -> Option<*const Item> {
+                self.0.strided_pointer()
+            }
+        }
     };
 }
 
@@ -0,0 +1,511 @@
+//! Matrices stored in memory-mapped files.
+//!
+//! This module requires the `mmap` feature. It provides the data containers
+//! [MmapContainer] (read-only) and [MmapContainerMut] (read-write), which are backed by
+//! a memory-mapped file. Matrices built on these containers are only paged into memory
+//! when their elements are accessed, so that [block](crate::matrix::Matrix::block) views
+//! and [MatMul](crate::MatMul) can operate on tiles of matrices that are larger than the
+//! available memory.
+//!
+//! A matrix file starts with a header of [HEADER_SIZE] bytes followed by the raw matrix
+//! data. The header stores the following fields in little-endian byte order.
+//! - A magic byte sequence identifying the file format.
+//! - The format version and the [ScalarType] of the elements.
+//! - The dimension and the stride of the matrix.
+//!
+//! The matrix data is stored in native byte order.
+//!
+//! ```no_run
+//! # use rlst_dense::*;
+//! # use rlst_dense::mmap::*;
+//! let mut mat = MmapMatrixMut::<f64>::create("matrix.bin", 100_000, 100_000).unwrap();
+//! mat[[5, 7]] = 1.0;
+//! mat.flush().unwrap();
+//!
+//! let mat = MmapMatrix::<f64>::open("matrix.bin").unwrap();
+//! let tile = mat.block((0, 0), (1000, 1000));
+//! assert_eq!(tile[[5, 7]], 1.0);
+//! ```
+
+use crate::base_matrix::BaseMatrix;
+use crate::data_container::{DataContainer, DataContainerMut};
+use crate::matrix::Matrix;
+use crate::traits::*;
+use crate::types::{c32, c64, IndexType, Scalar};
+use crate::DefaultLayout;
+use memmap2::{Mmap, MmapMut};
+use rlst_common::types::{RlstError, RlstResult};
+use std::fs::{File, OpenOptions};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Size in bytes of the header of a matrix file.
+pub const HEADER_SIZE: usize = 64;
+
+const MAGIC: [u8; 8] = *b"RLSTMAT\0";
+const VERSION: u32 = 1;
+
+/// The scalar type of the elements stored in a matrix file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ScalarType {
+    F32 = 1,
+    F64 = 2,
+    C32 = 3,
+    C64 = 4,
+}
+
+impl ScalarType {
+    fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(ScalarType::F32),
+            2 => Some(ScalarType::F64),
+            3 => Some(ScalarType::C32),
+            4 => Some(ScalarType::C64),
+            _ => None,
+        }
+    }
+}
+
+/// Scalar types that can be stored in a matrix file.
+pub trait MmapScalar: Scalar {
+    const SCALAR_TYPE: ScalarType;
+}
+
+macro_rules! mmap_scalar_impl {
+    ($Scalar:ty, $Type:ident) => {
+        impl MmapScalar for $Scalar {
+            const SCALAR_TYPE: ScalarType = ScalarType::$Type;
+        }
+    };
+}
+
+mmap_scalar_impl!(f32, F32);
+mmap_scalar_impl!(f64, F64);
+mmap_scalar_impl!(c32, C32);
+mmap_scalar_impl!(c64, C64);
+
+fn overflow_error() -> RlstError {
+    RlstError::InvalidFileHeader("Matrix size overflows the address space.".to_string())
+}
+
+/// The header of a matrix file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MmapHeader {
+    pub scalar_type: ScalarType,
+    pub dim: (IndexType, IndexType),
+    pub stride: (IndexType, IndexType),
+}
+
+impl MmapHeader {
+    /// Return the number of elements stored in the file.
+    ///
+    /// Empty matrices store no elements. Returns [RlstError::InvalidFileHeader] if the
+    /// number of elements overflows.
+    pub fn number_of_elements(&self) -> RlstResult<IndexType> {
+        let (last_row, last_col) = match (self.dim.0.checked_sub(1), self.dim.1.checked_sub(1)) {
+            (Some(last_row), Some(last_col)) => (last_row, last_col),
+            _ => return Ok(0),
+        };
+        self.stride
+            .0
+            .checked_mul(last_row)
+            .zip(self.stride.1.checked_mul(last_col))
+            .and_then(|(row_offset, col_offset)| row_offset.checked_add(col_offset))
+            .and_then(|offset| offset.checked_add(1))
+            .ok_or_else(overflow_error)
+    }
+
+    /// Return the size in bytes of a file storing the matrix with element type `Item`.
+    fn file_size<Item: MmapScalar>(&self) -> RlstResult<usize> {
+        self.number_of_elements()?
+            .checked_mul(std::mem::size_of::<Item>())
+            .and_then(|size| size.checked_add(HEADER_SIZE))
+            .ok_or_else(overflow_error)
+    }
+
+    fn to_bytes(self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0; HEADER_SIZE];
+        bytes[0..8].copy_from_slice(&MAGIC);
+        bytes[8..12].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[12..16].copy_from_slice(&(self.scalar_type as u32).to_le_bytes());
+        let fields = [self.dim.0, self.dim.1, self.stride.0, self.stride.1];
+        for (index, value) in fields.iter().enumerate() {
+            let start = 16 + 8 * index;
+            bytes[start..start + 8].copy_from_slice(&(*value as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> RlstResult<Self> {
+        if bytes.len() < HEADER_SIZE || bytes[0..8] != MAGIC {
+            return Err(RlstError::InvalidFileHeader(
+                "Not an rlst matrix file.".to_string(),
+            ));
+        }
+        let read_u32 =
+            |start: usize| u32::from_le_bytes(bytes[start..start + 4].try_into().unwrap());
+        let read_u64 = |start: usize| {
+            IndexType::try_from(u64::from_le_bytes(
+                bytes[start..start + 8].try_into().unwrap(),
+            ))
+            .map_err(|_| overflow_error())
+        };
+
+        let version = read_u32(8);
+        if version != VERSION {
+            return Err(RlstError::InvalidFileHeader(format!(
+                "Unsupported file format version {}.",
+                version
+            )));
+        }
+        let scalar_type = ScalarType::from_u32(read_u32(12)).ok_or_else(|| {
+            RlstError::InvalidFileHeader(format!("Unknown scalar type {}.", read_u32(12)))
+        })?;
+
+        Ok(Self {
+            scalar_type,
+            dim: (read_u64(16)?, read_u64(24)?),
+            stride: (read_u64(32)?, read_u64(40)?),
+        })
+    }
+
+    /// Return [RlstError::InvalidFileHeader] if the header does not describe elements of type `Item`.
+    fn check_scalar_type<Item: MmapScalar>(&self) -> RlstResult<()> {
+        if self.scalar_type != Item::SCALAR_TYPE {
+            return Err(RlstError::InvalidFileHeader(format!(
+                "File stores scalar type {:?} but {:?} was requested.",
+                self.scalar_type,
+                Item::SCALAR_TYPE
+            )));
+        }
+        Ok(())
+    }
+
+    /// Read and validate the header of a mapped file for the element type `Item`.
+    fn read<Item: MmapScalar>(bytes: &[u8]) -> RlstResult<Self> {
+        let header = Self::from_bytes(bytes)?;
+        header.check_scalar_type::<Item>()?;
+        let required = header.file_size::<Item>()?;
+        if bytes.len() < required {
+            return Err(RlstError::InvalidFileHeader(format!(
+                "File has {} bytes but the header requires {} bytes.",
+                bytes.len(),
+                required
+            )));
+        }
+        Ok(header)
+    }
+}
+
+/// A read-only data container backed by a memory-mapped file.
+pub struct MmapContainer<Item: MmapScalar> {
+    mmap: Mmap,
+    nelems: IndexType,
+    _marker: PhantomData<Item>,
+}
+
+/// A read-write data container backed by a memory-mapped file.
+pub struct MmapContainerMut<Item: MmapScalar> {
+    mmap: MmapMut,
+    nelems: IndexType,
+    _marker: PhantomData<Item>,
+}
+
+/// A read-only matrix stored in a memory-mapped file.
+pub type MmapMatrix<Item> =
+    Matrix<Item, BaseMatrix<Item, MmapContainer<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>;
+
+/// A read-write matrix stored in a memory-mapped file.
+pub type MmapMatrixMut<Item> =
+    Matrix<Item, BaseMatrix<Item, MmapContainerMut<Item>, Dynamic, Dynamic>, Dynamic, Dynamic>;
+
+impl<Item: MmapScalar> MmapContainer<Item> {
+    /// Map the file at `path` read-only and return the container and its header.
+    pub fn open<P: AsRef<Path>>(path: P) -> RlstResult<(Self, MmapHeader)> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+        let header = MmapHeader::read::<Item>(&mmap)?;
+        Ok((
+            Self {
+                mmap,
+                nelems: header.number_of_elements()?,
+                _marker: PhantomData,
+            },
+            header,
+        ))
+    }
+}
+
+impl<Item: MmapScalar> MmapContainerMut<Item> {
+    /// Create a new file at `path` for the matrix described by `header`.
+    ///
+    /// An existing file is truncated. The matrix data is initialized with zeros.
+    /// Returns [RlstError::InvalidFileHeader] if the scalar type of `header` is not
+    /// the one of `Item`.
+    pub fn create<P: AsRef<Path>>(path: P, header: MmapHeader) -> RlstResult<Self> {
+        header.check_scalar_type::<Item>()?;
+        let nelems = header.number_of_elements()?;
+        let file_size = header.file_size::<Item>()?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(file_size as u64)?;
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        mmap[0..HEADER_SIZE].copy_from_slice(&header.to_bytes());
+        Ok(Self {
+            mmap,
+            nelems,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Map the file at `path` for reading and writing and return the container and its header.
+    pub fn open<P: AsRef<Path>>(path: P) -> RlstResult<(Self, MmapHeader)> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        let header = MmapHeader::read::<Item>(&mmap)?;
+        Ok((
+            Self {
+                mmap,
+                nelems: header.number_of_elements()?,
+                _marker: PhantomData,
+            },
+            header,
+        ))
+    }
+
+    /// Write outstanding changes to disk.
+    pub fn flush(&self) -> RlstResult<()> {
+        Ok(self.mmap.flush()?)
+    }
+}
+
+impl<Item: MmapScalar> DataContainer for MmapContainer<Item> {
+    type Item = Item;
+
+    unsafe fn get_unchecked_value(&self, index: IndexType) -> Self::Item {
+        *self.get_pointer().add(index)
+    }
+
+    unsafe fn get_unchecked(&self, index: IndexType) -> &Self::Item {
+        &*self.get_pointer().add(index)
+    }
+
+    fn get_pointer(&self) -> *const Self::Item {
+        // The mapping is page aligned, so the data after the header is aligned for `Item`.
+        unsafe { self.mmap.as_ptr().add(HEADER_SIZE) as *const Item }
+    }
+
+    fn number_of_elements(&self) -> IndexType {
+        self.nelems
+    }
+}
+
+impl<Item: MmapScalar> DataContainer for MmapContainerMut<Item> {
+    type Item = Item;
+
+    unsafe fn get_unchecked_value(&self, index: IndexType) -> Self::Item {
+        *self.get_pointer().add(index)
+    }
+
+    unsafe fn get_unchecked(&self, index: IndexType) -> &Self::Item {
+        &*self.get_pointer().add(index)
+    }
+
+    fn get_pointer(&self) -> *const Self::Item {
+        unsafe { self.mmap.as_ptr().add(HEADER_SIZE) as *const Item }
+    }
+
+    fn number_of_elements(&self) -> IndexType {
+        self.nelems
+    }
+}
+
+impl<Item: MmapScalar> DataContainerMut for MmapContainerMut<Item> {
+    unsafe fn get_unchecked_mut(&mut self, index: IndexType) -> &mut Self::Item {
+        &mut *self.get_pointer_mut().add(index)
+    }
+
+    fn get_pointer_mut(&mut self) -> *mut Self::Item {
+        unsafe { self.mmap.as_mut_ptr().add(HEADER_SIZE) as *mut Item }
+    }
+}
+
+impl<Item: MmapScalar> MmapMatrix<Item> {
+    /// Open the matrix file at `path` read-only.
+    pub fn open<P: AsRef<Path>>(path: P) -> RlstResult<Self> {
+        let (data, header) = MmapContainer::open(path)?;
+        Ok(Self::from_data(
+            data,
+            DefaultLayout::new(header.dim, header.stride),
+        ))
+    }
+}
+
+impl<Item: MmapScalar> MmapMatrixMut<Item> {
+    /// Create a new zero matrix file at `path` with column-major layout.
+    ///
+    /// An existing file is truncated.
+    pub fn create<P: AsRef<Path>>(path: P, rows: IndexType, cols: IndexType) -> RlstResult<Self> {
+        let header = MmapHeader {
+            scalar_type: Item::SCALAR_TYPE,
+            dim: (rows, cols),
+            stride: (1, rows),
+        };
+        let data = MmapContainerMut::create(path, header)?;
+        Ok(Self::from_data(
+            data,
+            DefaultLayout::new(header.dim, header.stride),
+        ))
+    }
+
+    /// Open the matrix file at `path` for reading and writing.
+    pub fn open<P: AsRef<Path>>(path: P) -> RlstResult<Self> {
+        let (data, header) = MmapContainerMut::open(path)?;
+        Ok(Self::from_data(
+            data,
+            DefaultLayout::new(header.dim, header.stride),
+        ))
+    }
+
+    /// Write outstanding changes to disk.
+    pub fn flush(&self) -> RlstResult<()> {
+        self.data_container().flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::MatrixD;
+    use crate::Dot;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rlst_mmap_{}_{}.bin", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_create_and_open() {
+        let path = temp_path("create_and_open");
+        {
+            let mut mat = MmapMatrixMut::<f64>::create(&path, 4, 3).unwrap();
+            mat[[2, 1]] = 5.0;
+            mat[[3, 2]] = -1.0;
+            mat.flush().unwrap();
+        }
+
+        let mat = MmapMatrix::<f64>::open(&path).unwrap();
+        assert_eq!(mat.layout().dim(), (4, 3));
+        assert_eq!(mat.layout().stride(), (1, 4));
+        assert_eq!(mat[[2, 1]], 5.0);
+        assert_eq!(mat[[3, 2]], -1.0);
+        assert_eq!(mat[[0, 0]], 0.0);
+
+        {
+            let mut mat = MmapMatrixMut::<f64>::open(&path).unwrap();
+            mat[[0, 0]] = 2.0;
+        }
+        assert_eq!(MmapMatrix::<f64>::open(&path).unwrap()[[0, 0]], 2.0);
+
+        assert!(matches!(
+            MmapMatrix::<f32>::open(&path),
+            Err(RlstError::InvalidFileHeader(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_block_matmul() {
+        let path = temp_path("block_matmul");
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut expected = MatrixD::<f64>::zeros_from_dim(6, 5);
+        expected.fill_from_rand_standard_normal(&mut rng);
+        {
+            let mut mat = MmapMatrixMut::<f64>::create(&path, 6, 5).unwrap();
+            for row in 0..6 {
+                for col in 0..5 {
+                    mat[[row, col]] = expected[[row, col]];
+                }
+            }
+        }
+
+        let mat = MmapMatrix::<f64>::open(&path).unwrap();
+        let tile = mat.block((2, 1), (3, 4));
+        let expected_tile = expected.block((2, 1), (3, 4));
+        let mut rhs = MatrixD::<f64>::zeros_from_dim(4, 2);
+        rhs.fill_from_rand_standard_normal(&mut rng);
+
+        let actual = tile.dot(&rhs);
+        let expected = expected_tile.dot(&rhs);
+        for row in 0..3 {
+            for col in 0..2 {
+                assert_relative_eq!(actual[[row, col]], expected[[row, col]], epsilon = 1E-14);
+            }
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_file() {
+        let path = temp_path("invalid_file");
+        std::fs::write(&path, [0u8; 100]).unwrap();
+        assert!(matches!(
+            MmapMatrix::<f64>::open(&path),
+            Err(RlstError::InvalidFileHeader(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_create_with_wrong_scalar_type() {
+        let path = temp_path("wrong_scalar_type");
+        let header = MmapHeader {
+            scalar_type: ScalarType::C64,
+            dim: (2, 2),
+            stride: (1, 2),
+        };
+        assert!(matches!(
+            MmapContainerMut::<f64>::create(&path, header),
+            Err(RlstError::InvalidFileHeader(_))
+        ));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_overflowing_header() {
+        let path = temp_path("overflowing_header");
+        let header = MmapHeader {
+            scalar_type: ScalarType::F64,
+            dim: (3, 3),
+            stride: (1, IndexType::MAX / 2),
+        };
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend_from_slice(&[0u8; 48]);
+        std::fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            MmapMatrix::<f64>::open(&path),
+            Err(RlstError::InvalidFileHeader(_))
+        ));
+        assert!(header.number_of_elements().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_empty_matrix() {
+        let path = temp_path("empty_matrix");
+        MmapMatrixMut::<f64>::create(&path, 0, 3).unwrap();
+        let mat = MmapMatrix::<f64>::open(&path).unwrap();
+        assert_eq!(mat.layout().dim(), (0, 3));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,260 @@
+//! Row and column permutations of matrices.
+//!
+//! This module applies a [Permutation] to the rows or columns of a matrix, either in place
+//! with [permute_rows](Matrix::permute_rows) and [permute_columns](Matrix::permute_columns),
+//! or lazily with [permuted_rows](Matrix::permuted_rows) and
+//! [permuted_columns](Matrix::permuted_columns). Permuting the rows with `perm` produces the
+//! matrix whose row `i` is row `perm.get(i)` of the original matrix.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_dense::permutation::*;
+//! let mut mat = MatrixD::<f64>::zeros_from_dim(3, 2);
+//! mat[[0, 0]] = 1.0;
+//! mat[[2, 1]] = 2.0;
+//!
+//! let perm = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+//! mat.permute_rows(&perm);
+//! assert_eq!(mat[[1, 0]], 1.0);
+//! assert_eq!(mat[[0, 1]], 2.0);
+//!
+//! // Undo the permutation lazily.
+//! let inverse = perm.inverse();
+//! let original = mat.permuted_rows(&inverse).eval();
+//! assert_eq!(original[[0, 0]], 1.0);
+//! ```
+
+use crate::base_matrix::BaseMatrix;
+use crate::data_container::DataContainerMut;
+use crate::matrix::Matrix;
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use crate::DefaultLayout;
+use core::marker::PhantomData;
+
+pub use rlst_common::permutation::Permutation;
+
+/// The axis of a matrix to which a permutation is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermutedAxis {
+    Rows,
+    Columns,
+}
+
+/// This type represents a matrix with permuted rows or columns.
+pub type PermutedMat<'a, Item, MatImpl, RS, CS> =
+    Matrix<Item, Permuted<'a, Item, MatImpl, RS, CS>, RS, CS>;
+
+/// A structure holding a matrix and a permutation of its rows or columns.
+/// On random access the permuted index is used to access the matrix.
+pub struct Permuted<'a, Item, MatImpl, RS, CS>(
+    Matrix<Item, MatImpl, RS, CS>,
+    &'a Permutation,
+    PermutedAxis,
+    PhantomData<Item>,
+)
+where
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    MatImpl: MatrixTrait<Item, RS, CS>;
+
+impl<
+        'a,
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > Permuted<'a, Item, MatImpl, RS, CS>
+{
+    pub fn new(
+        mat: Matrix<Item, MatImpl, RS, CS>,
+        perm: &'a Permutation,
+        axis: PermutedAxis,
+    ) -> Self {
+        assert_permutation_length(mat.layout().dim(), perm, axis);
+        Self(mat, perm, axis, PhantomData)
+    }
+}
+
+fn assert_permutation_length(dim: (IndexType, IndexType), perm: &Permutation, axis: PermutedAxis) {
+    let expected = match axis {
+        PermutedAxis::Rows => dim.0,
+        PermutedAxis::Columns => dim.1,
+    };
+    assert_eq!(
+        perm.len(),
+        expected,
+        "Permutation of length {} does not match matrix of dimension {:?}.",
+        perm.len(),
+        dim
+    );
+}
+
+impl<
+        'a,
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > SizeType for Permuted<'a, Item, MatImpl, RS, CS>
+{
+    type R = RS;
+    type C = CS;
+}
+
+impl<
+        'a,
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > Layout for Permuted<'a, Item, MatImpl, RS, CS>
+{
+    type Impl = DefaultLayout;
+
+    #[inline]
+    fn layout(&self) -> &Self::Impl {
+        self.0.layout()
+    }
+}
+
+impl<
+        'a,
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > UnsafeRandomAccessByValue for Permuted<'a, Item, MatImpl, RS, CS>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, row: IndexType, col: IndexType) -> Self::Item {
+        match self.2 {
+            PermutedAxis::Rows => self.0.get_value_unchecked(self.1.get(row), col),
+            PermutedAxis::Columns => self.0.get_value_unchecked(row, self.1.get(col)),
+        }
+    }
+
+    #[inline]
+    unsafe fn get1d_value_unchecked(&self, index: IndexType) -> Self::Item {
+        let (row, col) = self.layout().convert_1d_2d(index);
+        self.get_value_unchecked(row, col)
+    }
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, MatImpl, RS, CS>
+{
+    /// Lazily permute the rows of the matrix.
+    pub fn permuted_rows(self, perm: &Permutation) -> PermutedMat<'_, Item, MatImpl, RS, CS> {
+        Matrix::new(Permuted::new(self, perm, PermutedAxis::Rows))
+    }
+
+    /// Lazily permute the columns of the matrix.
+    pub fn permuted_columns(self, perm: &Permutation) -> PermutedMat<'_, Item, MatImpl, RS, CS> {
+        Matrix::new(Permuted::new(self, perm, PermutedAxis::Columns))
+    }
+}
+
+impl<Item: Scalar, Data: DataContainerMut<Item = Item>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, BaseMatrix<Item, Data, RS, CS>, RS, CS>
+{
+    /// Permute the rows of the matrix in place.
+    pub fn permute_rows(&mut self, perm: &Permutation) {
+        let dim = self.layout().dim();
+        assert_permutation_length(dim, perm, PermutedAxis::Rows);
+        perm.for_each_swap(|first, second| {
+            for col in 0..dim.1 {
+                unsafe { self.swap_unchecked((first, col), (second, col)) };
+            }
+        });
+    }
+
+    /// Permute the columns of the matrix in place.
+    pub fn permute_columns(&mut self, perm: &Permutation) {
+        let dim = self.layout().dim();
+        assert_permutation_length(dim, perm, PermutedAxis::Columns);
+        perm.for_each_swap(|first, second| {
+            for row in 0..dim.0 {
+                unsafe { self.swap_unchecked((row, first), (row, second)) };
+            }
+        });
+    }
+
+    unsafe fn swap_unchecked(
+        &mut self,
+        first: (IndexType, IndexType),
+        second: (IndexType, IndexType),
+    ) {
+        let value = self.get_value_unchecked(first.0, first.1);
+        *self.get_unchecked_mut(first.0, first.1) = self.get_value_unchecked(second.0, second.1);
+        *self.get_unchecked_mut(second.0, second.1) = value;
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::{ColumnVectorD, MatrixD};
+    use alloc::vec;
+
+    fn example_matrix() -> MatrixD<f64> {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(4, 3);
+        for row in 0..4 {
+            for col in 0..3 {
+                mat[[row, col]] = (10 * row + col) as f64;
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn test_permute_rows() {
+        let perm = Permutation::from_indices(vec![3, 0, 1, 2]).unwrap();
+        let mut mat = example_matrix();
+        mat.permute_rows(&perm);
+        let lazy = example_matrix().permuted_rows(&perm).eval();
+
+        for row in 0..4 {
+            for col in 0..3 {
+                let expected = (10 * perm.get(row) + col) as f64;
+                assert_eq!(mat[[row, col]], expected);
+                assert_eq!(lazy[[row, col]], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permute_columns() {
+        let perm = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+        let mut mat = example_matrix();
+        mat.permute_columns(&perm);
+        let lazy = example_matrix().permuted_columns(&perm).eval();
+
+        for row in 0..4 {
+            for col in 0..3 {
+                let expected = (10 * row + perm.get(col)) as f64;
+                assert_eq!(mat[[row, col]], expected);
+                assert_eq!(lazy[[row, col]], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permute_vector() {
+        let perm = Permutation::from_indices(vec![1, 2, 0]).unwrap();
+        let mut vec = ColumnVectorD::<f64>::zeros_from_length(3);
+        vec[[0, 0]] = 1.0;
+        vec[[1, 0]] = 2.0;
+        vec[[2, 0]] = 3.0;
+
+        vec.permute_rows(&perm);
+
+        assert_eq!(vec[[0, 0]], 2.0);
+        assert_eq!(vec[[1, 0]], 3.0);
+        assert_eq!(vec[[2, 0]], 1.0);
+    }
+}
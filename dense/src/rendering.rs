@@ -0,0 +1,117 @@
+//! Rendering of dense matrices to images and text.
+//!
+//! The [sparsity_pattern](Matrix::sparsity_pattern) method renders the positions of the
+//! nonzero entries of a matrix, and [heatmap](Matrix::heatmap) renders the magnitudes of its
+//! entries on a logarithmic scale. The resulting [Rendering] can be printed as text or saved
+//! as a PGM, PPM or SVG image.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! let mut mat = MatrixD::<f64>::zeros_from_dim(4, 4);
+//! for index in 0..4 {
+//!     mat[[index, index]] = 1.0;
+//! }
+//!
+//! let rendering = mat.sparsity_pattern(2);
+//! assert_eq!(rendering.shape(), (2, 2));
+//! assert_eq!(rendering.to_text(), "▓ \n ▓\n");
+//! ```
+
+use crate::matrix::Matrix;
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use num::{ToPrimitive, Zero};
+
+pub use rlst_common::rendering::Rendering;
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, MatImpl, RS, CS>
+{
+    /// Render the positions of the nonzero entries, downsampled to at most `max_size`
+    /// pixels along each side.
+    pub fn sparsity_pattern(&self, max_size: IndexType) -> Rendering {
+        Rendering::sparsity_pattern(
+            self.layout().dim(),
+            max_size,
+            self.entries()
+                .filter(|&(_, _, value)| value != <Item as Zero>::zero())
+                .map(|(row, col, _)| (row, col)),
+        )
+    }
+
+    /// Render a log-scaled heatmap of the magnitudes of the entries, downsampled to at most
+    /// `max_size` pixels along each side.
+    pub fn heatmap(&self, max_size: IndexType) -> Rendering {
+        Rendering::heatmap(
+            self.layout().dim(),
+            max_size,
+            self.entries()
+                .map(|(row, col, value)| (row, col, value.abs().to_f64().unwrap())),
+        )
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (IndexType, IndexType, Item)> + '_ {
+        let (rows, cols) = self.layout().dim();
+        (0..rows).flat_map(move |row| {
+            (0..cols).map(move |col| (row, col, unsafe { self.get_value_unchecked(row, col) }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::matrix::MatrixD;
+    use crate::types::c64;
+
+    #[test]
+    fn test_sparsity_pattern() {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(5, 3);
+        mat[[0, 0]] = 1.0;
+        mat[[4, 2]] = -2.0;
+
+        let rendering = mat.sparsity_pattern(10);
+        assert_eq!(rendering.shape(), (5, 3));
+        assert_eq!(rendering.intensity(0, 0), 1.0);
+        assert_eq!(rendering.intensity(4, 2), 1.0);
+        assert_eq!(rendering.intensity(2, 1), 0.0);
+
+        // Downsampling keeps the aspect ratio.
+        let rendering = mat.sparsity_pattern(2);
+        assert_eq!(rendering.block_size(), 3);
+        assert_eq!(rendering.shape(), (2, 1));
+        assert!(rendering.intensity(0, 0) > 0.0);
+        assert!(rendering.intensity(0, 0) < 1.0);
+        assert_eq!(rendering.to_text().lines().count(), 2);
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let mut mat = MatrixD::<c64>::zeros_from_dim(2, 3);
+        mat[[0, 0]] = c64::new(0.0, 1000.0);
+        mat[[0, 2]] = c64::new(10.0, 0.0);
+        mat[[1, 1]] = c64::new(0.1, 0.0);
+
+        let rendering = mat.heatmap(3);
+        assert_eq!(rendering.intensity(0, 0), 1.0);
+        assert_eq!(rendering.intensity(1, 0), 0.0);
+        assert!(rendering.intensity(1, 1) > 0.0);
+        assert!(rendering.intensity(1, 1) < rendering.intensity(0, 2));
+
+        let mut pgm = Vec::new();
+        rendering.write_pgm(&mut pgm).unwrap();
+        assert!(pgm.starts_with(b"P5\n3 2\n255\n"));
+        assert_eq!(pgm.len(), 11 + 6);
+
+        let mut ppm = Vec::new();
+        rendering.write_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm.len(), 11 + 18);
+
+        let mut svg = Vec::new();
+        rendering.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches("width=\"1\"").count(), 3);
+
+        assert!(rendering.save("matrix.png").is_err());
+    }
+}
@@ -9,7 +9,7 @@ use crate::matrix_ref::MatrixRef;
 use crate::traits::*;
 use crate::types::{c32, c64, IndexType, Scalar};
 use crate::{matrix::*, DefaultLayout};
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// This type represents the multiplication of a matrix with a scalar.
 pub type ScalarProdMat<Item, MatImpl, RS, CS> =
@@ -77,7 +77,7 @@ impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: S
 macro_rules! scalar_mult_impl {
     ($Scalar:ty) => {
         impl<MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
-            std::ops::Mul<Matrix<$Scalar, MatImpl, RS, CS>> for $Scalar
+            core::ops::Mul<Matrix<$Scalar, MatImpl, RS, CS>> for $Scalar
         {
             type Output = ScalarProdMat<$Scalar, MatImpl, RS, CS>;
 
@@ -87,7 +87,7 @@ macro_rules! scalar_mult_impl {
         }
 
         impl<'a, MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
-            std::ops::Mul<&'a Matrix<$Scalar, MatImpl, RS, CS>> for $Scalar
+            core::ops::Mul<&'a Matrix<$Scalar, MatImpl, RS, CS>> for $Scalar
         {
             type Output = ScalarProdMat<$Scalar, MatrixRef<'a, $Scalar, MatImpl, RS, CS>, RS, CS>;
 
@@ -97,7 +97,7 @@ macro_rules! scalar_mult_impl {
         }
 
         impl<MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
-            std::ops::Mul<$Scalar> for Matrix<$Scalar, MatImpl, RS, CS>
+            core::ops::Mul<$Scalar> for Matrix<$Scalar, MatImpl, RS, CS>
         {
             type Output = ScalarProdMat<$Scalar, MatImpl, RS, CS>;
 
@@ -107,7 +107,7 @@ macro_rules! scalar_mult_impl {
         }
 
         impl<'a, MatImpl: MatrixTrait<$Scalar, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
-            std::ops::Mul<$Scalar> for &'a Matrix<$Scalar, MatImpl, RS, CS>
+            core::ops::Mul<$Scalar> for &'a Matrix<$Scalar, MatImpl, RS, CS>
         {
             type Output = ScalarProdMat<$Scalar, MatrixRef<'a, $Scalar, MatImpl, RS, CS>, RS, CS>;
 
@@ -0,0 +1,550 @@
+//! Three-dimensional tensors.
+//!
+//! A [Tensor] is a rank-3 array stored in any [DataContainer]. Its memory layout
+//! is described by a [Layout3d], the three-dimensional analogue of
+//! [DefaultLayout](crate::DefaultLayout). The entry `(i, j, k)` is stored at raw
+//! position `i * stride.0 + j * stride.1 + k * stride.2`.
+//! Tensors created with [Tensor3D::zeros_from_shape] are stored contiguously in column-major
+//! order, i.e. the first index runs fastest.
+//!
+//! The following operations are supported.
+//! - [Tensor::slice] and [Tensor::slice_mut] fix one index and return a strided
+//!   [SliceMatrix] view onto the remaining two axes.
+//! - [Tensor::permute_axes] reorders the axes without copying data.
+//! - [Tensor::mode_product] contracts one axis with a matrix.
+//! - [Tensor::to_matrix] and [Tensor3D::from_matrix] reshape between tensors and [MatrixD].
+//!   Both use column-major ordering of the elements.
+//!
+//! ```
+//! # use rlst_dense::*;
+//! # use rlst_dense::tensor::*;
+//! let mut tensor = Tensor3D::<f64>::zeros_from_shape((2, 3, 4));
+//! tensor[[1, 2, 3]] = 5.0;
+//!
+//! // The matrix at the last index 3 along axis 2.
+//! let slice = tensor.slice(2, 3);
+//! assert_eq!(slice.layout().dim(), (2, 3));
+//! assert_eq!(slice[[1, 2]], 5.0);
+//!
+//! // Swap the first and the last axis.
+//! let permuted = tensor.permute_axes([2, 1, 0]);
+//! assert_eq!(permuted.shape(), (4, 3, 2));
+//! assert_eq!(permuted[[3, 2, 1]], 5.0);
+//! ```
+
+use crate::data_container::{
+    DataContainer, DataContainerMut, SliceContainer, SliceContainerMut, VectorContainer,
+};
+use crate::matrix::{Matrix, MatrixD, SliceMatrix, SliceMatrixMut};
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+
+/// Memory layout of a three-dimensional tensor, given by a shape and a stride triple.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout3d {
+    shape: (IndexType, IndexType, IndexType),
+    stride: (IndexType, IndexType, IndexType),
+}
+
+impl Layout3d {
+    /// Create a new layout from a shape and a stride triple.
+    pub fn new(
+        shape: (IndexType, IndexType, IndexType),
+        stride: (IndexType, IndexType, IndexType),
+    ) -> Self {
+        Self { shape, stride }
+    }
+
+    /// Create a contiguous column-major layout for the given shape.
+    pub fn from_shape(shape: (IndexType, IndexType, IndexType)) -> Self {
+        Self::new(shape, (1, shape.0, shape.0 * shape.1))
+    }
+
+    /// Return the shape.
+    pub fn shape(&self) -> (IndexType, IndexType, IndexType) {
+        self.shape
+    }
+
+    /// Return the stride triple.
+    pub fn stride(&self) -> (IndexType, IndexType, IndexType) {
+        self.stride
+    }
+
+    /// Return the number of elements.
+    pub fn number_of_elements(&self) -> IndexType {
+        self.shape.0 * self.shape.1 * self.shape.2
+    }
+
+    /// Convert a multi-index into a raw memory index.
+    #[inline]
+    pub fn convert_3d_raw(&self, i: IndexType, j: IndexType, k: IndexType) -> IndexType {
+        i * self.stride.0 + j * self.stride.1 + k * self.stride.2
+    }
+
+    /// Convert a column-major 1d index into a multi-index.
+    #[inline]
+    pub fn convert_1d_3d(&self, index: IndexType) -> (IndexType, IndexType, IndexType) {
+        let i = index % self.shape.0;
+        let rest = index / self.shape.0;
+        (i, rest % self.shape.1, rest / self.shape.1)
+    }
+
+    /// Return the number of raw memory locations spanned by the tensor.
+    pub fn extent(&self) -> IndexType {
+        if self.number_of_elements() == 0 {
+            0
+        } else {
+            self.convert_3d_raw(self.shape.0 - 1, self.shape.1 - 1, self.shape.2 - 1) + 1
+        }
+    }
+
+    /// Return the layout with axes reordered by `perm`.
+    ///
+    /// Axis `n` of the new layout is axis `perm[n]` of the old layout.
+    pub fn permute(&self, perm: [usize; 3]) -> Self {
+        let mut sorted = perm;
+        sorted.sort_unstable();
+        assert_eq!(
+            sorted,
+            [0, 1, 2],
+            "{:?} is not a permutation of the axes",
+            perm
+        );
+
+        let shape = [self.shape.0, self.shape.1, self.shape.2];
+        let stride = [self.stride.0, self.stride.1, self.stride.2];
+        Self::new(
+            (shape[perm[0]], shape[perm[1]], shape[perm[2]]),
+            (stride[perm[0]], stride[perm[1]], stride[perm[2]]),
+        )
+    }
+
+    /// Return the raw offset, dimension and stride of the matrix obtained
+    /// by fixing `index` along `axis`.
+    ///
+    /// The offset of an empty matrix is zero, since an empty tensor may not
+    /// own any memory that the offset could point into.
+    fn slice_layout(
+        &self,
+        axis: usize,
+        index: IndexType,
+    ) -> (IndexType, (IndexType, IndexType), (IndexType, IndexType)) {
+        let (shape, stride) = (self.shape, self.stride);
+        let (len, offset, dim, mat_stride) = match axis {
+            0 => (
+                shape.0,
+                index * stride.0,
+                (shape.1, shape.2),
+                (stride.1, stride.2),
+            ),
+            1 => (
+                shape.1,
+                index * stride.1,
+                (shape.0, shape.2),
+                (stride.0, stride.2),
+            ),
+            2 => (
+                shape.2,
+                index * stride.2,
+                (shape.0, shape.1),
+                (stride.0, stride.1),
+            ),
+            _ => panic!("Axis {} out of bounds for a tensor of rank 3", axis),
+        };
+        assert!(
+            index < len,
+            "Index {} out of bounds for axis {} of length {}",
+            index,
+            axis,
+            len
+        );
+        if dim.0 * dim.1 == 0 {
+            (0, dim, mat_stride)
+        } else {
+            (offset, dim, mat_stride)
+        }
+    }
+}
+
+/// A three-dimensional tensor.
+pub struct Tensor<Item: Scalar, Data: DataContainer<Item = Item>> {
+    data: Data,
+    layout: Layout3d,
+}
+
+/// A three-dimensional tensor that owns its memory.
+pub type Tensor3D<Item> = Tensor<Item, VectorContainer<Item>>;
+
+/// A three-dimensional tensor that references a memory slice.
+pub type SliceTensor<'a, Item> = Tensor<Item, SliceContainer<'a, Item>>;
+
+/// Like [SliceTensor] but with mutable access.
+pub type SliceTensorMut<'a, Item> = Tensor<Item, SliceContainerMut<'a, Item>>;
+
+impl<Item: Scalar, Data: DataContainer<Item = Item>> Tensor<Item, Data> {
+    /// Create a new tensor from a data container and a layout.
+    pub fn new(data: Data, layout: Layout3d) -> Self {
+        assert!(
+            layout.extent() <= data.number_of_elements(),
+            "Number of elements in data: {}. But tensor layout requires {} elements.",
+            data.number_of_elements(),
+            layout.extent()
+        );
+        Self { data, layout }
+    }
+
+    /// Return the layout.
+    pub fn layout(&self) -> &Layout3d {
+        &self.layout
+    }
+
+    /// Return the shape.
+    pub fn shape(&self) -> (IndexType, IndexType, IndexType) {
+        self.layout.shape
+    }
+
+    /// Return the element at position `(i, j, k)` or `None` if out of bounds.
+    pub fn get_value(&self, i: IndexType, j: IndexType, k: IndexType) -> Option<Item> {
+        self.get(i, j, k).copied()
+    }
+
+    /// Return a reference to the element at position `(i, j, k)` or `None` if out of bounds.
+    pub fn get(&self, i: IndexType, j: IndexType, k: IndexType) -> Option<&Item> {
+        let shape = self.layout.shape;
+        if i < shape.0 && j < shape.1 && k < shape.2 {
+            Some(unsafe { self.data.get_unchecked(self.layout.convert_3d_raw(i, j, k)) })
+        } else {
+            None
+        }
+    }
+
+    /// Return a matrix view onto the tensor with `index` fixed along `axis`.
+    ///
+    /// The rows and columns of the matrix are the two remaining axes in their original order.
+    /// Panics if `index` is out of bounds, which is always the case for an axis of length
+    /// zero. Slices along the other axes of an empty tensor are empty matrices.
+    pub fn slice(&self, axis: usize, index: IndexType) -> SliceMatrix<'_, Item, Dynamic, Dynamic> {
+        let (offset, dim, stride) = self.layout.slice_layout(axis, index);
+        unsafe { SliceMatrix::from_pointer(self.data.get_pointer().add(offset), dim, stride) }
+    }
+
+    /// Reorder the axes without copying data.
+    ///
+    /// Axis `n` of the returned tensor is axis `perm[n]` of `self`.
+    pub fn permute_axes(self, perm: [usize; 3]) -> Self {
+        let layout = self.layout.permute(perm);
+        Self {
+            data: self.data,
+            layout,
+        }
+    }
+
+    /// Return a view onto the tensor.
+    pub fn view(&self) -> SliceTensor<'_, Item> {
+        SliceTensor::new(
+            SliceContainer::new(self.data.get_slice(0, self.layout.extent())),
+            self.layout,
+        )
+    }
+
+    /// Copy the tensor into a new contiguous column-major tensor.
+    pub fn eval(&self) -> Tensor3D<Item> {
+        let mut result = Tensor3D::zeros_from_shape(self.shape());
+        for index in 0..self.layout.number_of_elements() {
+            let (i, j, k) = self.layout.convert_1d_3d(index);
+            result[[i, j, k]] = self[[i, j, k]];
+        }
+        result
+    }
+
+    /// Reshape the tensor into a matrix of dimension `dim`.
+    ///
+    /// The elements are taken in column-major order from the tensor and stored
+    /// in column-major order in the matrix.
+    pub fn to_matrix(&self, dim: (IndexType, IndexType)) -> MatrixD<Item> {
+        assert_eq!(
+            dim.0 * dim.1,
+            self.layout.number_of_elements(),
+            "Cannot reshape tensor of shape {:?} into matrix of dimension {:?}",
+            self.shape(),
+            dim
+        );
+        let mut result = MatrixD::<Item>::zeros_from_dim(dim.0, dim.1);
+        for index in 0..self.layout.number_of_elements() {
+            let (i, j, k) = self.layout.convert_1d_3d(index);
+            result[[index % dim.0, index / dim.0]] = self[[i, j, k]];
+        }
+        result
+    }
+
+    /// Contract `axis` with the columns of `mat`.
+    ///
+    /// The result has the same shape as `self` except that the length of `axis` becomes
+    /// the number of rows of `mat`. For `axis = 0` this computes
+    /// `result[r, j, k] = sum_l mat[r, l] * self[l, j, k]`, and analogously for the other axes.
+    pub fn mode_product<MatImpl, RS, CS>(
+        &self,
+        axis: usize,
+        mat: &Matrix<Item, MatImpl, RS, CS>,
+    ) -> Tensor3D<Item>
+    where
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    {
+        let shape = [self.shape().0, self.shape().1, self.shape().2];
+        assert!(
+            axis < 3,
+            "Axis {} out of bounds for a tensor of rank 3",
+            axis
+        );
+        let (rows, cols) = mat.layout().dim();
+        assert_eq!(
+            cols, shape[axis],
+            "Matrix has {} columns but axis {} of the tensor has length {}",
+            cols, axis, shape[axis]
+        );
+
+        let mut result_shape = shape;
+        result_shape[axis] = rows;
+        let mut result =
+            Tensor3D::zeros_from_shape((result_shape[0], result_shape[1], result_shape[2]));
+
+        for index in 0..result.layout.number_of_elements() {
+            let (i, j, k) = result.layout.convert_1d_3d(index);
+            let mut multi_index = [i, j, k];
+            let row = multi_index[axis];
+            let mut sum = <Item as num::Zero>::zero();
+            for col in 0..cols {
+                multi_index[axis] = col;
+                sum += unsafe { mat.get_value_unchecked(row, col) }
+                    * self[[multi_index[0], multi_index[1], multi_index[2]]];
+            }
+            result[[i, j, k]] = sum;
+        }
+        result
+    }
+}
+
+impl<Item: Scalar, Data: DataContainerMut<Item = Item>> Tensor<Item, Data> {
+    /// Return a mutable reference to the element at position `(i, j, k)` or `None` if out of bounds.
+    pub fn get_mut(&mut self, i: IndexType, j: IndexType, k: IndexType) -> Option<&mut Item> {
+        let shape = self.layout.shape;
+        if i < shape.0 && j < shape.1 && k < shape.2 {
+            Some(unsafe {
+                self.data
+                    .get_unchecked_mut(self.layout.convert_3d_raw(i, j, k))
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Return a mutable matrix view onto the tensor with `index` fixed along `axis`.
+    pub fn slice_mut(
+        &mut self,
+        axis: usize,
+        index: IndexType,
+    ) -> SliceMatrixMut<'_, Item, Dynamic, Dynamic> {
+        let (offset, dim, stride) = self.layout.slice_layout(axis, index);
+        unsafe {
+            SliceMatrixMut::from_pointer(self.data.get_pointer_mut().add(offset), dim, stride)
+        }
+    }
+
+    /// Return a mutable view onto the tensor.
+    pub fn view_mut(&mut self) -> SliceTensorMut<'_, Item> {
+        let extent = self.layout.extent();
+        SliceTensorMut::new(
+            SliceContainerMut::new(self.data.get_slice_mut(0, extent)),
+            self.layout,
+        )
+    }
+}
+
+impl<Item: Scalar> Tensor3D<Item> {
+    /// Create a new zero tensor with the given shape.
+    pub fn zeros_from_shape(shape: (IndexType, IndexType, IndexType)) -> Self {
+        let layout = Layout3d::from_shape(shape);
+        Self::new(VectorContainer::new(layout.number_of_elements()), layout)
+    }
+
+    /// Reshape a matrix into a tensor of the given shape.
+    ///
+    /// The elements are taken in column-major order from the matrix and stored
+    /// in column-major order in the tensor.
+    pub fn from_matrix<MatImpl, RS, CS>(
+        mat: &Matrix<Item, MatImpl, RS, CS>,
+        shape: (IndexType, IndexType, IndexType),
+    ) -> Self
+    where
+        MatImpl: MatrixTrait<Item, RS, CS>,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    {
+        let dim = mat.layout().dim();
+        let mut result = Self::zeros_from_shape(shape);
+        assert_eq!(
+            dim.0 * dim.1,
+            result.layout.number_of_elements(),
+            "Cannot reshape matrix of dimension {:?} into tensor of shape {:?}",
+            dim,
+            shape
+        );
+        for index in 0..result.layout.number_of_elements() {
+            let (i, j, k) = result.layout.convert_1d_3d(index);
+            result[[i, j, k]] = unsafe { mat.get_value_unchecked(index % dim.0, index / dim.0) };
+        }
+        result
+    }
+}
+
+impl<Item: Scalar, Data: DataContainer<Item = Item>> core::ops::Index<[IndexType; 3]>
+    for Tensor<Item, Data>
+{
+    type Output = Item;
+
+    fn index(&self, index: [IndexType; 3]) -> &Self::Output {
+        self.get(index[0], index[1], index[2]).unwrap()
+    }
+}
+
+impl<Item: Scalar, Data: DataContainerMut<Item = Item>> core::ops::IndexMut<[IndexType; 3]>
+    for Tensor<Item, Data>
+{
+    fn index_mut(&mut self, index: [IndexType; 3]) -> &mut Self::Output {
+        self.get_mut(index[0], index[1], index[2]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    #[cfg(feature = "std")]
+    use approx::assert_relative_eq;
+
+    fn example_tensor() -> Tensor3D<f64> {
+        let mut tensor = Tensor3D::<f64>::zeros_from_shape((2, 3, 4));
+        for index in 0..24 {
+            let (i, j, k) = tensor.layout().convert_1d_3d(index);
+            tensor[[i, j, k]] = index as f64;
+        }
+        tensor
+    }
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout3d::from_shape((2, 3, 4));
+        assert_eq!(layout.stride(), (1, 2, 6));
+        assert_eq!(layout.convert_3d_raw(1, 2, 3), 23);
+        assert_eq!(layout.convert_1d_3d(23), (1, 2, 3));
+        assert_eq!(layout.extent(), 24);
+
+        let permuted = layout.permute([1, 2, 0]);
+        assert_eq!(permuted.shape(), (3, 4, 2));
+        assert_eq!(permuted.stride(), (2, 6, 1));
+    }
+
+    #[test]
+    fn test_slice() {
+        let tensor = example_tensor();
+        for axis in 0..3 {
+            let len = [2, 3, 4][axis];
+            for index in 0..len {
+                let slice = tensor.slice(axis, index);
+                let (rows, cols) = slice.layout().dim();
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let expected = match axis {
+                            0 => tensor[[index, row, col]],
+                            1 => tensor[[row, index, col]],
+                            _ => tensor[[row, col, index]],
+                        };
+                        assert_eq!(slice[[row, col]], expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_mut() {
+        let mut tensor = example_tensor();
+        tensor.slice_mut(1, 2)[[1, 3]] = -1.0;
+        assert_eq!(tensor[[1, 2, 3]], -1.0);
+    }
+
+    #[test]
+    fn test_slice_empty() {
+        let mut tensor = Tensor3D::<f64>::zeros_from_shape((2, 0, 4));
+        assert_eq!(tensor.slice(0, 1).layout().dim(), (0, 4));
+        assert_eq!(tensor.slice_mut(2, 3).layout().dim(), (2, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_empty_axis() {
+        let tensor = Tensor3D::<f64>::zeros_from_shape((2, 0, 4));
+        tensor.slice(1, 0);
+    }
+
+    #[test]
+    fn test_permute_axes() {
+        let tensor = example_tensor();
+        let expected = example_tensor();
+        let permuted = tensor.permute_axes([2, 0, 1]);
+        assert_eq!(permuted.shape(), (4, 2, 3));
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    assert_eq!(permuted[[k, i, j]], expected[[i, j, k]]);
+                }
+            }
+        }
+
+        let evaluated = permuted.eval();
+        assert_eq!(evaluated.layout().stride(), (1, 4, 8));
+        assert_eq!(evaluated[[3, 1, 2]], expected[[1, 2, 3]]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_mode_product() {
+        let tensor = example_tensor();
+        use rand::SeedableRng;
+
+        let mut mat = MatrixD::<f64>::zeros_from_dim(5, 3);
+        mat.fill_from_rand_standard_normal(&mut rand::rngs::StdRng::seed_from_u64(0));
+
+        let result = tensor.mode_product(1, &mat);
+        assert_eq!(result.shape(), (2, 5, 4));
+
+        for i in 0..2 {
+            for r in 0..5 {
+                for k in 0..4 {
+                    let mut expected = 0.0;
+                    for l in 0..3 {
+                        expected += mat[[r, l]] * tensor[[i, l, k]];
+                    }
+                    assert_relative_eq!(result[[i, r, k]], expected, epsilon = 1E-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_reshape() {
+        let tensor = example_tensor();
+        let mat = tensor.to_matrix((6, 4));
+        for index in 0..24 {
+            assert_eq!(mat[[index % 6, index / 6]], index as f64);
+        }
+
+        let back = Tensor3D::from_matrix(&mat, (2, 3, 4));
+        for index in 0..24 {
+            let (i, j, k) = back.layout().convert_1d_3d(index);
+            assert_eq!(back[[i, j, k]], tensor[[i, j, k]]);
+        }
+    }
+}
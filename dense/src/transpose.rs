@@ -0,0 +1,151 @@
+//! Lazy transpose of a matrix.
+//!
+//! The [transpose](Matrix::transpose) method returns a matrix whose implementation holds
+//! the original matrix and swaps row and column indices on element access. No data is
+//! copied. If the original matrix is backed by strided memory the transpose is passed
+//! directly to the multiplication kernel in products with [Dot](crate::Dot::dot).
+//!
+//! ```
+//! # use rlst_dense::*;
+//! let mut mat = MatrixD::<f64>::zeros_from_dim(2, 3);
+//! mat[[0, 2]] = 1.0;
+//!
+//! let transpose = Matrix::from_ref(&mat).transpose();
+//! assert_eq!(transpose.layout().dim(), (3, 2));
+//! assert_eq!(transpose.get_value(2, 0), 1.0);
+//! ```
+
+use crate::matrix::Matrix;
+use crate::matrix_multiply::MatMulOperand;
+use crate::traits::*;
+use crate::types::{IndexType, Scalar};
+use crate::DefaultLayout;
+use core::marker::PhantomData;
+
+/// This type represents the transpose of a matrix.
+pub type TransposeMat<Item, MatImpl, RS, CS> =
+    Matrix<Item, Transpose<Item, MatImpl, RS, CS>, CS, RS>;
+
+/// A structure holding a matrix and the layout of its transpose.
+/// On random access the row and column indices are swapped.
+pub struct Transpose<Item, MatImpl, RS, CS>(
+    Matrix<Item, MatImpl, RS, CS>,
+    DefaultLayout,
+    PhantomData<Item>,
+)
+where
+    Item: Scalar,
+    RS: SizeIdentifier,
+    CS: SizeIdentifier,
+    MatImpl: MatrixTrait<Item, RS, CS>;
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Transpose<Item, MatImpl, RS, CS>
+{
+    pub fn new(mat: Matrix<Item, MatImpl, RS, CS>) -> Self {
+        let dim = mat.layout().dim();
+        let stride = mat.layout().stride();
+        let layout = DefaultLayout::new((dim.1, dim.0), (stride.1, stride.0));
+        Self(mat, layout, PhantomData)
+    }
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    SizeType for Transpose<Item, MatImpl, RS, CS>
+{
+    type R = CS;
+    type C = RS;
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Layout for Transpose<Item, MatImpl, RS, CS>
+{
+    type Impl = DefaultLayout;
+
+    #[inline]
+    fn layout(&self) -> &Self::Impl {
+        &self.1
+    }
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    UnsafeRandomAccessByValue for Transpose<Item, MatImpl, RS, CS>
+{
+    type Item = Item;
+
+    #[inline]
+    unsafe fn get_value_unchecked(&self, row: IndexType, col: IndexType) -> Self::Item {
+        self.0.get_value_unchecked(col, row)
+    }
+
+    #[inline]
+    unsafe fn get1d_value_unchecked(&self, index: IndexType) -> Self::Item {
+        let (row, col) = self.1.convert_1d_2d(index);
+        self.get_value_unchecked(row, col)
+    }
+}
+
+impl<
+        Item: Scalar,
+        MatImpl: MatrixTrait<Item, RS, CS> + MatMulOperand,
+        RS: SizeIdentifier,
+        CS: SizeIdentifier,
+    > MatMulOperand for Transpose<Item, MatImpl, RS, CS>
+{
+    #[inline]
+    fn strided_pointer(&self) -> Option<*const Item> {
+        self.0.strided_pointer()
+    }
+}
+
+impl<Item: Scalar, MatImpl: MatrixTrait<Item, RS, CS>, RS: SizeIdentifier, CS: SizeIdentifier>
+    Matrix<Item, MatImpl, RS, CS>
+{
+    /// Lazily transpose the matrix.
+    pub fn transpose(self) -> TransposeMat<Item, MatImpl, RS, CS> {
+        Matrix::new(Transpose::new(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::matrix::{Matrix23, MatrixD};
+    use crate::Dot;
+
+    #[test]
+    fn test_transpose() {
+        let mut mat = MatrixD::<f64>::zeros_from_dim(3, 2);
+        mat[[0, 1]] = 1.0;
+        mat[[2, 0]] = 2.0;
+
+        let transpose = Matrix::from_ref(&mat).transpose();
+        assert_eq!(transpose.layout().dim(), (2, 3));
+        assert_eq!(transpose.get_value(1, 0), 1.0);
+        assert_eq!(transpose.get_value(0, 2), 2.0);
+
+        let evaluated = (2.0 * &mat).transpose().eval();
+        assert_eq!(evaluated[[1, 0]], 2.0);
+        assert_eq!(evaluated[[0, 2]], 4.0);
+    }
+
+    #[test]
+    fn test_transpose_dot() {
+        let mut mat = Matrix23::<f64>::zeros_from_dim();
+        for row in 0..2 {
+            for col in 0..3 {
+                mat[[row, col]] = (1 + row + 2 * col) as f64;
+            }
+        }
+
+        let gram = Matrix::from_ref(&mat).transpose().dot(&mat);
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let expected: f64 = (0..2).map(|k| mat[[k, row]] * mat[[k, col]]).sum();
+                assert_eq!(gram[[row, col]], expected);
+            }
+        }
+    }
+}
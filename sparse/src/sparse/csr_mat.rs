@@ -1,6 +1,8 @@
 //! Definition of CSR matrices.
 
 use crate::sparse::SparseMatType;
+use num::ToPrimitive;
+use rlst_common::rendering::Rendering;
 use rlst_common::types::RlstResult;
 
 use rlst_common::types::{IndexType, Scalar};
@@ -100,6 +102,34 @@ impl<T: Scalar> CsrMatrix<T> {
 
         Ok(Self::new(shape, indices, indptr, new_data))
     }
+
+    /// Render the positions of the stored entries, downsampled to at most `max_size`
+    /// pixels along each side.
+    pub fn sparsity_pattern(&self, max_size: IndexType) -> Rendering {
+        Rendering::sparsity_pattern(
+            self.shape,
+            max_size,
+            self.entries().map(|(row, col, _)| (row, col)),
+        )
+    }
+
+    /// Render a log-scaled heatmap of the magnitudes of the stored entries, downsampled to
+    /// at most `max_size` pixels along each side.
+    pub fn heatmap(&self, max_size: IndexType) -> Rendering {
+        Rendering::heatmap(
+            self.shape,
+            max_size,
+            self.entries()
+                .map(|(row, col, value)| (row, col, value.abs().to_f64().unwrap())),
+        )
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (IndexType, IndexType, T)> + '_ {
+        (0..self.shape.0).flat_map(move |row| {
+            (self.indptr[row]..self.indptr[1 + row])
+                .map(move |index| (row, self.indices[index], self.data[index]))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -153,4 +183,29 @@ mod test {
         assert_eq!(res[0], 35.0);
         assert_eq!(res[1], 79.0);
     }
+
+    #[test]
+    fn test_csr_rendering() {
+        // Test the matrix [[1, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 100]]
+        let rows = vec![0, 3];
+        let cols = vec![0, 3];
+        let data = vec![1.0, 100.0];
+
+        let csr = CsrMatrix::from_aij((4, 4), &rows, &cols, &data).unwrap();
+
+        let pattern = csr.sparsity_pattern(4);
+        assert_eq!(pattern.shape(), (4, 4));
+        assert_eq!(pattern.intensity(0, 0), 1.0);
+        assert_eq!(pattern.intensity(3, 3), 1.0);
+        assert_eq!(pattern.intensity(1, 2), 0.0);
+
+        let pattern = csr.sparsity_pattern(2);
+        assert_eq!(pattern.shape(), (2, 2));
+        assert_eq!(pattern.to_text(), "▒ \n ▒\n");
+
+        let heatmap = csr.heatmap(4);
+        assert_eq!(heatmap.intensity(3, 3), 1.0);
+        assert!(heatmap.intensity(0, 0) > 0.0);
+        assert!(heatmap.intensity(0, 0) < 1.0);
+    }
 }
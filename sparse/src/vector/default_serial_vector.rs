@@ -2,6 +2,7 @@
 use crate::traits::index_layout::IndexLayout;
 use crate::traits::indexable_vector::*;
 use num::{Float, Zero};
+use rlst_common::permutation::Permutation;
 use rlst_common::types::Scalar;
 use rlst_common::types::{IndexType, RlstError, RlstResult};
 
@@ -27,6 +28,19 @@ impl<T: Scalar> DefaultSerialVector<T> {
             index_layout: DefaultSerialIndexLayout::new(size),
         }
     }
+
+    /// Permute the vector in place.
+    ///
+    /// Entry `i` of the result is entry `perm.get(i)` of the original vector.
+    pub fn permute(&mut self, perm: &Permutation) -> RlstResult<()> {
+        if perm.len() != self.index_layout().number_of_global_indices() {
+            return Err(RlstError::IndexLayoutError(
+                "Permutation in `permute` must have the length of the vector".to_string(),
+            ));
+        }
+        perm.apply_in_place(&mut self.data);
+        Ok(())
+    }
 }
 
 impl<T: Scalar> IndexableVector for DefaultSerialVector<T> {
@@ -272,6 +286,20 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_permute() {
+        let mut vec = new_vec::<f64>(3);
+        for (index, elem) in vec.view_mut().unwrap().iter_mut().enumerate() {
+            *elem = index as f64;
+        }
+
+        let perm = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+        vec.permute(&perm).unwrap();
+        assert_eq!(vec.view().unwrap().data(), &[2.0, 0.0, 1.0]);
+
+        assert!(vec.permute(&Permutation::identity(2)).is_err());
+    }
+
     #[test]
     fn abs_square_sum() {
         let mut vec = new_vec::<c64>(VEC_SIZE);